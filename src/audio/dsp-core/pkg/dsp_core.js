@@ -1,6 +1,231 @@
 /* @ts-self-types="./dsp_core.d.ts" */
 
+/**
+ * @enum {0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11 | 12 | 13 | 14 | 15 | 16 | 17 | 18 | 19 | 20 | 21 | 22 | 23 | 24}
+ */
+export const BioType = Object.freeze({
+    Chirp: 0, "0": "Chirp",
+    SnappingShrimp: 1, "1": "SnappingShrimp",
+    WhaleMoan: 2, "2": "WhaleMoan",
+    DolphinWhistle: 3, "3": "DolphinWhistle",
+    EcholocationClick: 4, "4": "EcholocationClick",
+    HumpbackSong: 5, "5": "HumpbackSong",
+    BlueWhale: 6, "6": "BlueWhale",
+    FinWhale: 7, "7": "FinWhale",
+    SpermWhaleClick: 8, "8": "SpermWhaleClick",
+    OrcaCall: 9, "9": "OrcaCall",
+    BelugaCall: 10, "10": "BelugaCall",
+    FishChorus: 11, "11": "FishChorus",
+    HerringSchool: 12, "12": "HerringSchool",
+    HelicopterRotor: 13, "13": "HelicopterRotor",
+    FixedWingAircraft: 14, "14": "FixedWingAircraft",
+    JetAircraft: 15, "15": "JetAircraft",
+    AmbientOcean: 16, "16": "AmbientOcean",
+    Precipitation: 17, "17": "Precipitation",
+    IceNoise: 18, "18": "IceNoise",
+    GeologicalNoise: 19, "19": "GeologicalNoise",
+    MinkePulse: 20, "20": "MinkePulse",
+    DolphinSchool: 21, "21": "DolphinSchool",
+    HydrothermalVent: 22, "22": "HydrothermalVent",
+    SeismicTPhase: 23, "23": "SeismicTPhase",
+    SurfAgitation: 24, "24": "SurfAgitation",
+});
+
+/**
+ * @enum {0 | 1 | 2}
+ */
+export const ControlCurve = Object.freeze({
+    Linear: 0, "0": "Linear",
+    Exponential: 1, "1": "Exponential",
+    Quadratic: 2, "2": "Quadratic",
+});
+
+export class DemonAnalyzer {
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        DemonAnalyzerFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_demonanalyzer_free(ptr, 0);
+    }
+    /**
+     * @param {number} budget
+     * @returns {boolean}
+     */
+    advance(budget) {
+        const ret = wasm.demonanalyzer_advance(this.__wbg_ptr, budget);
+        return ret !== 0;
+    }
+    /**
+     * @param {Float32Array} input
+     * @param {number} sample_rate
+     * @param {number} max_freq_hz
+     * @param {number} input_band_low_hz
+     * @param {number} input_band_high_hz
+     * @param {number} envelope_hp_hz
+     * @param {number} decimated_rate_target_hz
+     * @returns {number}
+     */
+    analyze(input, sample_rate, max_freq_hz, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz) {
+        const ptr0 = passArrayF32ToWasm0(input, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.demonanalyzer_analyze(this.__wbg_ptr, ptr0, len0, sample_rate, max_freq_hz, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz);
+        return ret >>> 0;
+    }
+    /**
+     * @param {Float32Array} input
+     * @param {number} sample_rate
+     * @param {number} max_freq_hz
+     * @param {number} frame_len
+     * @param {number} hop
+     * @param {number} input_band_low_hz
+     * @param {number} input_band_high_hz
+     * @param {number} envelope_hp_hz
+     * @param {number} decimated_rate_target_hz
+     * @returns {number}
+     */
+    analyze_frames(input, sample_rate, max_freq_hz, frame_len, hop, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz) {
+        const ptr0 = passArrayF32ToWasm0(input, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.demonanalyzer_analyze_frames(this.__wbg_ptr, ptr0, len0, sample_rate, max_freq_hz, frame_len, hop, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    /**
+     * As analyze, but writes the spectrum to `ptr`, a host-owned region of this module's
+     * memory (see alloc_f32_buffer), rather than keeping it: `len` bins, so up to len - 1 Hz.
+     * Fails with OutOfRange for a null, misaligned or empty buffer.
+     *
+     * # Safety
+     *
+     * `ptr` must address `len` writable floats, such as a live alloc_f32_buffer block, that
+     * nothing else reads or writes during the call.
+     * @param {Float32Array} input
+     * @param {number} sample_rate
+     * @param {number} input_band_low_hz
+     * @param {number} input_band_high_hz
+     * @param {number} envelope_hp_hz
+     * @param {number} decimated_rate_target_hz
+     * @param {number} ptr
+     * @param {number} len
+     */
+    analyze_into(input, sample_rate, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz, ptr, len) {
+        const ptr0 = passArrayF32ToWasm0(input, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.demonanalyzer_analyze_into(this.__wbg_ptr, ptr0, len0, sample_rate, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz, ptr, len);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {Float32Array} input
+     * @param {number} sample_rate
+     * @param {number} max_freq_hz
+     * @param {number} input_band_low_hz
+     * @param {number} input_band_high_hz
+     * @param {number} envelope_hp_hz
+     * @param {number} decimated_rate_target_hz
+     */
+    begin(input, sample_rate, max_freq_hz, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz) {
+        const ptr0 = passArrayF32ToWasm0(input, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        wasm.demonanalyzer_begin(this.__wbg_ptr, ptr0, len0, sample_rate, max_freq_hz, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz);
+    }
+    /**
+     * @returns {number}
+     */
+    frame_count() {
+        const ret = wasm.demonanalyzer_frame_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {boolean}
+     */
+    is_complete() {
+        const ret = wasm.demonanalyzer_is_complete(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {number}
+     */
+    memory_bytes() {
+        const ret = wasm.demonanalyzer_memory_bytes(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    min_input_seconds() {
+        const ret = wasm.demonanalyzer_min_input_seconds(this.__wbg_ptr);
+        return ret;
+    }
+    constructor() {
+        const ret = wasm.demonanalyzer_new();
+        this.__wbg_ptr = ret >>> 0;
+        DemonAnalyzerFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @returns {number}
+     */
+    spectrum_len() {
+        const ret = wasm.demonanalyzer_spectrum_len(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    spectrum_ptr() {
+        const ret = wasm.demonanalyzer_spectrum_ptr(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {DemonStatus}
+     */
+    status() {
+        const ret = wasm.demonanalyzer_status(this.__wbg_ptr);
+        return ret;
+    }
+}
+if (Symbol.dispose) DemonAnalyzer.prototype[Symbol.dispose] = DemonAnalyzer.prototype.free;
+
+/**
+ * @enum {0 | 1 | 2 | 3 | 4}
+ */
+export const DemonStatus = Object.freeze({
+    Ok: 0, "0": "Ok",
+    Pending: 1, "1": "Pending",
+    BadSampleRate: 2, "2": "BadSampleRate",
+    InputTooShort: 3, "3": "InputTooShort",
+    OverDecimated: 4, "4": "OverDecimated",
+});
+
+/**
+ * @enum {0 | 1 | 2 | 3 | 4 | 5 | 6}
+ */
+export const DspError = Object.freeze({
+    InvalidVoice: 0, "0": "InvalidVoice",
+    InvalidParam: 1, "1": "InvalidParam",
+    OutOfRange: 2, "2": "OutOfRange",
+    PoolExhausted: 3, "3": "PoolExhausted",
+    QueueFull: 4, "4": "QueueFull",
+    InvalidSnapshot: 5, "5": "InvalidSnapshot",
+    Unsupported: 6, "6": "Unsupported",
+});
+
 export class DspGraph {
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(DspGraph.prototype);
+        obj.__wbg_ptr = ptr;
+        DspGraphFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
     __destroy_into_raw() {
         const ptr = this.__wbg_ptr;
         this.__wbg_ptr = 0;
@@ -11,112 +236,2720 @@ export class DspGraph {
         const ptr = this.__destroy_into_raw();
         wasm.__wbg_dspgraph_free(ptr, 0);
     }
+    /**
+     * @returns {number}
+     */
+    active_voice_count() {
+        const ret = wasm.dspgraph_active_voice_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {Uint32Array}
+     */
+    active_voice_ids() {
+        const ret = wasm.dspgraph_active_voice_ids(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
     /**
      * @returns {number}
      */
     add_voice() {
         const ret = wasm.dspgraph_add_voice(this.__wbg_ptr);
-        return ret;
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
     }
     /**
+     * @param {number} priority
      * @returns {number}
      */
-    average_process_ms() {
-        const ret = wasm.dspgraph_average_process_ms(this.__wbg_ptr);
-        return ret;
+    add_voice_with_priority(priority) {
+        const ret = wasm.dspgraph_add_voice_with_priority(this.__wbg_ptr, priority);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
     }
     /**
      * @returns {number}
      */
-    max_frames() {
-        const ret = wasm.dspgraph_max_frames(this.__wbg_ptr);
+    analysis_budget() {
+        const ret = wasm.dspgraph_analysis_budget(this.__wbg_ptr);
         return ret >>> 0;
     }
+    /**
+     * @param {number} voice_id
+     * @param {number} preset_id
+     */
+    apply_preset(voice_id, preset_id) {
+        const ret = wasm.dspgraph_apply_preset(this.__wbg_ptr, voice_id, preset_id);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
     /**
      * @returns {number}
      */
-    max_process_ms() {
-        const ret = wasm.dspgraph_max_process_ms(this.__wbg_ptr);
+    average_process_ms() {
+        const ret = wasm.dspgraph_average_process_ms(this.__wbg_ptr);
         return ret;
     }
     /**
-     * @param {number} sample_rate
-     * @param {number} max_frames
-     * @param {number} max_voices
+     * @returns {boolean}
      */
-    constructor(sample_rate, max_frames, max_voices) {
-        const ret = wasm.dspgraph_new(sample_rate, max_frames, max_voices);
-        this.__wbg_ptr = ret >>> 0;
-        DspGraphFinalization.register(this, this.__wbg_ptr, this);
-        return this;
+    band_limited_engines() {
+        const ret = wasm.dspgraph_band_limited_engines(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} control
+     * @param {number} voice_id
+     * @param {Param} param
+     * @param {ControlCurve} curve
+     * @param {number} min
+     * @param {number} max
+     */
+    bind_control(control, voice_id, param, curve, min, max) {
+        const ret = wasm.dspgraph_bind_control(this.__wbg_ptr, control, voice_id, param, curve, min, max);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
     }
     /**
+     * @param {number} voice_id
+     * @returns {number | undefined}
+     */
+    bio_ducking_db(voice_id) {
+        const ret = wasm.dspgraph_bio_ducking_db(this.__wbg_ptr, voice_id);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number}
+     */
+    cancel_scheduled(voice_id) {
+        const ret = wasm.dspgraph_cancel_scheduled(this.__wbg_ptr, voice_id);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} channel
      * @returns {Float32Array}
      */
-    output_copy() {
-        const ret = wasm.dspgraph_output_copy(this.__wbg_ptr);
+    channel_copy(channel) {
+        const ret = wasm.dspgraph_channel_copy(this.__wbg_ptr, channel);
         var v1 = getArrayF32FromWasm0(ret[0], ret[1]).slice();
         wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
         return v1;
     }
     /**
+     * @param {number} channel
      * @returns {number}
      */
-    output_len() {
-        const ret = wasm.dspgraph_output_len(this.__wbg_ptr);
+    channel_ptr(channel) {
+        const ret = wasm.dspgraph_channel_ptr(this.__wbg_ptr, channel);
         return ret >>> 0;
     }
+    clear_bathymetry() {
+        wasm.dspgraph_clear_bathymetry(this.__wbg_ptr);
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} slot
+     */
+    clear_mod_route(voice_id, slot) {
+        const ret = wasm.dspgraph_clear_mod_route(this.__wbg_ptr, voice_id, slot);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    clear_scenario() {
+        wasm.dspgraph_clear_scenario(this.__wbg_ptr);
+    }
+    /**
+     * @param {number} voice_id
+     */
+    clear_voice_kinematics(voice_id) {
+        const ret = wasm.dspgraph_clear_voice_kinematics(this.__wbg_ptr, voice_id);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
     /**
      * @returns {number}
      */
-    output_ptr() {
-        const ret = wasm.dspgraph_output_ptr(this.__wbg_ptr);
+    clear_voices() {
+        const ret = wasm.dspgraph_clear_voices(this.__wbg_ptr);
         return ret >>> 0;
     }
     /**
-     * @param {number} frames
+     * @param {number} source_id
      * @returns {number}
      */
-    process(frames) {
-        const ret = wasm.dspgraph_process(this.__wbg_ptr, frames);
+    clone_voice(source_id) {
+        const ret = wasm.dspgraph_clone_voice(this.__wbg_ptr, source_id);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} component
+     * @returns {boolean | undefined}
+     */
+    component_enabled(voice_id, component) {
+        const ret = wasm.dspgraph_component_enabled(this.__wbg_ptr, voice_id, component);
+        return ret === 0xFFFFFF ? undefined : ret !== 0;
+    }
+    /**
+     * @returns {number}
+     */
+    control_binding_count() {
+        const ret = wasm.dspgraph_control_binding_count(this.__wbg_ptr);
         return ret >>> 0;
     }
     /**
      * @returns {number}
      */
-    process_call_count() {
-        const ret = wasm.dspgraph_process_call_count(this.__wbg_ptr);
+    culled_voice_count() {
+        const ret = wasm.dspgraph_culled_voice_count(this.__wbg_ptr);
         return ret >>> 0;
     }
     /**
-     * @param {number} elapsed_ms
+     * @returns {number}
      */
-    record_process_ms(elapsed_ms) {
-        wasm.dspgraph_record_process_ms(this.__wbg_ptr, elapsed_ms);
+    demon_min_input_seconds() {
+        const ret = wasm.dspgraph_demon_min_input_seconds(this.__wbg_ptr);
+        return ret;
     }
     /**
-     * @param {number} voice_id
      * @returns {boolean}
      */
-    remove_voice(voice_id) {
-        const ret = wasm.dspgraph_remove_voice(this.__wbg_ptr, voice_id);
+    demon_spectrum_complete() {
+        const ret = wasm.dspgraph_demon_spectrum_complete(this.__wbg_ptr);
         return ret !== 0;
     }
-    reset_process_stats() {
-        wasm.dspgraph_reset_process_stats(this.__wbg_ptr);
+    /**
+     * @returns {number}
+     */
+    demon_spectrum_len() {
+        const ret = wasm.dspgraph_demon_spectrum_len(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    demon_spectrum_ptr() {
+        const ret = wasm.dspgraph_demon_spectrum_ptr(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {DemonStatus}
+     */
+    demon_spectrum_status() {
+        const ret = wasm.dspgraph_demon_spectrum_status(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {Float64Array}
+     */
+    drain_events() {
+        const ret = wasm.dspgraph_drain_events(this.__wbg_ptr);
+        var v1 = getArrayF64FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 8, 8);
+        return v1;
+    }
+    /**
+     * @param {number} start_hz
+     * @param {number} end_hz
+     * @param {number} duration_s
+     * @param {number} level
+     * @param {number} reverb_level
+     */
+    emit_ping(start_hz, end_hz, duration_s, level, reverb_level) {
+        const ret = wasm.dspgraph_emit_ping(this.__wbg_ptr, start_hz, end_hz, duration_s, level, reverb_level);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @returns {number}
+     */
+    event_stride() {
+        const ret = wasm.dspgraph_event_stride(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {bigint} tag
+     * @returns {number | undefined}
+     */
+    find_voice_by_tag(tag) {
+        const ret = wasm.dspgraph_find_voice_by_tag(this.__wbg_ptr, tag);
+        return ret === 0x100000001 ? undefined : ret;
     }
     /**
      * @param {number} voice_id
-     * @param {number} param_id
-     * @param {number} value
+     * @param {Param} param
+     * @returns {number | undefined}
+     */
+    get_param(voice_id, param) {
+        const ret = wasm.dspgraph_get_param(this.__wbg_ptr, voice_id, param);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} group
+     * @returns {number | undefined}
+     */
+    group_gain(group) {
+        const ret = wasm.dspgraph_group_gain(this.__wbg_ptr, group);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} group
+     * @returns {boolean | undefined}
+     */
+    group_muted(group) {
+        const ret = wasm.dspgraph_group_muted(this.__wbg_ptr, group);
+        return ret === 0xFFFFFF ? undefined : ret !== 0;
+    }
+    /**
+     * @returns {HeadroomReport}
+     */
+    headroom_report() {
+        const ret = wasm.dspgraph_headroom_report(this.__wbg_ptr);
+        return HeadroomReport.__wrap(ret);
+    }
+    /**
+     * @param {number} channel
+     * @returns {Float32Array}
+     */
+    hydrophone_copy(channel) {
+        const ret = wasm.dspgraph_hydrophone_copy(this.__wbg_ptr, channel);
+        var v1 = getArrayF32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @returns {boolean}
+     */
+    hydrophone_output() {
+        const ret = wasm.dspgraph_hydrophone_output(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} channel
+     * @returns {number}
+     */
+    hydrophone_ptr(channel) {
+        const ret = wasm.dspgraph_hydrophone_ptr(this.__wbg_ptr, channel);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {boolean}
+     */
+    interleaved_output() {
+        const ret = wasm.dspgraph_interleaved_output(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {boolean}
+     */
+    is_paused() {
+        const ret = wasm.dspgraph_is_paused(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
      * @returns {boolean}
      */
-    set_param(voice_id, param_id, value) {
-        const ret = wasm.dspgraph_set_param(this.__wbg_ptr, voice_id, param_id, value);
+    is_recording() {
+        const ret = wasm.dspgraph_is_recording(this.__wbg_ptr);
         return ret !== 0;
     }
+    /**
+     * @returns {number}
+     */
+    limiter_drive_db() {
+        const ret = wasm.dspgraph_limiter_drive_db(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    limiter_knee_db() {
+        const ret = wasm.dspgraph_limiter_knee_db(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    limiter_reduction_db() {
+        const ret = wasm.dspgraph_limiter_reduction_db(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {Float64Array} steps
+     * @returns {number}
+     */
+    load_scenario(steps) {
+        const ptr0 = passArrayF64ToWasm0(steps, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.dspgraph_load_scenario(this.__wbg_ptr, ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    loudness_gain_db() {
+        const ret = wasm.dspgraph_loudness_gain_db(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {boolean}
+     */
+    low_rate_sources() {
+        const ret = wasm.dspgraph_low_rate_sources(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number | undefined}
+     */
+    machinery_ducking_db(voice_id) {
+        const ret = wasm.dspgraph_machinery_ducking_db(this.__wbg_ptr, voice_id);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @returns {Meter}
+     */
+    master_meter() {
+        const ret = wasm.dspgraph_master_meter(this.__wbg_ptr);
+        return Meter.__wrap(ret);
+    }
+    /**
+     * @returns {number}
+     */
+    max_array_elements() {
+        const ret = wasm.dspgraph_max_array_elements(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_block_frames() {
+        const ret = wasm.dspgraph_max_block_frames(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_control_bindings() {
+        const ret = wasm.dspgraph_max_control_bindings(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_controls() {
+        const ret = wasm.dspgraph_max_controls(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_frames() {
+        const ret = wasm.dspgraph_max_frames(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_graph_events() {
+        const ret = wasm.dspgraph_max_graph_events(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_groups() {
+        const ret = wasm.dspgraph_max_groups(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_mod_routes() {
+        const ret = wasm.dspgraph_max_groups(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_process_ms() {
+        const ret = wasm.dspgraph_max_process_ms(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    max_recorded_changes() {
+        const ret = wasm.dspgraph_max_recorded_changes(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_ring_channels() {
+        const ret = wasm.dspgraph_max_groups(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_scenario_steps() {
+        const ret = wasm.dspgraph_max_scenario_steps(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_scheduled_events() {
+        const ret = wasm.dspgraph_max_scenario_steps(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    max_voice_limit() {
+        const ret = wasm.dspgraph_max_graph_events(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {MemoryUsage}
+     */
+    memory_usage() {
+        const ret = wasm.dspgraph_memory_usage(this.__wbg_ptr);
+        return MemoryUsage.__wrap(ret);
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {Meter | undefined}
+     */
+    meter(voice_id) {
+        const ret = wasm.dspgraph_meter(this.__wbg_ptr, voice_id);
+        return ret === 0 ? undefined : Meter.__wrap(ret);
+    }
+    /**
+     * @returns {number}
+     */
+    mod_source_count() {
+        const ret = wasm.dspgraph_mod_source_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} sample_rate
+     * @param {number} max_frames
+     * @param {number} max_voices
+     */
+    constructor(sample_rate, max_frames, max_voices) {
+        const ret = wasm.dspgraph_new(sample_rate, max_frames, max_voices);
+        this.__wbg_ptr = ret >>> 0;
+        DspGraphFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @returns {number}
+     */
+    noise_sector_count() {
+        const ret = wasm.dspgraph_max_groups(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    operator_band_high_hz() {
+        const ret = wasm.dspgraph_operator_band_high_hz(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    operator_band_low_hz() {
+        const ret = wasm.dspgraph_operator_band_low_hz(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    output_channels() {
+        const ret = wasm.dspgraph_output_channels(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {Float32Array}
+     */
+    output_copy() {
+        const ret = wasm.dspgraph_output_copy(this.__wbg_ptr);
+        var v1 = getArrayF32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @returns {number}
+     */
+    output_len() {
+        const ret = wasm.dspgraph_output_len(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    output_ptr() {
+        const ret = wasm.dspgraph_output_ptr(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {Param} param
+     * @returns {number}
+     */
+    param_ramp_ms(param) {
+        const ret = wasm.dspgraph_param_ramp_ms(this.__wbg_ptr, param);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    param_update_stride() {
+        const ret = wasm.dspgraph_param_update_stride(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {Int16Array}
+     */
+    pcm16_copy() {
+        const ret = wasm.dspgraph_pcm16_copy(this.__wbg_ptr);
+        var v1 = getArrayI16FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 2, 2);
+        return v1;
+    }
+    /**
+     * @returns {boolean}
+     */
+    pcm16_output() {
+        const ret = wasm.dspgraph_pcm16_output(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {number}
+     */
+    pcm16_ptr() {
+        const ret = wasm.dspgraph_pcm16_ptr(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    pending_event_count() {
+        const ret = wasm.dspgraph_pending_event_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {Precision}
+     */
+    precision() {
+        const ret = wasm.dspgraph_precision(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} frames
+     * @returns {number}
+     */
+    process(frames) {
+        const ret = wasm.dspgraph_process(this.__wbg_ptr, frames);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    process_call_count() {
+        const ret = wasm.dspgraph_process_call_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Renders like process() but writes the block to `ptr`, a host-owned region of this
+     * module's memory (see alloc_f32_buffer) with room for output_channels() * frames floats.
+     * Planar channels are packed back to back, so a single view covers them all however the
+     * memory has grown; in interleaved mode the frames follow one another as usual. Returns
+     * the frames written: `frames`, capped at max_frames().
+     *
+     * # Safety
+     *
+     * `ptr` must address output_channels() * frames writable floats, such as a live
+     * alloc_f32_buffer block, that nothing else reads or writes during the call.
+     * @param {number} ptr
+     * @param {number} frames
+     * @returns {number}
+     */
+    process_into(ptr, frames) {
+        const ret = wasm.dspgraph_process_into(this.__wbg_ptr, ptr, frames);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    rain_level() {
+        const ret = wasm.dspgraph_rain_level(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} elapsed_ms
+     */
+    record_process_ms(elapsed_ms) {
+        wasm.dspgraph_record_process_ms(this.__wbg_ptr, elapsed_ms);
+    }
+    /**
+     * @returns {number}
+     */
+    record_stride() {
+        const ret = wasm.dspgraph_record_stride(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    recorded_count() {
+        const ret = wasm.dspgraph_recorded_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} voice_id
+     */
+    remove_voice(voice_id) {
+        const ret = wasm.dspgraph_remove_voice(this.__wbg_ptr, voice_id);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @returns {number}
+     */
+    replay_pending() {
+        const ret = wasm.dspgraph_replay_pending(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {Float32Array} input
+     * @param {number} max_freq_hz
+     * @param {number} input_band_low_hz
+     * @param {number} input_band_high_hz
+     * @param {number} envelope_hp_hz
+     * @param {number} decimated_rate_target_hz
+     */
+    request_demon_spectrum(input, max_freq_hz, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz) {
+        const ptr0 = passArrayF32ToWasm0(input, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        wasm.dspgraph_request_demon_spectrum(this.__wbg_ptr, ptr0, len0, max_freq_hz, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz);
+    }
+    reset() {
+        wasm.dspgraph_reset(this.__wbg_ptr);
+    }
+    reset_process_stats() {
+        wasm.dspgraph_reset_process_stats(this.__wbg_ptr);
+    }
+    reset_stage_profile() {
+        wasm.dspgraph_reset_stage_profile(this.__wbg_ptr);
+    }
+    /**
+     * @param {string} snapshot
+     */
+    restore(snapshot) {
+        const ptr0 = passStringToWasm0(snapshot, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.dspgraph_restore(this.__wbg_ptr, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number | undefined}
+     */
+    rpm_accel(voice_id) {
+        const ret = wasm.dspgraph_rpm_accel(this.__wbg_ptr, voice_id);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number | undefined}
+     */
+    rpm_decel(voice_id) {
+        const ret = wasm.dspgraph_rpm_decel(this.__wbg_ptr, voice_id);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @returns {number}
+     */
+    sample_rate() {
+        const ret = wasm.dspgraph_sample_rate(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    scenario_pending() {
+        const ret = wasm.dspgraph_scenario_pending(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    scenario_stride() {
+        const ret = wasm.dspgraph_event_stride(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} voice_id
+     * @param {Param} param
+     * @param {number} value
+     * @param {number} offset_samples
+     * @param {number} ramp_ms
+     */
+    schedule_param(voice_id, param, value, offset_samples, ramp_ms) {
+        const ret = wasm.dspgraph_schedule_param(this.__wbg_ptr, voice_id, param, value, offset_samples, ramp_ms);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {Param} param
+     * @param {number} value
+     * @param {number} delay_s
+     * @param {number} ramp_ms
+     */
+    schedule_param_in(voice_id, param, value, delay_s, ramp_ms) {
+        const ret = wasm.dspgraph_schedule_param_in(this.__wbg_ptr, voice_id, param, value, delay_s, ramp_ms);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} priority
+     * @param {number} offset_samples
+     * @returns {number}
+     */
+    schedule_voice(priority, offset_samples) {
+        const ret = wasm.dspgraph_schedule_voice(this.__wbg_ptr, priority, offset_samples);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    /**
+     * @param {number} priority
+     * @param {number} delay_s
+     * @returns {number}
+     */
+    schedule_voice_in(priority, delay_s) {
+        const ret = wasm.dspgraph_schedule_voice_in(this.__wbg_ptr, priority, delay_s);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    scheduled_count() {
+        const ret = wasm.dspgraph_scheduled_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    sea_state() {
+        const ret = wasm.dspgraph_sea_state(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    seed() {
+        const ret = wasm.dspgraph_seed(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    self_noise_db() {
+        const ret = wasm.dspgraph_self_noise_db(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} strength
+     */
+    set_absorption_filter(strength) {
+        const ret = wasm.dspgraph_set_absorption_filter(this.__wbg_ptr, strength);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} units
+     */
+    set_analysis_budget(units) {
+        const ret = wasm.dspgraph_set_analysis_budget(this.__wbg_ptr, units);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {boolean} enabled
+     */
+    set_band_limited_engines(enabled) {
+        wasm.dspgraph_set_band_limited_engines(this.__wbg_ptr, enabled);
+    }
+    /**
+     * @param {number} origin_x
+     * @param {number} origin_z
+     * @param {number} cell_m
+     * @param {number} cols
+     * @param {Float32Array} depths
+     * @param {number} transition_s
+     */
+    set_bathymetry(origin_x, origin_z, cell_m, cols, depths, transition_s) {
+        const ptr0 = passArrayF32ToWasm0(depths, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.dspgraph_set_bathymetry(this.__wbg_ptr, origin_x, origin_z, cell_m, cols, ptr0, len0, transition_s);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {boolean} enabled
+     */
+    set_binaural(enabled) {
+        wasm.dspgraph_set_binaural(this.__wbg_ptr, enabled);
+    }
+    /**
+     * @param {number} bottom_type
+     */
+    set_bottom_type(bottom_type) {
+        const ret = wasm.dspgraph_set_bottom_type(this.__wbg_ptr, bottom_type);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} model
+     */
+    set_channel_model(model) {
+        const ret = wasm.dspgraph_set_channel_model(this.__wbg_ptr, model);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} component
+     * @param {boolean} enabled
+     */
+    set_component_enabled(voice_id, component, enabled) {
+        const ret = wasm.dspgraph_set_component_enabled(this.__wbg_ptr, voice_id, component, enabled);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} control
+     * @param {number} position
+     * @returns {number}
+     */
+    set_control(control, position) {
+        const ret = wasm.dspgraph_set_control(this.__wbg_ptr, control, position);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    /**
+     * @param {number} interval_m
+     * @param {number} width_m
+     * @param {number} gain_db
+     */
+    set_convergence_zones(interval_m, width_m, gain_db) {
+        const ret = wasm.dspgraph_set_convergence_zones(this.__wbg_ptr, interval_m, width_m, gain_db);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} group
+     * @param {number} gain
+     */
+    set_group_gain(group, gain) {
+        const ret = wasm.dspgraph_set_group_gain(this.__wbg_ptr, group, gain);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} group
+     * @param {boolean} muted
+     */
+    set_group_mute(group, muted) {
+        const ret = wasm.dspgraph_set_group_mute(this.__wbg_ptr, group, muted);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} cutoff_hz
+     * @param {number} resonance
+     * @param {number} return_level
+     */
+    set_hull_filter(cutoff_hz, resonance, return_level) {
+        const ret = wasm.dspgraph_set_hull_filter(this.__wbg_ptr, cutoff_hz, resonance, return_level);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {boolean} enabled
+     */
+    set_hydrophone_output(enabled) {
+        wasm.dspgraph_set_hydrophone_output(this.__wbg_ptr, enabled);
+    }
+    /**
+     * @param {boolean} enabled
+     */
+    set_interleaved_output(enabled) {
+        wasm.dspgraph_set_interleaved_output(this.__wbg_ptr, enabled);
+    }
+    /**
+     * @param {boolean} enabled
+     */
+    set_itd(enabled) {
+        wasm.dspgraph_set_itd(this.__wbg_ptr, enabled);
+    }
+    /**
+     * @param {number} depth_m
+     * @param {number} loss_db
+     */
+    set_layer(depth_m, loss_db) {
+        const ret = wasm.dspgraph_set_layer(this.__wbg_ptr, depth_m, loss_db);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {boolean} enabled
+     * @param {number} threshold_db
+     * @param {number} release_ms
+     */
+    set_limiter(enabled, threshold_db, release_ms) {
+        const ret = wasm.dspgraph_set_limiter(this.__wbg_ptr, enabled, threshold_db, release_ms);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} drive_db
+     * @param {number} knee_db
+     */
+    set_limiter_shape(drive_db, knee_db) {
+        const ret = wasm.dspgraph_set_limiter_shape(this.__wbg_ptr, drive_db, knee_db);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} depth_m
+     */
+    set_listener_depth(depth_m) {
+        wasm.dspgraph_set_listener_depth(this.__wbg_ptr, depth_m);
+    }
+    /**
+     * @param {number} yaw_deg
+     */
+    set_listener_yaw(yaw_deg) {
+        const ret = wasm.dspgraph_set_listener_yaw(this.__wbg_ptr, yaw_deg);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} bearing_deg
+     * @param {number} width_deg
+     */
+    set_look_direction(bearing_deg, width_deg) {
+        wasm.dspgraph_set_look_direction(this.__wbg_ptr, bearing_deg, width_deg);
+    }
+    /**
+     * @param {boolean} enabled
+     * @param {number} target_db
+     * @param {number} max_boost_db
+     * @param {number} max_cut_db
+     */
+    set_loudness_normalization(enabled, target_db, max_boost_db, max_cut_db) {
+        const ret = wasm.dspgraph_set_loudness_normalization(this.__wbg_ptr, enabled, target_db, max_boost_db, max_cut_db);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {boolean} enabled
+     */
+    set_low_rate_sources(enabled) {
+        wasm.dspgraph_set_low_rate_sources(this.__wbg_ptr, enabled);
+    }
+    /**
+     * @param {number} max_frames
+     */
+    set_max_frames(max_frames) {
+        const ret = wasm.dspgraph_set_max_frames(this.__wbg_ptr, max_frames);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} slot
+     * @param {number} source
+     * @param {Param} param
+     * @param {number} depth
+     */
+    set_mod_route(voice_id, slot, source, param, depth) {
+        const ret = wasm.dspgraph_set_mod_route(this.__wbg_ptr, voice_id, slot, source, param, depth);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} source
+     * @param {number} shape
+     * @param {number} rate_hz
+     */
+    set_mod_source(voice_id, source, shape, rate_hz) {
+        const ret = wasm.dspgraph_set_mod_source(this.__wbg_ptr, voice_id, source, shape, rate_hz);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} taps
+     * @param {number} water_depth_m
+     */
+    set_multipath(taps, water_depth_m) {
+        const ret = wasm.dspgraph_set_multipath(this.__wbg_ptr, taps, water_depth_m);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} sector
+     * @param {number} level
+     * @param {number} tilt
+     */
+    set_noise_sector(sector, level, tilt) {
+        const ret = wasm.dspgraph_set_noise_sector(this.__wbg_ptr, sector, level, tilt);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {boolean} enabled
+     * @param {number} low_hz
+     * @param {number} high_hz
+     */
+    set_operator_band(enabled, low_hz, high_hz) {
+        const ret = wasm.dspgraph_set_operator_band(this.__wbg_ptr, enabled, low_hz, high_hz);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} channels
+     */
+    set_output_channels(channels) {
+        const ret = wasm.dspgraph_set_output_channels(this.__wbg_ptr, channels);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} x
+     * @param {number} z
+     * @param {number} vx
+     * @param {number} vz
+     * @param {number} heading_deg
+     */
+    set_own_ship(x, z, vx, vz, heading_deg) {
+        const ret = wasm.dspgraph_set_own_ship(this.__wbg_ptr, x, z, vx, vz, heading_deg);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {Param} param
+     * @param {number} value
+     */
+    set_param(voice_id, param, value) {
+        const ret = wasm.dspgraph_set_param(this.__wbg_ptr, voice_id, param, value);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {Param} param
+     * @param {number} ramp_ms
+     */
+    set_param_ramp(param, ramp_ms) {
+        const ret = wasm.dspgraph_set_param_ramp(this.__wbg_ptr, param, ramp_ms);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {Float32Array} updates
+     * @returns {number}
+     */
+    set_params(updates) {
+        const ptr0 = passArrayF32ToWasm0(updates, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.dspgraph_set_params(this.__wbg_ptr, ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    /**
+     * @param {boolean} paused
+     */
+    set_paused(paused) {
+        wasm.dspgraph_set_paused(this.__wbg_ptr, paused);
+    }
+    /**
+     * @param {boolean} enabled
+     */
+    set_pcm16_output(enabled) {
+        wasm.dspgraph_set_pcm16_output(this.__wbg_ptr, enabled);
+    }
+    /**
+     * @param {boolean} enabled
+     */
+    set_profiling(enabled) {
+        const ret = wasm.dspgraph_set_profiling(this.__wbg_ptr, enabled);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} reference_m
+     * @param {number} transition_m
+     * @param {number} absorption_db_per_km
+     */
+    set_propagation(reference_m, transition_m, absorption_db_per_km) {
+        const ret = wasm.dspgraph_set_propagation(this.__wbg_ptr, reference_m, transition_m, absorption_db_per_km);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} pattern
+     * @param {number} width_deg
+     * @param {number} loss_db
+     */
+    set_receiver_directivity(pattern, width_deg, loss_db) {
+        const ret = wasm.dspgraph_set_receiver_directivity(this.__wbg_ptr, pattern, width_deg, loss_db);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} decay_s
+     * @param {number} damping
+     * @param {number} return_level
+     */
+    set_reverb(decay_s, damping, return_level) {
+        const ret = wasm.dspgraph_set_reverb(this.__wbg_ptr, decay_s, damping, return_level);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} accel_rpm_s
+     * @param {number} decel_rpm_s
+     */
+    set_rpm_slew(voice_id, accel_rpm_s, decel_rpm_s) {
+        const ret = wasm.dspgraph_set_rpm_slew(this.__wbg_ptr, voice_id, accel_rpm_s, decel_rpm_s);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} sample_rate
+     */
+    set_sample_rate(sample_rate) {
+        const ret = wasm.dspgraph_set_sample_rate(this.__wbg_ptr, sample_rate);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} sea_state
+     */
+    set_sea_state(sea_state) {
+        wasm.dspgraph_set_sea_state(this.__wbg_ptr, sea_state);
+    }
+    /**
+     * @param {number} seed
+     */
+    set_seed(seed) {
+        wasm.dspgraph_set_seed(this.__wbg_ptr, seed);
+    }
+    /**
+     * @param {number} level
+     * @param {number} machinery
+     */
+    set_self_noise(level, machinery) {
+        const ret = wasm.dspgraph_set_self_noise(this.__wbg_ptr, level, machinery);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} upper_ms
+     * @param {number} lower_ms
+     */
+    set_sound_speed_profile(upper_ms, lower_ms) {
+        const ret = wasm.dspgraph_set_sound_speed_profile(this.__wbg_ptr, upper_ms, lower_ms);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} policy
+     */
+    set_steal_policy(policy) {
+        const ret = wasm.dspgraph_set_steal_policy(this.__wbg_ptr, policy);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {boolean} enabled
+     */
+    set_stereo_output(enabled) {
+        wasm.dspgraph_set_stereo_output(this.__wbg_ptr, enabled);
+    }
+    /**
+     * @param {number} depth_m
+     * @param {number} leak_db_per_km
+     */
+    set_surface_duct(depth_m, leak_db_per_km) {
+        const ret = wasm.dspgraph_set_surface_duct(this.__wbg_ptr, depth_m, leak_db_per_km);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} elements
+     * @param {number} spacing_m
+     * @param {number} flow_level
+     */
+    set_towed_array(elements, spacing_m, flow_level) {
+        const ret = wasm.dspgraph_set_towed_array(this.__wbg_ptr, elements, spacing_m, flow_level);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {boolean} enabled
+     * @param {number} threshold_db
+     */
+    set_voice_culling(enabled, threshold_db) {
+        const ret = wasm.dspgraph_set_voice_culling(this.__wbg_ptr, enabled, threshold_db);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} threshold_db
+     * @param {number} bio_ratio
+     * @param {number} machinery_ratio
+     * @param {number} release_ms
+     */
+    set_voice_ducking(voice_id, threshold_db, bio_ratio, machinery_ratio, release_ms) {
+        const ret = wasm.dspgraph_set_voice_ducking(this.__wbg_ptr, voice_id, threshold_db, bio_ratio, machinery_ratio, release_ms);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} group
+     */
+    set_voice_group(voice_id, group) {
+        const ret = wasm.dspgraph_set_voice_group(this.__wbg_ptr, voice_id, group);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {boolean} enabled
+     */
+    set_voice_input(voice_id, enabled) {
+        const ret = wasm.dspgraph_set_voice_input(this.__wbg_ptr, voice_id, enabled);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} x
+     * @param {number} z
+     * @param {number} vx
+     * @param {number} vz
+     */
+    set_voice_kinematics(voice_id, x, z, vx, vz) {
+        const ret = wasm.dspgraph_set_voice_kinematics(this.__wbg_ptr, voice_id, x, z, vx, vz);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {string} label
+     */
+    set_voice_label(voice_id, label) {
+        const ptr0 = passStringToWasm0(label, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.dspgraph_set_voice_label(this.__wbg_ptr, voice_id, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} limit
+     */
+    set_voice_limit(limit) {
+        wasm.dspgraph_set_voice_limit(this.__wbg_ptr, limit);
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} priority
+     */
+    set_voice_priority(voice_id, priority) {
+        const ret = wasm.dspgraph_set_voice_priority(this.__wbg_ptr, voice_id, priority);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} bus
+     * @param {number} level
+     */
+    set_voice_send(voice_id, bus, level) {
+        const ret = wasm.dspgraph_set_voice_send(this.__wbg_ptr, voice_id, bus, level);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {bigint} tag
+     */
+    set_voice_tag(voice_id, tag) {
+        const ret = wasm.dspgraph_set_voice_tag(this.__wbg_ptr, voice_id, tag);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} voice_id
+     * @param {boolean} enabled
+     */
+    set_voice_tap(voice_id, enabled) {
+        const ret = wasm.dspgraph_set_voice_tap(this.__wbg_ptr, voice_id, enabled);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} depth_m
+     * @param {number} transition_s
+     */
+    set_water_depth(depth_m, transition_s) {
+        const ret = wasm.dspgraph_set_water_depth(this.__wbg_ptr, depth_m, transition_s);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} sea_state
+     * @param {number} rain
+     * @param {number} wind
+     * @param {number} transition_s
+     */
+    set_weather_target(sea_state, rain, wind, transition_s) {
+        const ret = wasm.dspgraph_set_weather_target(this.__wbg_ptr, sea_state, rain, wind, transition_s);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @returns {string | undefined}
+     */
+    snapshot() {
+        const ret = wasm.dspgraph_snapshot(this.__wbg_ptr);
+        let v1;
+        if (ret[0] !== 0) {
+            v1 = getStringFromWasm0(ret[0], ret[1]).slice();
+            wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        }
+        return v1;
+    }
+    /**
+     * @returns {number}
+     */
+    snapshot_version() {
+        const ret = wasm.dspgraph_mod_source_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    sounding_voice_count() {
+        const ret = wasm.dspgraph_sounding_voice_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {ProfileStage} stage
+     * @returns {StageProfile}
+     */
+    stage_profile(stage) {
+        const ret = wasm.dspgraph_stage_profile(this.__wbg_ptr, stage);
+        return StageProfile.__wrap(ret);
+    }
+    start_recording() {
+        wasm.dspgraph_start_recording(this.__wbg_ptr);
+    }
+    /**
+     * @param {Float64Array} recording
+     * @returns {number}
+     */
+    start_replay(recording) {
+        const ptr0 = passArrayF64ToWasm0(recording, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.dspgraph_start_replay(this.__wbg_ptr, ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    /**
+     * @param {number} peak_sea_state
+     * @param {number} peak_rain
+     * @param {number} peak_wind
+     * @param {number} build_s
+     * @param {number} hold_s
+     * @param {number} abate_s
+     */
+    start_storm(peak_sea_state, peak_rain, peak_wind, build_s, hold_s, abate_s) {
+        const ret = wasm.dspgraph_start_storm(this.__wbg_ptr, peak_sea_state, peak_rain, peak_wind, build_s, hold_s, abate_s);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @returns {number}
+     */
+    steal_policy() {
+        const ret = wasm.dspgraph_steal_policy(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    stop_recording() {
+        wasm.dspgraph_stop_recording(this.__wbg_ptr);
+    }
+    stop_replay() {
+        wasm.dspgraph_stop_replay(this.__wbg_ptr);
+    }
+    /**
+     * @returns {Float64Array}
+     */
+    take_recording() {
+        const ret = wasm.dspgraph_take_recording(this.__wbg_ptr);
+        var v1 = getArrayF64FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 8, 8);
+        return v1;
+    }
+    /**
+     * @param {number} source_depth_m
+     * @param {number} receiver_depth_m
+     * @param {number} frequency_hz
+     * @param {number} max_range_m
+     * @param {number} points
+     * @returns {Float32Array}
+     */
+    transmission_loss_curve(source_depth_m, receiver_depth_m, frequency_hz, max_range_m, points) {
+        const ret = wasm.dspgraph_transmission_loss_curve(this.__wbg_ptr, source_depth_m, receiver_depth_m, frequency_hz, max_range_m, points);
+        var v1 = getArrayF32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @param {number} voice_id
+     */
+    trigger_bio_event(voice_id) {
+        const ret = wasm.dspgraph_trigger_bio_event(this.__wbg_ptr, voice_id);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} control
+     * @returns {number}
+     */
+    unbind_control(control) {
+        const ret = wasm.dspgraph_unbind_control(this.__wbg_ptr, control);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number | undefined}
+     */
+    voice_group(voice_id) {
+        const ret = wasm.dspgraph_voice_group(this.__wbg_ptr, voice_id);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number}
+     */
+    voice_input_ptr(voice_id) {
+        const ret = wasm.dspgraph_voice_input_ptr(this.__wbg_ptr, voice_id);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {string | undefined}
+     */
+    voice_label(voice_id) {
+        const ret = wasm.dspgraph_voice_label(this.__wbg_ptr, voice_id);
+        let v1;
+        if (ret[0] !== 0) {
+            v1 = getStringFromWasm0(ret[0], ret[1]).slice();
+            wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        }
+        return v1;
+    }
+    /**
+     * @returns {number}
+     */
+    voice_limit() {
+        const ret = wasm.dspgraph_voice_limit(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    voice_pool_size() {
+        const ret = wasm.dspgraph_voice_pool_size(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number | undefined}
+     */
+    voice_preset(voice_id) {
+        const ret = wasm.dspgraph_voice_preset(this.__wbg_ptr, voice_id);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number | undefined}
+     */
+    voice_priority(voice_id) {
+        const ret = wasm.dspgraph_voice_priority(this.__wbg_ptr, voice_id);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} voice_id
+     * @param {number} bus
+     * @returns {number | undefined}
+     */
+    voice_send(voice_id, bus) {
+        const ret = wasm.dspgraph_voice_send(this.__wbg_ptr, voice_id, bus);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number | undefined}
+     */
+    voice_start_delay(voice_id) {
+        const ret = wasm.dspgraph_voice_start_delay(this.__wbg_ptr, voice_id);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {VoiceState | undefined}
+     */
+    voice_state(voice_id) {
+        const ret = wasm.dspgraph_voice_state(this.__wbg_ptr, voice_id);
+        return ret === 0 ? undefined : VoiceState.__wrap(ret);
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {bigint | undefined}
+     */
+    voice_tag(voice_id) {
+        const ret = wasm.dspgraph_voice_tag(this.__wbg_ptr, voice_id);
+        return ret[0] === 0 ? undefined : BigInt.asUintN(64, ret[1]);
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {Float32Array}
+     */
+    voice_tap_copy(voice_id) {
+        const ret = wasm.dspgraph_voice_tap_copy(this.__wbg_ptr, voice_id);
+        var v1 = getArrayF32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @param {number} voice_id
+     * @returns {number}
+     */
+    voice_tap_ptr(voice_id) {
+        const ret = wasm.dspgraph_voice_tap_ptr(this.__wbg_ptr, voice_id);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    water_depth() {
+        const ret = wasm.dspgraph_water_depth(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    wind_level() {
+        const ret = wasm.dspgraph_wind_level(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} voice_id
+     * @param {Float32Array} samples
+     */
+    write_voice_input(voice_id, samples) {
+        const ptr0 = passArrayF32ToWasm0(samples, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.dspgraph_write_voice_input(this.__wbg_ptr, voice_id, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+}
+if (Symbol.dispose) DspGraph.prototype[Symbol.dispose] = DspGraph.prototype.free;
+
+export class DspGraphBuilder {
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(DspGraphBuilder.prototype);
+        obj.__wbg_ptr = ptr;
+        DspGraphBuilderFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        DspGraphBuilderFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_dspgraphbuilder_free(ptr, 0);
+    }
+    /**
+     * @param {number} sea_state
+     * @param {number} rain
+     * @param {number} wind
+     * @returns {DspGraphBuilder}
+     */
+    ambience(sea_state, rain, wind) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.dspgraphbuilder_ambience(ptr, sea_state, rain, wind);
+        return DspGraphBuilder.__wrap(ret);
+    }
+    /**
+     * @returns {DspGraph}
+     */
+    build() {
+        const ret = wasm.dspgraphbuilder_build(this.__wbg_ptr);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return DspGraph.__wrap(ret[0]);
+    }
+    /**
+     * @param {number} channels
+     * @returns {DspGraphBuilder}
+     */
+    channels(channels) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.dspgraphbuilder_channels(ptr, channels);
+        return DspGraphBuilder.__wrap(ret);
+    }
+    /**
+     * @param {number} sample_rate
+     * @param {number} max_frames
+     */
+    constructor(sample_rate, max_frames) {
+        const ret = wasm.dspgraphbuilder_new(sample_rate, max_frames);
+        this.__wbg_ptr = ret >>> 0;
+        DspGraphBuilderFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @param {Precision} precision
+     * @returns {DspGraphBuilder}
+     */
+    precision(precision) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.dspgraphbuilder_precision(ptr, precision);
+        return DspGraphBuilder.__wrap(ret);
+    }
+    /**
+     * @param {number} seed
+     * @returns {DspGraphBuilder}
+     */
+    seed(seed) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.dspgraphbuilder_seed(ptr, seed);
+        return DspGraphBuilder.__wrap(ret);
+    }
+    /**
+     * @param {VoiceConfig} config
+     * @returns {DspGraphBuilder}
+     */
+    voice(config) {
+        const ptr = this.__destroy_into_raw();
+        _assertClass(config, VoiceConfig);
+        const ret = wasm.dspgraphbuilder_voice(ptr, config.__wbg_ptr);
+        return DspGraphBuilder.__wrap(ret);
+    }
+    /**
+     * @param {number} max_voices
+     * @returns {DspGraphBuilder}
+     */
+    voice_pool(max_voices) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.dspgraphbuilder_voice_pool(ptr, max_voices);
+        return DspGraphBuilder.__wrap(ret);
+    }
+    /**
+     * @param {number} depth_m
+     * @returns {DspGraphBuilder}
+     */
+    water_depth(depth_m) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.dspgraphbuilder_water_depth(ptr, depth_m);
+        return DspGraphBuilder.__wrap(ret);
+    }
+}
+if (Symbol.dispose) DspGraphBuilder.prototype[Symbol.dispose] = DspGraphBuilder.prototype.free;
+
+export class HeadroomReport {
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(HeadroomReport.prototype);
+        obj.__wbg_ptr = ptr;
+        HeadroomReportFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        HeadroomReportFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_headroomreport_free(ptr, 0);
+    }
+    /**
+     * @returns {number}
+     */
+    get blocks() {
+        const ret = wasm.__wbg_get_headroomreport_blocks(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get clipped_blocks() {
+        const ret = wasm.__wbg_get_headroomreport_clipped_blocks(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get clipped_samples() {
+        const ret = wasm.__wbg_get_headroomreport_clipped_samples(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get limited_blocks() {
+        const ret = wasm.__wbg_get_headroomreport_limited_blocks(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get max_reduction_db() {
+        const ret = wasm.__wbg_get_headroomreport_max_reduction_db(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get peak_db() {
+        const ret = wasm.__wbg_get_headroomreport_peak_db(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} arg0
+     */
+    set blocks(arg0) {
+        wasm.__wbg_set_headroomreport_blocks(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set clipped_blocks(arg0) {
+        wasm.__wbg_set_headroomreport_clipped_blocks(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set clipped_samples(arg0) {
+        wasm.__wbg_set_headroomreport_clipped_samples(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set limited_blocks(arg0) {
+        wasm.__wbg_set_headroomreport_limited_blocks(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set max_reduction_db(arg0) {
+        wasm.__wbg_set_headroomreport_max_reduction_db(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set peak_db(arg0) {
+        wasm.__wbg_set_headroomreport_peak_db(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) HeadroomReport.prototype[Symbol.dispose] = HeadroomReport.prototype.free;
+
+export class MemoryUsage {
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(MemoryUsage.prototype);
+        obj.__wbg_ptr = ptr;
+        MemoryUsageFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        MemoryUsageFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_memoryusage_free(ptr, 0);
+    }
+    /**
+     * @returns {number}
+     */
+    get buffers() {
+        const ret = wasm.__wbg_get_headroomreport_limited_blocks(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get history() {
+        const ret = wasm.__wbg_get_headroomreport_clipped_blocks(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get scene() {
+        const ret = wasm.__wbg_get_headroomreport_clipped_samples(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get total() {
+        const ret = wasm.__wbg_get_memoryusage_total(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get voices() {
+        const ret = wasm.__wbg_get_headroomreport_blocks(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} arg0
+     */
+    set buffers(arg0) {
+        wasm.__wbg_set_headroomreport_limited_blocks(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set history(arg0) {
+        wasm.__wbg_set_headroomreport_clipped_blocks(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set scene(arg0) {
+        wasm.__wbg_set_headroomreport_clipped_samples(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set total(arg0) {
+        wasm.__wbg_set_memoryusage_total(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set voices(arg0) {
+        wasm.__wbg_set_headroomreport_blocks(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) MemoryUsage.prototype[Symbol.dispose] = MemoryUsage.prototype.free;
+
+export class Meter {
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(Meter.prototype);
+        obj.__wbg_ptr = ptr;
+        MeterFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        MeterFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_meter_free(ptr, 0);
+    }
+    /**
+     * @returns {number}
+     */
+    get peak() {
+        const ret = wasm.__wbg_get_meter_peak(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get rms() {
+        const ret = wasm.__wbg_get_meter_rms(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} arg0
+     */
+    set peak(arg0) {
+        wasm.__wbg_set_meter_peak(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set rms(arg0) {
+        wasm.__wbg_set_meter_rms(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) Meter.prototype[Symbol.dispose] = Meter.prototype.free;
+
+/**
+ * @enum {0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11 | 12 | 13 | 14 | 15 | 16 | 17 | 18 | 19 | 20 | 21 | 22 | 23 | 24 | 25 | 26 | 27}
+ */
+export const Param = Object.freeze({
+    Rpm: 0, "0": "Rpm",
+    Blades: 1, "1": "Blades",
+    Gain: 2, "2": "Gain",
+    EngineMix: 3, "3": "EngineMix",
+    CavMix: 4, "4": "CavMix",
+    BioMix: 5, "5": "BioMix",
+    BioType: 6, "6": "BioType",
+    BioRate: 7, "7": "BioRate",
+    ShaftRate: 8, "8": "ShaftRate",
+    Load: 9, "9": "Load",
+    RpmJitter: 10, "10": "RpmJitter",
+    ClassProfile: 11, "11": "ClassProfile",
+    CavitationLevel: 12, "12": "CavitationLevel",
+    Range: 13, "13": "Range",
+    SourceDepth: 14, "14": "SourceDepth",
+    ReverbSend: 15, "15": "ReverbSend",
+    Bearing: 16, "16": "Bearing",
+    TargetStrength: 17, "17": "TargetStrength",
+    TargetLength: 18, "18": "TargetLength",
+    BearingWander: 19, "19": "BearingWander",
+    BearingJitter: 20, "20": "BearingJitter",
+    Elevation: 21, "21": "Elevation",
+    Variation: 22, "22": "Variation",
+    GainDb: 23, "23": "GainDb",
+    EngineMixDb: 24, "24": "EngineMixDb",
+    CavMixDb: 25, "25": "CavMixDb",
+    BioMixDb: 26, "26": "BioMixDb",
+    ReverbSendDb: 27, "27": "ReverbSendDb",
+});
+
+export class ParamInfo {
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(ParamInfo.prototype);
+        obj.__wbg_ptr = ptr;
+        ParamInfoFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        ParamInfoFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_paraminfo_free(ptr, 0);
+    }
+    /**
+     * @returns {number}
+     */
+    get default_ramp_ms() {
+        const ret = wasm.paraminfo_default_ramp_ms(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get default_value() {
+        const ret = wasm.paraminfo_default_value(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get id() {
+        const ret = wasm.paraminfo_id(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get max() {
+        const ret = wasm.paraminfo_max(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get min() {
+        const ret = wasm.paraminfo_min(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {string}
+     */
+    get name() {
+        let deferred1_0;
+        let deferred1_1;
+        try {
+            const ret = wasm.paraminfo_name(this.__wbg_ptr);
+            deferred1_0 = ret[0];
+            deferred1_1 = ret[1];
+            return getStringFromWasm0(ret[0], ret[1]);
+        } finally {
+            wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+        }
+    }
+    /**
+     * @returns {string}
+     */
+    get smoothing() {
+        let deferred1_0;
+        let deferred1_1;
+        try {
+            const ret = wasm.paraminfo_smoothing(this.__wbg_ptr);
+            deferred1_0 = ret[0];
+            deferred1_1 = ret[1];
+            return getStringFromWasm0(ret[0], ret[1]);
+        } finally {
+            wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+        }
+    }
+    /**
+     * @returns {string}
+     */
+    get units() {
+        let deferred1_0;
+        let deferred1_1;
+        try {
+            const ret = wasm.paraminfo_units(this.__wbg_ptr);
+            deferred1_0 = ret[0];
+            deferred1_1 = ret[1];
+            return getStringFromWasm0(ret[0], ret[1]);
+        } finally {
+            wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+        }
+    }
+    /**
+     * @returns {boolean}
+     */
+    get wraps() {
+        const ret = wasm.paraminfo_wraps(this.__wbg_ptr);
+        return ret !== 0;
+    }
+}
+if (Symbol.dispose) ParamInfo.prototype[Symbol.dispose] = ParamInfo.prototype.free;
+
+/**
+ * @enum {0 | 1}
+ */
+export const Precision = Object.freeze({
+    Full: 0, "0": "Full",
+    Reduced: 1, "1": "Reduced",
+});
+
+/**
+ * @enum {0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8}
+ */
+export const ProfileStage = Object.freeze({
+    Control: 0, "0": "Control",
+    Voices: 1, "1": "Voices",
+    Engine: 2, "2": "Engine",
+    Cavitation: 3, "3": "Cavitation",
+    Bio: 4, "4": "Bio",
+    Buses: 5, "5": "Buses",
+    Ambience: 6, "6": "Ambience",
+    Master: 7, "7": "Master",
+    Analysis: 8, "8": "Analysis",
+});
+
+/**
+ * @enum {0 | 1 | 2}
+ */
+export const RecordedChange = Object.freeze({
+    AddVoice: 0, "0": "AddVoice",
+    RemoveVoice: 1, "1": "RemoveVoice",
+    SetParam: 2, "2": "SetParam",
+});
+
+/**
+ * @enum {0 | 1 | 2 | 3}
+ */
+export const ScenarioAction = Object.freeze({
+    AddVoice: 0, "0": "AddVoice",
+    SetParam: 1, "1": "SetParam",
+    TriggerBio: 2, "2": "TriggerBio",
+    RemoveVoice: 3, "3": "RemoveVoice",
+});
+
+export class SlidingSpectrum {
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        SlidingSpectrumFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_slidingspectrum_free(ptr, 0);
+    }
+    /**
+     * @returns {number}
+     */
+    bin_count() {
+        const ret = wasm.slidingspectrum_bin_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    frame_count() {
+        const ret = wasm.slidingspectrum_frame_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    magnitudes() {
+        const ret = wasm.slidingspectrum_magnitudes(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * As magnitudes(), but writes them to `ptr`, a host-owned region of this module's memory
+     * (see alloc_f32_buffer) with room for `len` floats. Fails with OutOfRange for a null or
+     * misaligned buffer, or one shorter than bin_count().
+     *
+     * # Safety
+     *
+     * `ptr` must address `len` writable floats, such as a live alloc_f32_buffer block, that
+     * nothing else reads or writes during the call.
+     * @param {number} ptr
+     * @param {number} len
+     */
+    magnitudes_into(ptr, len) {
+        const ret = wasm.slidingspectrum_magnitudes_into(this.__wbg_ptr, ptr, len);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @returns {number}
+     */
+    memory_bytes() {
+        const ret = wasm.slidingspectrum_memory_bytes(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} size
+     */
+    constructor(size) {
+        const ret = wasm.slidingspectrum_new(size);
+        this.__wbg_ptr = ret >>> 0;
+        SlidingSpectrumFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @param {Float32Array} samples
+     */
+    push(samples) {
+        const ptr0 = passArrayF32ToWasm0(samples, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        wasm.slidingspectrum_push(this.__wbg_ptr, ptr0, len0);
+    }
+    /**
+     * @param {Float32Array} samples
+     * @param {number} hop
+     * @returns {number}
+     */
+    push_frames(samples, hop) {
+        const ptr0 = passArrayF32ToWasm0(samples, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.slidingspectrum_push_frames(this.__wbg_ptr, ptr0, len0, hop);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
+    }
+    reset() {
+        wasm.slidingspectrum_reset(this.__wbg_ptr);
+    }
+    /**
+     * @param {Uint32Array} bins
+     */
+    select_bins(bins) {
+        const ptr0 = passArray32ToWasm0(bins, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.slidingspectrum_select_bins(this.__wbg_ptr, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @returns {number}
+     */
+    size() {
+        const ret = wasm.slidingspectrum_size(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+}
+if (Symbol.dispose) SlidingSpectrum.prototype[Symbol.dispose] = SlidingSpectrum.prototype.free;
+
+export class StageProfile {
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(StageProfile.prototype);
+        obj.__wbg_ptr = ptr;
+        StageProfileFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        StageProfileFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_stageprofile_free(ptr, 0);
+    }
+    /**
+     * @returns {number}
+     */
+    get calls() {
+        const ret = wasm.__wbg_get_stageprofile_calls(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {bigint}
+     */
+    get frames() {
+        const ret = wasm.__wbg_get_stageprofile_frames(this.__wbg_ptr);
+        return BigInt.asUintN(64, ret);
+    }
+    /**
+     * @returns {number}
+     */
+    get ms() {
+        const ret = wasm.__wbg_get_stageprofile_ms(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} arg0
+     */
+    set calls(arg0) {
+        wasm.__wbg_set_stageprofile_calls(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {bigint} arg0
+     */
+    set frames(arg0) {
+        wasm.__wbg_set_stageprofile_frames(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set ms(arg0) {
+        wasm.__wbg_set_stageprofile_ms(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) StageProfile.prototype[Symbol.dispose] = StageProfile.prototype.free;
+
+export class VoiceConfig {
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(VoiceConfig.prototype);
+        obj.__wbg_ptr = ptr;
+        VoiceConfigFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        VoiceConfigFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_voiceconfig_free(ptr, 0);
+    }
+    /**
+     * @param {number} group
+     * @returns {VoiceConfig}
+     */
+    group(group) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.voiceconfig_group(ptr, group);
+        return VoiceConfig.__wrap(ret);
+    }
+    constructor() {
+        const ret = wasm.voiceconfig_new();
+        this.__wbg_ptr = ret >>> 0;
+        VoiceConfigFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @param {Param} param
+     * @param {number} value
+     * @returns {VoiceConfig}
+     */
+    param(param, value) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.voiceconfig_param(ptr, param, value);
+        return VoiceConfig.__wrap(ret);
+    }
+    /**
+     * @param {number} preset_id
+     * @returns {VoiceConfig}
+     */
+    preset(preset_id) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.voiceconfig_preset(ptr, preset_id);
+        return VoiceConfig.__wrap(ret);
+    }
+    /**
+     * @param {number} priority
+     * @returns {VoiceConfig}
+     */
+    priority(priority) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.voiceconfig_priority(ptr, priority);
+        return VoiceConfig.__wrap(ret);
+    }
+    /**
+     * @param {bigint} tag
+     * @returns {VoiceConfig}
+     */
+    tag(tag) {
+        const ptr = this.__destroy_into_raw();
+        const ret = wasm.voiceconfig_tag(ptr, tag);
+        return VoiceConfig.__wrap(ret);
+    }
+}
+if (Symbol.dispose) VoiceConfig.prototype[Symbol.dispose] = VoiceConfig.prototype.free;
+
+export class VoiceState {
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(VoiceState.prototype);
+        obj.__wbg_ptr = ptr;
+        VoiceStateFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        VoiceStateFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_voicestate_free(ptr, 0);
+    }
+    /**
+     * @returns {number}
+     */
+    get bearing() {
+        const ret = wasm.__wbg_get_voicestate_bearing(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {BioType}
+     */
+    get bio_type() {
+        const ret = wasm.__wbg_get_voicestate_bio_type(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get bio_xfade() {
+        const ret = wasm.__wbg_get_headroomreport_peak_db(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {boolean}
+     */
+    get culled() {
+        const ret = wasm.__wbg_get_voicestate_culled(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get current_rpm() {
+        const ret = wasm.__wbg_get_meter_peak(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get current_shaft_rate() {
+        const ret = wasm.__wbg_get_voicestate_current_shaft_rate(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get doppler() {
+        const ret = wasm.__wbg_get_voicestate_doppler(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {BioType}
+     */
+    get prev_bio_type() {
+        const ret = wasm.__wbg_get_voicestate_prev_bio_type(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get propagation_gain() {
+        const ret = wasm.__wbg_get_voicestate_propagation_gain(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get range() {
+        const ret = wasm.__wbg_get_headroomreport_max_reduction_db(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get receive_gain() {
+        const ret = wasm.__wbg_get_voicestate_receive_gain(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get target_rpm() {
+        const ret = wasm.__wbg_get_meter_rms(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get target_shaft_rate() {
+        const ret = wasm.__wbg_get_voicestate_target_shaft_rate(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} arg0
+     */
+    set bearing(arg0) {
+        wasm.__wbg_set_voicestate_bearing(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {BioType} arg0
+     */
+    set bio_type(arg0) {
+        wasm.__wbg_set_voicestate_bio_type(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set bio_xfade(arg0) {
+        wasm.__wbg_set_headroomreport_peak_db(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {boolean} arg0
+     */
+    set culled(arg0) {
+        wasm.__wbg_set_voicestate_culled(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set current_rpm(arg0) {
+        wasm.__wbg_set_meter_peak(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set current_shaft_rate(arg0) {
+        wasm.__wbg_set_voicestate_current_shaft_rate(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set doppler(arg0) {
+        wasm.__wbg_set_voicestate_doppler(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {BioType} arg0
+     */
+    set prev_bio_type(arg0) {
+        wasm.__wbg_set_voicestate_prev_bio_type(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set propagation_gain(arg0) {
+        wasm.__wbg_set_voicestate_propagation_gain(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set range(arg0) {
+        wasm.__wbg_set_headroomreport_max_reduction_db(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set receive_gain(arg0) {
+        wasm.__wbg_set_voicestate_receive_gain(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set target_rpm(arg0) {
+        wasm.__wbg_set_meter_rms(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} arg0
+     */
+    set target_shaft_rate(arg0) {
+        wasm.__wbg_set_voicestate_target_shaft_rate(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) VoiceState.prototype[Symbol.dispose] = VoiceState.prototype.free;
+
+/**
+ * @param {number} len
+ * @returns {number}
+ */
+export function alloc_f32_buffer(len) {
+    const ret = wasm.alloc_f32_buffer(len);
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function bus_hull() {
+    const ret = wasm.bus_hull();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function bus_reverb() {
+    const ret = wasm.bus_reverb();
+    return ret >>> 0;
 }
-if (Symbol.dispose) DspGraph.prototype[Symbol.dispose] = DspGraph.prototype.free;
 
 /**
  * @param {Float32Array} input
@@ -137,11 +2970,164 @@ export function compute_demon_spectrum(input, sample_rate, max_freq_hz, input_ba
     return v2;
 }
 
+/**
+ * compute_demon_spectrum into a host-owned buffer of `len` floats at `ptr` (see
+ * alloc_f32_buffer), bins 0..len one per Hz, so nothing is allocated per call.
+ *
+ * # Safety
+ *
+ * As for DemonAnalyzer::analyze_into.
+ * @param {Float32Array} input
+ * @param {number} sample_rate
+ * @param {number} input_band_low_hz
+ * @param {number} input_band_high_hz
+ * @param {number} envelope_hp_hz
+ * @param {number} decimated_rate_target_hz
+ * @param {number} ptr
+ * @param {number} len
+ */
+export function compute_demon_spectrum_into(input, sample_rate, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz, ptr, len) {
+    const ptr0 = passArrayF32ToWasm0(input, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.compute_demon_spectrum_into(ptr0, len0, sample_rate, input_band_low_hz, input_band_high_hz, envelope_hp_hz, decimated_rate_target_hz, ptr, len);
+    if (ret[1]) {
+        throw takeFromExternrefTable0(ret[0]);
+    }
+}
+
+/**
+ * @returns {number}
+ */
+export function demon_min_input_seconds() {
+    const ret = wasm.demon_min_input_seconds();
+    return ret;
+}
+
+/**
+ * @returns {DemonStatus}
+ */
+export function demon_spectrum_status() {
+    const ret = wasm.demon_spectrum_status();
+    return ret;
+}
+
+/**
+ * @param {DspError} error
+ * @returns {string}
+ */
+export function dsp_error_message(error) {
+    let deferred1_0;
+    let deferred1_1;
+    try {
+        const ret = wasm.dsp_error_message(error);
+        deferred1_0 = ret[0];
+        deferred1_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+}
+
+/**
+ * @returns {number}
+ */
+export function event_automation() {
+    const ret = wasm.event_automation();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function event_bio() {
+    const ret = wasm.event_bio();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function event_ping() {
+    const ret = wasm.event_ping();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function event_quarantine() {
+    const ret = wasm.event_quarantine();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function event_transient() {
+    const ret = wasm.event_transient();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function event_voice_activated() {
+    const ret = wasm.bus_reverb();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function event_voice_deactivated() {
+    const ret = wasm.bus_hull();
+    return ret >>> 0;
+}
+
+/**
+ * Releases a buffer from alloc_f32_buffer, given the same length it was allocated with. A
+ * null address is ignored.
+ *
+ * # Safety
+ *
+ * `ptr` and `len` must come from a single alloc_f32_buffer call, the buffer must not have
+ * been freed already, and nothing may use it afterwards.
+ * @param {number} ptr
+ * @param {number} len
+ */
+export function free_f32_buffer(ptr, len) {
+    wasm.free_f32_buffer(ptr, len);
+}
+
+/**
+ * @returns {number}
+ */
+export function param_bearing() {
+    const ret = wasm.param_bearing();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function param_bearing_jitter() {
+    const ret = wasm.param_bearing_jitter();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function param_bearing_wander() {
+    const ret = wasm.param_bearing_wander();
+    return ret >>> 0;
+}
+
 /**
  * @returns {number}
  */
 export function param_bio_mix() {
-    const ret = wasm.param_bio_mix();
+    const ret = wasm.event_bio();
     return ret >>> 0;
 }
 
@@ -157,7 +3143,7 @@ export function param_bio_rate() {
  * @returns {number}
  */
 export function param_bio_type() {
-    const ret = wasm.param_bio_type();
+    const ret = wasm.event_quarantine();
     return ret >>> 0;
 }
 
@@ -165,7 +3151,7 @@ export function param_bio_type() {
  * @returns {number}
  */
 export function param_blades() {
-    const ret = wasm.param_blades();
+    const ret = wasm.bus_hull();
     return ret >>> 0;
 }
 
@@ -173,7 +3159,7 @@ export function param_blades() {
  * @returns {number}
  */
 export function param_cav_mix() {
-    const ret = wasm.param_cav_mix();
+    const ret = wasm.event_automation();
     return ret >>> 0;
 }
 
@@ -193,11 +3179,27 @@ export function param_class_profile() {
     return ret >>> 0;
 }
 
+/**
+ * @returns {number}
+ */
+export function param_count() {
+    const ret = wasm.param_count();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function param_elevation() {
+    const ret = wasm.param_elevation();
+    return ret >>> 0;
+}
+
 /**
  * @returns {number}
  */
 export function param_engine_mix() {
-    const ret = wasm.param_engine_mix();
+    const ret = wasm.event_transient();
     return ret >>> 0;
 }
 
@@ -205,10 +3207,19 @@ export function param_engine_mix() {
  * @returns {number}
  */
 export function param_gain() {
-    const ret = wasm.param_gain();
+    const ret = wasm.event_ping();
     return ret >>> 0;
 }
 
+/**
+ * @param {number} param_id
+ * @returns {ParamInfo | undefined}
+ */
+export function param_info(param_id) {
+    const ret = wasm.param_info(param_id);
+    return ret === 0 ? undefined : ParamInfo.__wrap(ret);
+}
+
 /**
  * @returns {number}
  */
@@ -217,11 +3228,27 @@ export function param_load() {
     return ret >>> 0;
 }
 
+/**
+ * @returns {number}
+ */
+export function param_range() {
+    const ret = wasm.param_range();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function param_reverb_send() {
+    const ret = wasm.param_reverb_send();
+    return ret >>> 0;
+}
+
 /**
  * @returns {number}
  */
 export function param_rpm() {
-    const ret = wasm.param_rpm();
+    const ret = wasm.bus_reverb();
     return ret >>> 0;
 }
 
@@ -241,12 +3268,111 @@ export function param_shaft_rate() {
     return ret >>> 0;
 }
 
+/**
+ * @returns {number}
+ */
+export function param_source_depth() {
+    const ret = wasm.param_source_depth();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function param_target_length() {
+    const ret = wasm.param_target_length();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function param_target_strength() {
+    const ret = wasm.param_target_strength();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function preset_count() {
+    const ret = wasm.preset_count();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function preset_destroyer() {
+    const ret = wasm.event_ping();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function preset_diesel_sub() {
+    const ret = wasm.event_transient();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function preset_merchant() {
+    const ret = wasm.bus_hull();
+    return ret >>> 0;
+}
+
+/**
+ * @param {number} preset_id
+ * @returns {string | undefined}
+ */
+export function preset_name(preset_id) {
+    const ret = wasm.preset_name(preset_id);
+    let v1;
+    if (ret[0] !== 0) {
+        v1 = getStringFromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    }
+    return v1;
+}
+
+/**
+ * @returns {number}
+ */
+export function preset_ssn() {
+    const ret = wasm.event_automation();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function preset_trawler() {
+    const ret = wasm.bus_reverb();
+    return ret >>> 0;
+}
+
+/**
+ * @returns {number}
+ */
+export function wasm_memory_bytes() {
+    const ret = wasm.wasm_memory_bytes();
+    return ret >>> 0;
+}
+
 function __wbg_get_imports() {
     const import0 = {
         __proto__: null,
         __wbg___wbindgen_throw_be289d5034ed271b: function(arg0, arg1) {
             throw new Error(getStringFromWasm0(arg0, arg1));
         },
+        __wbindgen_cast_0000000000000001: function(arg0) {
+            // Cast intrinsic for `F64 -> Externref`.
+            const ret = arg0;
+            return ret;
+        },
         __wbindgen_init_externref_table: function() {
             const table = wasm.__wbindgen_externrefs;
             const offset = table.grow(4);
@@ -263,15 +3389,66 @@ function __wbg_get_imports() {
     };
 }
 
+const DemonAnalyzerFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_demonanalyzer_free(ptr >>> 0, 1));
 const DspGraphFinalization = (typeof FinalizationRegistry === 'undefined')
     ? { register: () => {}, unregister: () => {} }
     : new FinalizationRegistry(ptr => wasm.__wbg_dspgraph_free(ptr >>> 0, 1));
+const DspGraphBuilderFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_dspgraphbuilder_free(ptr >>> 0, 1));
+const HeadroomReportFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_headroomreport_free(ptr >>> 0, 1));
+const MemoryUsageFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_memoryusage_free(ptr >>> 0, 1));
+const MeterFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_meter_free(ptr >>> 0, 1));
+const ParamInfoFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_paraminfo_free(ptr >>> 0, 1));
+const SlidingSpectrumFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_slidingspectrum_free(ptr >>> 0, 1));
+const StageProfileFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_stageprofile_free(ptr >>> 0, 1));
+const VoiceConfigFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_voiceconfig_free(ptr >>> 0, 1));
+const VoiceStateFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_voicestate_free(ptr >>> 0, 1));
+
+function _assertClass(instance, klass) {
+    if (!(instance instanceof klass)) {
+        throw new Error(`expected instance of ${klass.name}`);
+    }
+}
 
 function getArrayF32FromWasm0(ptr, len) {
     ptr = ptr >>> 0;
     return getFloat32ArrayMemory0().subarray(ptr / 4, ptr / 4 + len);
 }
 
+function getArrayF64FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getFloat64ArrayMemory0().subarray(ptr / 8, ptr / 8 + len);
+}
+
+function getArrayI16FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getInt16ArrayMemory0().subarray(ptr / 2, ptr / 2 + len);
+}
+
+function getArrayU32FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getUint32ArrayMemory0().subarray(ptr / 4, ptr / 4 + len);
+}
+
 let cachedFloat32ArrayMemory0 = null;
 function getFloat32ArrayMemory0() {
     if (cachedFloat32ArrayMemory0 === null || cachedFloat32ArrayMemory0.byteLength === 0) {
@@ -280,11 +3457,35 @@ function getFloat32ArrayMemory0() {
     return cachedFloat32ArrayMemory0;
 }
 
+let cachedFloat64ArrayMemory0 = null;
+function getFloat64ArrayMemory0() {
+    if (cachedFloat64ArrayMemory0 === null || cachedFloat64ArrayMemory0.byteLength === 0) {
+        cachedFloat64ArrayMemory0 = new Float64Array(wasm.memory.buffer);
+    }
+    return cachedFloat64ArrayMemory0;
+}
+
+let cachedInt16ArrayMemory0 = null;
+function getInt16ArrayMemory0() {
+    if (cachedInt16ArrayMemory0 === null || cachedInt16ArrayMemory0.byteLength === 0) {
+        cachedInt16ArrayMemory0 = new Int16Array(wasm.memory.buffer);
+    }
+    return cachedInt16ArrayMemory0;
+}
+
 function getStringFromWasm0(ptr, len) {
     ptr = ptr >>> 0;
     return decodeText(ptr, len);
 }
 
+let cachedUint32ArrayMemory0 = null;
+function getUint32ArrayMemory0() {
+    if (cachedUint32ArrayMemory0 === null || cachedUint32ArrayMemory0.byteLength === 0) {
+        cachedUint32ArrayMemory0 = new Uint32Array(wasm.memory.buffer);
+    }
+    return cachedUint32ArrayMemory0;
+}
+
 let cachedUint8ArrayMemory0 = null;
 function getUint8ArrayMemory0() {
     if (cachedUint8ArrayMemory0 === null || cachedUint8ArrayMemory0.byteLength === 0) {
@@ -293,6 +3494,13 @@ function getUint8ArrayMemory0() {
     return cachedUint8ArrayMemory0;
 }
 
+function passArray32ToWasm0(arg, malloc) {
+    const ptr = malloc(arg.length * 4, 4) >>> 0;
+    getUint32ArrayMemory0().set(arg, ptr / 4);
+    WASM_VECTOR_LEN = arg.length;
+    return ptr;
+}
+
 function passArrayF32ToWasm0(arg, malloc) {
     const ptr = malloc(arg.length * 4, 4) >>> 0;
     getFloat32ArrayMemory0().set(arg, ptr / 4);
@@ -300,6 +3508,56 @@ function passArrayF32ToWasm0(arg, malloc) {
     return ptr;
 }
 
+function passArrayF64ToWasm0(arg, malloc) {
+    const ptr = malloc(arg.length * 8, 8) >>> 0;
+    getFloat64ArrayMemory0().set(arg, ptr / 8);
+    WASM_VECTOR_LEN = arg.length;
+    return ptr;
+}
+
+function passStringToWasm0(arg, malloc, realloc) {
+    if (realloc === undefined) {
+        const buf = cachedTextEncoder.encode(arg);
+        const ptr = malloc(buf.length, 1) >>> 0;
+        getUint8ArrayMemory0().subarray(ptr, ptr + buf.length).set(buf);
+        WASM_VECTOR_LEN = buf.length;
+        return ptr;
+    }
+
+    let len = arg.length;
+    let ptr = malloc(len, 1) >>> 0;
+
+    const mem = getUint8ArrayMemory0();
+
+    let offset = 0;
+
+    for (; offset < len; offset++) {
+        const code = arg.charCodeAt(offset);
+        if (code > 0x7F) break;
+        mem[ptr + offset] = code;
+    }
+    if (offset !== len) {
+        if (offset !== 0) {
+            arg = arg.slice(offset);
+        }
+        ptr = realloc(ptr, len, len = offset + arg.length * 3, 1) >>> 0;
+        const view = getUint8ArrayMemory0().subarray(ptr + offset, ptr + len);
+        const ret = cachedTextEncoder.encodeInto(arg, view);
+
+        offset += ret.written;
+        ptr = realloc(ptr, len, offset, 1) >>> 0;
+    }
+
+    WASM_VECTOR_LEN = offset;
+    return ptr;
+}
+
+function takeFromExternrefTable0(idx) {
+    const value = wasm.__wbindgen_externrefs.get(idx);
+    wasm.__externref_table_dealloc(idx);
+    return value;
+}
+
 let cachedTextDecoder = new TextDecoder('utf-8', { ignoreBOM: true, fatal: true });
 cachedTextDecoder.decode();
 const MAX_SAFARI_DECODE_BYTES = 2146435072;
@@ -314,6 +3572,19 @@ function decodeText(ptr, len) {
     return cachedTextDecoder.decode(getUint8ArrayMemory0().subarray(ptr, ptr + len));
 }
 
+const cachedTextEncoder = new TextEncoder();
+
+if (!('encodeInto' in cachedTextEncoder)) {
+    cachedTextEncoder.encodeInto = function (arg, view) {
+        const buf = cachedTextEncoder.encode(arg);
+        view.set(buf);
+        return {
+            read: arg.length,
+            written: buf.length
+        };
+    };
+}
+
 let WASM_VECTOR_LEN = 0;
 
 let wasmModule, wasm;
@@ -321,6 +3592,9 @@ function __wbg_finalize_init(instance, module) {
     wasm = instance.exports;
     wasmModule = module;
     cachedFloat32ArrayMemory0 = null;
+    cachedFloat64ArrayMemory0 = null;
+    cachedInt16ArrayMemory0 = null;
+    cachedUint32ArrayMemory0 = null;
     cachedUint8ArrayMemory0 = null;
     wasm.__wbindgen_start();
     return wasm;
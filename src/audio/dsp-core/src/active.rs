@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+
+use crate::propagation::SOUND_SPEED;
+use crate::sine::{cosine, sine};
+use crate::{clamp, rand_signed, TWO_PI};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Bow, sail and stern returns, as fractions of hull length from amidships.
+const HIGHLIGHTS: [(f32, f32); 3] = [(0.5, 0.8), (0.15, 1.0), (-0.5, 0.6)];
+
+// The transmitted pulse: a CW tone when both frequencies match, otherwise a linear sweep.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Ping {
+    pub(crate) start_hz: f32,
+    pub(crate) end_hz: f32,
+    pub(crate) duration_s: f32,
+    pub(crate) level: f32,
+}
+
+// Everything about one target that shapes its echo, frozen at the moment of transmission.
+pub(crate) struct EchoGeometry {
+    pub(crate) range: f32,
+    // Passive (one-way) Doppler ratio; the echo travels both ways so it applies twice.
+    pub(crate) doppler: f32,
+    // Target course relative to the line of sight back to own-ship, radians.
+    pub(crate) aspect: f32,
+    // Two-way propagation gain, already squared.
+    pub(crate) path_gain: f32,
+    pub(crate) target_strength_db: f32,
+    pub(crate) length: f32,
+}
+
+// Pending or sounding echo for one voice. Each hull highlight is its own time-shifted copy of
+// the Doppler-compressed pulse, so a bow-on target smears into a train of returns while a
+// beam-on target stacks them into one strong specular echo.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Echo {
+    armed: bool,
+    countdown: u32,
+    elapsed: u32,
+    start_hz: f32,
+    sweep_rate: f32,
+    duration: u32,
+    highlights: [(u32, f32); HIGHLIGHTS.len()],
+    last: u32,
+}
+
+impl Echo {
+    pub(crate) fn new() -> Self {
+        Self {
+            armed: false,
+            countdown: 0,
+            elapsed: 0,
+            start_hz: 0.0,
+            sweep_rate: 0.0,
+            duration: 0,
+            highlights: [(0, 0.0); HIGHLIGHTS.len()],
+            last: 0,
+        }
+    }
+
+    pub(crate) fn schedule(&mut self, ping: &Ping, geo: &EchoGeometry, sample_rate: f32, rng: &mut u32) {
+        let compression = clamp(geo.doppler * geo.doppler, 0.25, 4.0);
+        let duration_s = ping.duration_s / compression;
+        self.start_hz = clamp(ping.start_hz * compression, 20.0, sample_rate * 0.45);
+        let end_hz = clamp(ping.end_hz * compression, 20.0, sample_rate * 0.45);
+        self.sweep_rate = (end_hz - self.start_hz) / duration_s;
+        self.duration = (duration_s * sample_rate).max(1.0) as u32;
+
+        // Broadside presents the whole hull as a mirror; end-on it is a few scattered points.
+        let beam = geo.aspect.sin().abs();
+        let ts_db = geo.target_strength_db + 6.0 * beam - 3.0;
+        // Active source levels sit some 20 dB above anything a contact radiates.
+        let amp = ping.level * 10.0 * 10.0f32.powf(ts_db / 20.0) * geo.path_gain;
+
+        // Highlights nearer to us arrive first; offsets are relative to the earliest one.
+        let along = geo.aspect.cos();
+        let mut offsets = [0.0f32; HIGHLIGHTS.len()];
+        for (offset, (pos, _)) in offsets.iter_mut().zip(HIGHLIGHTS.iter()) {
+            *offset = -2.0 * pos * geo.length * along / SOUND_SPEED * sample_rate;
+        }
+        let earliest = offsets.iter().cloned().fold(f32::INFINITY, f32::min);
+        self.last = 0;
+        for (slot, (offset, (_, weight))) in self.highlights.iter_mut().zip(offsets.iter().zip(HIGHLIGHTS.iter())) {
+            let jitter = 1.0 + 0.25 * rand_signed(rng);
+            let delay = (offset - earliest) as u32;
+            *slot = (delay, amp * weight * jitter);
+            self.last = self.last.max(delay);
+        }
+
+        let two_way = (2.0 * geo.range / SOUND_SPEED + earliest / sample_rate).max(0.0);
+        self.countdown = (two_way * sample_rate) as u32;
+        self.elapsed = 0;
+        self.armed = true;
+    }
+
+    pub(crate) fn rescale(&mut self, ratio: f32) {
+        self.countdown = crate::scale_samples(self.countdown, ratio);
+        self.elapsed = crate::scale_samples(self.elapsed, ratio);
+        self.duration = crate::scale_samples(self.duration, ratio).max(1);
+        self.last = crate::scale_samples(self.last, ratio);
+        for (offset, _) in &mut self.highlights {
+            *offset = crate::scale_samples(*offset, ratio);
+        }
+    }
+
+    // An echo is on its way or still sounding.
+    pub(crate) fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    // Frame within the next `frames` at which the first highlight reaches the receiver.
+    pub(crate) fn arrival_within(&self, frames: usize) -> Option<usize> {
+        let countdown = self.countdown as usize;
+        (self.armed && self.elapsed == 0 && countdown < frames).then_some(countdown)
+    }
+
+    #[inline]
+    pub(crate) fn tick(&mut self, sample_rate: f32) -> f32 {
+        if !self.armed {
+            return 0.0;
+        }
+        if self.countdown > 0 {
+            self.countdown -= 1;
+            return 0.0;
+        }
+
+        let mut out = 0.0;
+        for &(offset, amp) in &self.highlights {
+            if self.elapsed < offset || self.elapsed - offset >= self.duration {
+                continue;
+            }
+            let n = self.elapsed - offset;
+            let t = n as f32 / sample_rate;
+            let window = 0.5 - 0.5 * cosine(TWO_PI * n as f32 / self.duration as f32);
+            let phase = TWO_PI * (self.start_hz * t + 0.5 * self.sweep_rate * t * t);
+            out += sine(phase) * window * amp;
+        }
+
+        self.elapsed += 1;
+        if self.elapsed >= self.last + self.duration {
+            self.armed = false;
+        }
+        out
+    }
+}
+
+// Band-limited reverberation following a ping: a volume-scatter tail from time zero plus
+// surface and bottom ridges at their two-way arrival times.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct PingReverb {
+    active: bool,
+    level: f32,
+    t: f32,
+    surface_t: f32,
+    bottom_t: f32,
+    rng: u32,
+    // Two-pole resonator state centred on the ping frequency.
+    y1: f32,
+    y2: f32,
+    b1: f32,
+    b2: f32,
+}
+
+impl PingReverb {
+    pub(crate) fn new(seed: u32) -> Self {
+        Self {
+            active: false,
+            level: 0.0,
+            t: 0.0,
+            surface_t: 0.0,
+            bottom_t: 0.0,
+            rng: seed | 1,
+            y1: 0.0,
+            y2: 0.0,
+            b1: 0.0,
+            b2: 0.0,
+        }
+    }
+
+    pub(crate) fn reseed(&mut self, seed: u32) {
+        self.rng = seed | 1;
+    }
+
+    // Cuts off the reverberation of the last ping.
+    pub(crate) fn clear(&mut self) {
+        self.active = false;
+        self.y1 = 0.0;
+        self.y2 = 0.0;
+    }
+
+    // Re-derives the resonator for a new rate from its current pole, keeping centre frequency,
+    // bandwidth and level.
+    pub(crate) fn set_sample_rate(&mut self, old_rate: f32, new_rate: f32) {
+        if self.b2 >= 0.0 {
+            return;
+        }
+        let r = (-self.b2).sqrt();
+        let omega = clamp(self.b1 / (2.0 * r), -1.0, 1.0).acos();
+        let centre = clamp(omega * old_rate / TWO_PI, 20.0, new_rate * 0.45);
+        let bandwidth = -r.ln() * old_rate / core::f32::consts::PI;
+        let new_r = (-core::f32::consts::PI * bandwidth / new_rate).exp();
+        self.b1 = 2.0 * new_r * (TWO_PI * centre / new_rate).cos();
+        self.b2 = -new_r * new_r;
+        self.level *= (1.0 - new_r) / (1.0 - r);
+    }
+
+    pub(crate) fn start(&mut self, ping: &Ping, level: f32, listener_depth: f32, water_depth: f32, sample_rate: f32) {
+        if level <= 0.0 {
+            return;
+        }
+        let centre = clamp(0.5 * (ping.start_hz + ping.end_hz), 20.0, sample_rate * 0.45);
+        let bandwidth = (ping.end_hz - ping.start_hz).abs().max(1.0 / ping.duration_s.max(0.001)) + 50.0;
+        let r = (-core::f32::consts::PI * bandwidth / sample_rate).exp();
+        self.b1 = 2.0 * r * (TWO_PI * centre / sample_rate).cos();
+        self.b2 = -r * r;
+        self.level = level * ping.level * (1.0 - r);
+        self.surface_t = 2.0 * listener_depth.max(0.0) / SOUND_SPEED;
+        self.bottom_t = 2.0 * (water_depth - listener_depth).max(0.0) / SOUND_SPEED;
+        self.t = 0.0;
+        self.active = true;
+    }
+
+    #[inline]
+    fn envelope(&self) -> f32 {
+        let volume = (0.05 / (self.t + 0.05)).powf(1.5);
+        let ridge = |at: f32, tau: f32| {
+            if self.t < at {
+                0.0
+            } else {
+                (-(self.t - at) / tau).exp()
+            }
+        };
+        volume + 0.5 * ridge(self.surface_t, 0.25) + ridge(self.bottom_t, 0.4)
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.active
+    }
+
+    #[inline]
+    pub(crate) fn tick(&mut self, sample_rate: f32) -> f32 {
+        let env = self.envelope();
+        self.t += 1.0 / sample_rate;
+        if env < 1.0e-4 && self.t > self.bottom_t {
+            self.active = false;
+            return 0.0;
+        }
+        let x = rand_signed(&mut self.rng);
+        let y = x + self.b1 * self.y1 + self.b2 * self.y2;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y * env * self.level
+    }
+}
@@ -0,0 +1,380 @@
+use serde::{Deserialize, Serialize};
+
+use crate::sine::sine;
+use crate::spatial::pan_gains;
+use crate::{clamp, decay, lowpass, rand_signed, smoothing, TWO_PI};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+pub(crate) const NOISE_SECTORS: usize = 8;
+
+const SECTOR_WIDTH: f32 = TWO_PI / NOISE_SECTORS as f32;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct NoiseSector {
+    level: f32,
+    // 0 = distant-shipping rumble, 1 = bright surf/wind hiss.
+    tilt: f32,
+    rng: u32,
+    lp_a: f32,
+    lp_b: f32,
+}
+
+impl NoiseSector {
+    fn new(seed: u32) -> Self {
+        Self {
+            level: 0.0,
+            tilt: 0.5,
+            rng: seed,
+            lp_a: 0.0,
+            lp_b: 0.0,
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self, (low_lp, high_lp): (f32, f32)) -> f32 {
+        let white = rand_signed(&mut self.rng);
+        self.lp_a += low_lp * (white - self.lp_a);
+        self.lp_b += high_lp * (white - self.lp_b);
+        let low = self.lp_a * 5.0;
+        let high = (white - self.lp_b) * 0.6 + self.lp_b * 0.4;
+        (low * (1.0 - self.tilt) + high * self.tilt) * self.level
+    }
+}
+
+// Anisotropic ambient field: each 45-degree sector (0 = north, clockwise) carries its own
+// independent noise stream so anything that listens in a direction hears a different floor.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct DirectionalNoiseField {
+    sectors: [NoiseSector; NOISE_SECTORS],
+    look_bearing: f32,
+    look_width: f32,
+    beam_gain: [f32; NOISE_SECTORS],
+    active: bool,
+}
+
+impl DirectionalNoiseField {
+    pub(crate) fn new(seed: u32) -> Self {
+        let mut field = Self {
+            sectors: [NoiseSector::new(1); NOISE_SECTORS],
+            look_bearing: 0.0,
+            look_width: TWO_PI,
+            beam_gain: [1.0; NOISE_SECTORS],
+            active: false,
+        };
+        field.reseed(seed);
+        field.refresh_beam();
+        field
+    }
+
+    pub(crate) fn reseed(&mut self, seed: u32) {
+        for (i, sector) in self.sectors.iter_mut().enumerate() {
+            sector.rng = seed.wrapping_add((i as u32 + 1).wrapping_mul(0x85eb_ca6b)) | 1;
+        }
+    }
+
+    pub(crate) fn sector_bearing(sector: usize) -> f32 {
+        sector as f32 * SECTOR_WIDTH
+    }
+
+    pub(crate) fn set_sector(&mut self, sector: usize, level: f32, tilt: f32) -> bool {
+        if sector >= NOISE_SECTORS || !level.is_finite() || !tilt.is_finite() {
+            return false;
+        }
+        self.sectors[sector].level = clamp(level, 0.0, 2.0);
+        self.sectors[sector].tilt = clamp(tilt, 0.0, 1.0);
+        self.active = self.sectors.iter().any(|s| s.level > 0.0);
+        true
+    }
+
+    pub(crate) fn set_look(&mut self, bearing_rad: f32, width_rad: f32) {
+        self.look_bearing = bearing_rad.rem_euclid(TWO_PI);
+        self.look_width = clamp(width_rad, 0.05, TWO_PI);
+        self.refresh_beam();
+    }
+
+    // Raised-cosine receive lobe whose half-power points sit at +/- width/2, with a small
+    // sidelobe floor so off-axis sectors never disappear entirely.
+    fn refresh_beam(&mut self) {
+        if self.look_width >= TWO_PI - 1.0e-3 {
+            self.beam_gain = [1.0; NOISE_SECTORS];
+            return;
+        }
+        let half = 0.5 + 0.5 * (self.look_width * 0.5).cos();
+        let k = 0.5f32.ln() / half.max(1.0e-4).ln();
+        for (i, gain) in self.beam_gain.iter_mut().enumerate() {
+            let diff = Self::sector_bearing(i) - self.look_bearing;
+            let lobe = (0.5 + 0.5 * diff.cos()).powf(k);
+            *gain = lobe.max(0.05);
+        }
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.active
+    }
+
+    // Low-pass coefficients for the sectors' low band (corner 116 Hz) and the split under their
+    // bright band (1.9 kHz), for the tick variants below. Computed once per block.
+    pub(crate) fn filters(sample_rate: f32) -> (f32, f32) {
+        (lowpass(116.0, sample_rate), lowpass(1910.0, sample_rate))
+    }
+
+    // Advances every sector by one sample and returns the mono mix seen through the look beam.
+    #[inline]
+    pub(crate) fn tick(&mut self, filters: (f32, f32)) -> f32 {
+        let mut out = 0.0;
+        for i in 0..NOISE_SECTORS {
+            out += self.sectors[i].tick(filters) * self.beam_gain[i];
+        }
+        out * 0.35
+    }
+
+    // Advances every sector by one sample and returns them unsteered, for receivers that do
+    // their own spatial filtering.
+    #[inline]
+    pub(crate) fn tick_sectors(&mut self, filters: (f32, f32)) -> [f32; NOISE_SECTORS] {
+        let mut out = [0.0; NOISE_SECTORS];
+        for (v, sector) in out.iter_mut().zip(self.sectors.iter_mut()) {
+            *v = sector.tick(filters) * 0.35;
+        }
+        out
+    }
+
+    // Pan gains that place each sector at its bearing relative to a listener on `heading`,
+    // for tick_stereo. Cheap enough per block, not per sample.
+    pub(crate) fn stereo_pan(heading: f32) -> [(f32, f32); NOISE_SECTORS] {
+        core::array::from_fn(|i| pan_gains(Self::sector_bearing(i) - heading))
+    }
+
+    // Stereo variant: each sector is panned by `pan` (see stereo_pan) before summing.
+    #[inline]
+    pub(crate) fn tick_stereo(
+        &mut self,
+        pan: &[(f32, f32); NOISE_SECTORS],
+        filters: (f32, f32),
+    ) -> (f32, f32) {
+        let mut left = 0.0;
+        let mut right = 0.0;
+        let steered = self.sectors.iter_mut().zip(self.beam_gain);
+        for ((sector, gain), (pan_l, pan_r)) in steered.zip(pan) {
+            let v = sector.tick(filters) * gain;
+            left += v * pan_l;
+            right += v * pan_r;
+        }
+        (left * 0.35, right * 0.35)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub(crate) struct WeatherLevels {
+    pub(crate) sea_state: f32,
+    // Rain and wind intensities, 0 (none) to 1 (torrential / gale).
+    pub(crate) rain: f32,
+    pub(crate) wind: f32,
+}
+
+impl WeatherLevels {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        Self {
+            sea_state: a.sea_state + (b.sea_state - a.sea_state) * t,
+            rain: a.rain + (b.rain - a.rain) * t,
+            wind: a.wind + (b.wind - a.wind) * t,
+        }
+    }
+
+    fn sanitized(self) -> Self {
+        Self {
+            sea_state: clamp(self.sea_state, 0.0, 9.0),
+            rain: clamp(self.rain, 0.0, 1.0),
+            wind: clamp(self.wind, 0.0, 1.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum WeatherPhase {
+    Steady,
+    Building,
+    Peak,
+    Abating,
+}
+
+// Slow weather state machine. Levels glide along a smoothstep ramp evaluated every sample so
+// retargeting never steps, and storms walk Building -> Peak -> Abating -> Steady on their own.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Weather {
+    phase: WeatherPhase,
+    current: WeatherLevels,
+    ramp_from: WeatherLevels,
+    ramp_to: WeatherLevels,
+    ramp_pos: f32,
+    ramp_step: f32,
+    calm: WeatherLevels,
+    hold_samples: u32,
+    abate_s: f32,
+    rng: u32,
+    wind_lp: f32,
+    wind_gust: f32,
+    rain_lp: f32,
+    drop_env: f32,
+    drop_phase: f32,
+    drop_hz: f32,
+}
+
+impl Weather {
+    pub(crate) fn new(sea_state: f32, seed: u32) -> Self {
+        let levels = WeatherLevels {
+            sea_state,
+            rain: 0.0,
+            wind: 0.0,
+        };
+        Self {
+            phase: WeatherPhase::Steady,
+            current: levels,
+            ramp_from: levels,
+            ramp_to: levels,
+            ramp_pos: 1.0,
+            ramp_step: 0.0,
+            calm: levels,
+            hold_samples: 0,
+            abate_s: 0.0,
+            rng: seed | 1,
+            wind_lp: 0.0,
+            wind_gust: 0.0,
+            rain_lp: 0.0,
+            drop_env: 0.0,
+            drop_phase: 0.0,
+            drop_hz: 9000.0,
+        }
+    }
+
+    pub(crate) fn reseed(&mut self, seed: u32) {
+        self.rng = seed | 1;
+    }
+
+    pub(crate) fn levels(&self) -> WeatherLevels {
+        self.current
+    }
+
+    fn start_ramp(&mut self, target: WeatherLevels, seconds: f32, sample_rate: f32) {
+        self.ramp_from = self.current;
+        self.ramp_to = target.sanitized();
+        let samples = (seconds.max(0.0) * sample_rate).max(1.0);
+        self.ramp_step = 1.0 / samples;
+        self.ramp_pos = 0.0;
+    }
+
+    // Keeps ramp and storm timings in seconds across a sample-rate change.
+    pub(crate) fn rescale(&mut self, ratio: f32) {
+        self.ramp_step /= ratio;
+        self.hold_samples = crate::scale_samples(self.hold_samples, ratio);
+    }
+
+    // Jumps straight to `levels`, cancelling any ramp or storm in progress.
+    pub(crate) fn snap(&mut self, levels: WeatherLevels) {
+        let levels = levels.sanitized();
+        self.phase = WeatherPhase::Steady;
+        self.current = levels;
+        self.ramp_from = levels;
+        self.ramp_to = levels;
+        self.ramp_pos = 1.0;
+    }
+
+    pub(crate) fn retarget(&mut self, target: WeatherLevels, seconds: f32, sample_rate: f32) {
+        self.phase = WeatherPhase::Steady;
+        self.start_ramp(target, seconds, sample_rate);
+    }
+
+    pub(crate) fn start_storm(
+        &mut self,
+        peak: WeatherLevels,
+        build_s: f32,
+        hold_s: f32,
+        abate_s: f32,
+        sample_rate: f32,
+    ) {
+        self.calm = self.current;
+        self.phase = WeatherPhase::Building;
+        self.hold_samples = (hold_s.max(0.0) * sample_rate) as u32;
+        self.abate_s = abate_s.max(0.0);
+        self.start_ramp(peak, build_s, sample_rate);
+    }
+
+    pub(crate) fn is_audible(&self) -> bool {
+        self.current.rain > 0.0 || self.current.wind > 0.0 || self.ramp_pos < 1.0
+    }
+
+    #[inline]
+    fn advance(&mut self, sample_rate: f32) {
+        if self.ramp_pos < 1.0 {
+            self.ramp_pos = (self.ramp_pos + self.ramp_step).min(1.0);
+            let t = self.ramp_pos;
+            let eased = t * t * (3.0 - 2.0 * t);
+            self.current = WeatherLevels::lerp(&self.ramp_from, &self.ramp_to, eased);
+            return;
+        }
+
+        match self.phase {
+            WeatherPhase::Building => self.phase = WeatherPhase::Peak,
+            WeatherPhase::Peak => {
+                if self.hold_samples > 0 {
+                    self.hold_samples -= 1;
+                } else {
+                    self.phase = WeatherPhase::Abating;
+                    let calm = self.calm;
+                    self.start_ramp(calm, self.abate_s, sample_rate);
+                }
+            }
+            WeatherPhase::Abating => self.phase = WeatherPhase::Steady,
+            WeatherPhase::Steady => {}
+        }
+    }
+
+    // Keeps ramps and storm timers moving while the bed itself is silent.
+    pub(crate) fn skip(&mut self, frames: usize, sample_rate: f32) {
+        if self.phase == WeatherPhase::Steady && self.ramp_pos >= 1.0 {
+            return;
+        }
+        for _ in 0..frames {
+            self.advance(sample_rate);
+        }
+    }
+
+    // Advances the state machine one sample and returns the isotropic wind/rain bed.
+    #[inline]
+    pub(crate) fn tick(&mut self, sample_rate: f32) -> f32 {
+        self.advance(sample_rate);
+
+        let white = rand_signed(&mut self.rng);
+        let wind = self.current.wind;
+        let rain = self.current.rain;
+
+        // Wind-driven breaking: mid-band noise with slow gusting.
+        self.wind_lp += lowpass(755.0, sample_rate) * (white - self.wind_lp);
+        self.wind_gust += smoothing(520.0, sample_rate) * (rand_signed(&mut self.rng) * 40.0 - self.wind_gust);
+        let gust = clamp(0.75 + 0.25 * self.wind_gust, 0.4, 1.2);
+        let wind_out = self.wind_lp * 1.6 * wind * wind.sqrt() * gust;
+
+        // Rain: bright hiss plus sparse drop-bubble pings.
+        self.rain_lp += lowpass(7640.0, sample_rate) * (white - self.rain_lp);
+        let hiss = white - self.rain_lp;
+        let u = (crate::xorshift32(&mut self.rng) as f32) / u32::MAX as f32;
+        if u < rain * 0.004 {
+            self.drop_env = 0.4 + 0.6 * u / (rain * 0.004);
+            self.drop_hz = clamp(13000.0 + 4000.0 * self.drop_env, 0.0, sample_rate * 0.45);
+            self.drop_phase = 0.0;
+        }
+        let mut drop = 0.0;
+        if self.drop_env > 0.0001 {
+            self.drop_phase += TWO_PI * self.drop_hz / sample_rate;
+            if self.drop_phase >= TWO_PI {
+                self.drop_phase -= TWO_PI;
+            }
+            drop = sine(self.drop_phase) * self.drop_env;
+            self.drop_env *= decay(0.29, sample_rate);
+        }
+        let rain_out = (hiss * 0.22 + drop * 0.35) * rain;
+
+        (wind_out + rain_out) * 0.4
+    }
+}
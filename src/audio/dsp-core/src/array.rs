@@ -0,0 +1,168 @@
+use alloc::{vec, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::propagation::SOUND_SPEED;
+use crate::{clamp, lowpass, rand_signed, vec_bytes};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+pub(crate) const MAX_ARRAY_ELEMENTS: usize = 32;
+
+// Towed line array trailing straight astern of own-ship. Element 0 is the forward-most
+// hydrophone; each element also picks up its own turbulent flow noise.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct TowedArray {
+    pub(crate) elements: usize,
+    spacing: f32,
+    flow_level: f32,
+    rng: [u32; MAX_ARRAY_ELEMENTS],
+    flow_lp: [f32; MAX_ARRAY_ELEMENTS],
+}
+
+impl TowedArray {
+    pub(crate) fn new(seed: u32) -> Self {
+        let mut array = Self {
+            elements: 0,
+            spacing: 1.5,
+            flow_level: 0.3,
+            rng: [0; MAX_ARRAY_ELEMENTS],
+            flow_lp: [0.0; MAX_ARRAY_ELEMENTS],
+        };
+        array.reseed(seed);
+        array
+    }
+
+    pub(crate) fn reseed(&mut self, seed: u32) {
+        for (i, state) in self.rng.iter_mut().enumerate() {
+            *state = seed.wrapping_add((i as u32 + 1).wrapping_mul(0x9e37_79b9)) | 1;
+        }
+    }
+
+    pub(crate) fn configure(&mut self, elements: usize, spacing_m: f32, flow_level: f32) {
+        self.elements = elements.min(MAX_ARRAY_ELEMENTS);
+        self.spacing = clamp(spacing_m, 0.1, 8.0);
+        self.flow_level = clamp(flow_level, 0.0, 2.0);
+    }
+
+    // Longest element delay, in samples, over all bearings.
+    fn max_delay(&self, sample_rate: f32) -> f32 {
+        2.0 * self.elements.saturating_sub(1) as f32 * self.spacing / SOUND_SPEED * sample_rate
+    }
+
+    // Plane-wave arrival delay (samples) at element `k` for a relative bearing, offset so the
+    // earliest element over any bearing sits at zero. Port and starboard are indistinguishable,
+    // as they are on a real line array.
+    #[inline]
+    fn element_delay(&self, k: usize, bearing: f32, sample_rate: f32) -> f32 {
+        let aperture = self.elements.saturating_sub(1) as f32 * self.spacing;
+        (aperture + k as f32 * self.spacing * bearing.cos()) / SOUND_SPEED * sample_rate
+    }
+
+    // Adds decorrelated flow noise to every element of a planar block. Flow noise rises
+    // steeply with tow speed, like the hull-mounted self-noise, and sits below about 240 Hz.
+    pub(crate) fn add_flow_noise(
+        &mut self,
+        out: &mut [f32],
+        stride: usize,
+        frames: usize,
+        speed_ms: f32,
+        sample_rate: f32,
+    ) {
+        let speed_norm = clamp(speed_ms.abs() / 15.0, 0.0, 1.5);
+        let amp = self.flow_level * speed_norm * speed_norm * 0.6;
+        if amp <= 0.0 {
+            return;
+        }
+        let lp = lowpass(236.0, sample_rate);
+        for k in 0..self.elements {
+            let channel = &mut out[k * stride..k * stride + frames];
+            for sample in channel.iter_mut() {
+                let white = rand_signed(&mut self.rng[k]);
+                self.flow_lp[k] += lp * (white - self.flow_lp[k]);
+                *sample += self.flow_lp[k] * 6.0 * amp;
+            }
+        }
+    }
+}
+
+// Fractional delay line that fans one source out to every array element at the delays its
+// bearing implies. Delays glide so a turning contact sweeps across the beams smoothly.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct ArrayDelay {
+    line: Vec<f32>,
+    write: usize,
+    elements: usize,
+    delay: [f32; MAX_ARRAY_ELEMENTS],
+    target: [f32; MAX_ARRAY_ELEMENTS],
+    primed: bool,
+}
+
+impl ArrayDelay {
+    pub(crate) fn new() -> Self {
+        Self {
+            line: Vec::new(),
+            write: 0,
+            elements: 0,
+            delay: [0.0; MAX_ARRAY_ELEMENTS],
+            target: [0.0; MAX_ARRAY_ELEMENTS],
+            primed: false,
+        }
+    }
+
+    pub(crate) fn rescale(&mut self, ratio: f32) {
+        for (delay, target) in self.delay.iter_mut().zip(self.target.iter_mut()) {
+            *delay *= ratio;
+            *target *= ratio;
+        }
+    }
+
+    pub(crate) fn steer(&mut self, array: &TowedArray, bearing: f32, sample_rate: f32) {
+        let capacity = array.max_delay(sample_rate) as usize + 2;
+        if self.line.len() < capacity {
+            self.line = vec![0.0; capacity];
+            self.write = 0;
+            self.primed = false;
+        }
+        self.elements = array.elements;
+        for k in 0..self.elements {
+            self.target[k] = array.element_delay(k, bearing, sample_rate);
+        }
+        if !self.primed {
+            self.delay = self.target;
+            self.primed = true;
+        }
+    }
+
+    // Writes one input sample and adds each element's delayed copy at `index` of a planar
+    // output whose channels are `stride` samples apart. Delays glide by `glide` a sample.
+    #[inline]
+    pub(crate) fn process(
+        &mut self,
+        input: f32,
+        glide: f32,
+        out: &mut [f32],
+        stride: usize,
+        index: usize,
+    ) {
+        let len = self.line.len();
+        if len == 0 {
+            return;
+        }
+        self.line[self.write] = input;
+        for k in 0..self.elements {
+            self.delay[k] += glide * (self.target[k] - self.delay[k]);
+            let read = self.write as f32 - self.delay[k];
+            let read = if read < 0.0 { read + len as f32 } else { read };
+            let i0 = read as usize % len;
+            let i1 = (i0 + 1) % len;
+            let frac = read - read.floor();
+            out[k * stride + index] += self.line[i0] + (self.line[i1] - self.line[i0]) * frac;
+        }
+        self.write = (self.write + 1) % len;
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.line)
+    }
+}
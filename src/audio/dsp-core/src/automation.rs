@@ -0,0 +1,120 @@
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{vec_bytes, Param};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Upper bound on queued parameter events, so a runaway scheduler can't grow the queue forever.
+pub(crate) const MAX_SCHEDULED_EVENTS: usize = 4096;
+
+// One parameter change waiting for its sample. The value is already clamped and the ramp
+// already converted to samples, so applying it is identical to a set_param at that instant.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct ParamEvent {
+    at: u64,
+    pub(crate) voice: usize,
+    pub(crate) param: Param,
+    pub(crate) value: f32,
+    pub(crate) ramp: u32,
+}
+
+// Time-ordered parameter changes keyed to the graph's running sample clock. Events sharing a
+// sample keep the order they were scheduled in.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ParamQueue {
+    events: Vec<ParamEvent>,
+    // Absolute sample index of the first frame of the next process() call.
+    clock: u64,
+    // Events falling inside the block being rendered.
+    due: usize,
+}
+
+impl ParamQueue {
+    pub(crate) fn new() -> Self {
+        Self {
+            events: Vec::with_capacity(MAX_SCHEDULED_EVENTS),
+            clock: 0,
+            due: 0,
+        }
+    }
+
+    // `offset` counts samples from the start of the next process() call.
+    pub(crate) fn push(&mut self, voice: usize, param: Param, value: f32, ramp: u32, offset: u64) -> bool {
+        if self.events.len() >= MAX_SCHEDULED_EVENTS {
+            return false;
+        }
+        let at = self.clock.saturating_add(offset);
+        let slot = self.events.partition_point(|e| e.at <= at);
+        self.events.insert(
+            slot,
+            ParamEvent {
+                at,
+                voice,
+                param,
+                value,
+                ramp,
+            },
+        );
+        true
+    }
+
+    pub(crate) fn cancel(&mut self, voice: usize) -> usize {
+        self.cancel_from(voice, 0)
+    }
+
+    // Drops a voice's events landing at or after absolute sample `from`.
+    pub(crate) fn cancel_from(&mut self, voice: usize, from: u64) -> usize {
+        let before = self.events.len();
+        self.events.retain(|e| e.voice != voice || e.at < from);
+        before - self.events.len()
+    }
+
+    // Moves pending events so each still lands the same time from now at the new rate.
+    pub(crate) fn rescale(&mut self, ratio: f32) {
+        let clock = self.clock;
+        for event in &mut self.events {
+            event.at = clock + ((event.at - clock) as f64 * ratio as f64).round() as u64;
+            event.ramp = crate::scale_samples(event.ramp, ratio);
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    // Absolute sample index of the first frame of the next (or current) block.
+    pub(crate) fn clock(&self) -> u64 {
+        self.clock
+    }
+
+    pub(crate) fn begin_block(&mut self, frames: usize) {
+        let end = self.clock + frames as u64;
+        self.due = self.events.partition_point(|e| e.at < end);
+    }
+
+    // This block's events for one voice, as (frame within the block, event).
+    pub(crate) fn pending(&self, voice: usize) -> impl Iterator<Item = (usize, &ParamEvent)> {
+        let clock = self.clock;
+        self.events[..self.due]
+            .iter()
+            .filter(move |e| e.voice == voice)
+            .map(move |e| ((e.at - clock) as usize, e))
+    }
+
+    // Every event landing in this block, as (absolute sample, event).
+    pub(crate) fn due(&self) -> impl Iterator<Item = (u64, &ParamEvent)> {
+        self.events[..self.due].iter().map(|e| (e.at, e))
+    }
+
+    pub(crate) fn end_block(&mut self, frames: usize) {
+        self.events.drain(..self.due);
+        self.due = 0;
+        self.clock += frames as u64;
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.events)
+    }
+}
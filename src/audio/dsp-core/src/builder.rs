@@ -0,0 +1,174 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+use crate::ambient::WeatherLevels;
+use crate::error::ensure;
+use crate::mixer::MAX_GROUPS;
+use crate::presets::preset;
+use crate::{DspError, DspGraph, Param, Precision, MAX_BLOCK_FRAMES, MAX_VOICE_LIMIT};
+
+// One voice for DspGraphBuilder: a preset, then parameter overrides in the order given, plus
+// priority, tag and mix group. Each setter hands back the config, so calls chain.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Default)]
+pub struct VoiceConfig {
+    preset: Option<u32>,
+    priority: u32,
+    tag: u64,
+    group: u32,
+    params: Vec<(Param, f32)>,
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+impl VoiceConfig {
+    #[cfg_attr(feature = "std", wasm_bindgen(constructor))]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn preset(mut self, preset_id: u32) -> Self {
+        self.preset = Some(preset_id);
+        self
+    }
+
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn tag(mut self, tag: u64) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    pub fn group(mut self, group: u32) -> Self {
+        self.group = group;
+        self
+    }
+
+    pub fn param(mut self, param: Param, value: f32) -> Self {
+        self.params.push((param, value));
+        self
+    }
+}
+
+// Collects a graph's setup and builds it in one step: build() either returns a graph with
+// every setting and voice in place or fails without one, instead of leaving a half-configured
+// graph behind. Voices start at their configured values rather than ramping to them.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub struct DspGraphBuilder {
+    sample_rate: f32,
+    max_frames: usize,
+    max_voices: usize,
+    channels: u32,
+    seed: u32,
+    precision: Precision,
+    weather: Option<WeatherLevels>,
+    water_depth: Option<f32>,
+    voices: Vec<VoiceConfig>,
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+impl DspGraphBuilder {
+    // Mono, a pool of 8 voices, seed 0, full precision and the constructor's calm sea until
+    // told otherwise.
+    #[cfg_attr(feature = "std", wasm_bindgen(constructor))]
+    pub fn new(sample_rate: f32, max_frames: usize) -> Self {
+        Self {
+            sample_rate,
+            max_frames,
+            max_voices: 8,
+            channels: 1,
+            seed: 0,
+            precision: Precision::Full,
+            weather: None,
+            water_depth: None,
+            voices: Vec::new(),
+        }
+    }
+
+    // Starting pool size and voice limit, as DspGraph's max_voices.
+    pub fn voice_pool(mut self, max_voices: usize) -> Self {
+        self.max_voices = max_voices;
+        self
+    }
+
+    // As set_output_channels.
+    pub fn channels(mut self, channels: u32) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    pub fn seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    // How closely the voices' generators are computed; fixed for the graph's lifetime.
+    pub fn precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    // Starting sea state, rain (0-1) and wind (0-1), in place from the first block.
+    pub fn ambience(mut self, sea_state: f32, rain: f32, wind: f32) -> Self {
+        self.weather = Some(WeatherLevels { sea_state, rain, wind });
+        self
+    }
+
+    pub fn water_depth(mut self, depth_m: f32) -> Self {
+        self.water_depth = Some(depth_m);
+        self
+    }
+
+    // Adds a voice set up as `config`; the same config can be added any number of times.
+    // Voices get ids in the order they are added, from 0.
+    pub fn voice(mut self, config: &VoiceConfig) -> Self {
+        self.voices.push(config.clone());
+        self
+    }
+
+    // Fails with OutOfRange for a sample rate outside 8 kHz..384 kHz, a block size outside
+    // 1..max_block_frames(), or any invalid setting, preset, group or parameter value, and
+    // with PoolExhausted when the voices don't fit the pool.
+    pub fn build(&self) -> Result<DspGraph, DspError> {
+        ensure((8000.0..=384_000.0).contains(&self.sample_rate), DspError::OutOfRange)?;
+        ensure((1..=MAX_BLOCK_FRAMES).contains(&self.max_frames), DspError::OutOfRange)?;
+        ensure((1..=MAX_VOICE_LIMIT).contains(&self.max_voices), DspError::OutOfRange)?;
+        ensure(self.voices.len() <= self.max_voices, DspError::PoolExhausted)?;
+
+        let mut graph = DspGraph::new(self.sample_rate, self.max_frames, self.max_voices);
+        graph.set_seed(self.seed);
+        graph.precision = self.precision;
+        graph.set_output_channels(self.channels)?;
+        if let Some(levels) = self.weather {
+            let finite = [levels.sea_state, levels.rain, levels.wind]
+                .iter()
+                .all(|v| v.is_finite());
+            ensure(finite, DspError::OutOfRange)?;
+            graph.weather.snap(levels);
+            graph.environment.sea_state = graph.weather.levels().sea_state;
+        }
+        if let Some(depth_m) = self.water_depth {
+            graph.set_water_depth(depth_m, 0.0)?;
+        }
+        for config in &self.voices {
+            ensure((config.group as usize) < MAX_GROUPS, DspError::OutOfRange)?;
+            let values = match config.preset {
+                Some(id) => preset(id).ok_or(DspError::OutOfRange)?.values,
+                None => &[],
+            };
+            let id = graph.start_voice(config.priority, 0, config.tag)?;
+            let voice = &mut graph.voices[id as usize];
+            for &(param, value) in values.iter().chain(&config.params) {
+                ensure(param.accepts(value), DspError::OutOfRange)?;
+                voice.set_value(param, param.spec().apply(value), 0);
+            }
+            voice.group = config.group as usize;
+            voice.preset = config.preset;
+        }
+        Ok(graph)
+    }
+}
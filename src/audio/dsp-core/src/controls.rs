@@ -0,0 +1,100 @@
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+use crate::{clamp, vec_bytes, Param};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Control ids run from 0 to MAX_CONTROLS - 1, enough for a bank of MIDI CCs or pad axes.
+pub(crate) const MAX_CONTROLS: u32 = 256;
+
+// Upper bound on bindings across all controls.
+pub(crate) const MAX_CONTROL_BINDINGS: usize = 512;
+
+// How a control's 0..1 position spreads over a binding's range. Exponential makes equal
+// steps equal ratios, for rates and frequencies, and needs a range on one side of zero;
+// Quadratic gives the low end finer resolution, for levels.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlCurve {
+    Linear = 0,
+    Exponential = 1,
+    Quadratic = 2,
+}
+
+// One control driving one voice parameter. Position 0 gives `min` and 1 gives `max`; either
+// may be the larger, which inverts the control.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Binding {
+    pub(crate) control: u32,
+    pub(crate) voice: usize,
+    pub(crate) param: Param,
+    pub(crate) curve: ControlCurve,
+    pub(crate) min: f32,
+    pub(crate) max: f32,
+}
+
+impl Binding {
+    pub(crate) fn is_valid(&self) -> bool {
+        let finite = self.min.is_finite() && self.max.is_finite();
+        let one_sided = self.min * self.max > 0.0;
+        finite && (self.curve != ControlCurve::Exponential || one_sided)
+    }
+
+    pub(crate) fn value(&self, position: f32) -> f32 {
+        let p = clamp(position, 0.0, 1.0);
+        match self.curve {
+            ControlCurve::Linear => self.min + (self.max - self.min) * p,
+            ControlCurve::Exponential => self.min * (self.max / self.min).powf(p),
+            ControlCurve::Quadratic => self.min + (self.max - self.min) * p * p,
+        }
+    }
+}
+
+// Every binding in the graph, in the order they were made.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct ControlMap {
+    pub(crate) bindings: Vec<Binding>,
+}
+
+impl ControlMap {
+    pub(crate) fn new() -> Self {
+        Self { bindings: Vec::new() }
+    }
+
+    // Adds a binding, replacing one from the same control to the same voice parameter. False
+    // when the map is full.
+    pub(crate) fn bind(&mut self, binding: Binding) -> bool {
+        let existing = self.bindings.iter().position(|b| {
+            b.control == binding.control && b.voice == binding.voice && b.param == binding.param
+        });
+        match existing {
+            Some(i) => self.bindings[i] = binding,
+            None if self.bindings.len() < MAX_CONTROL_BINDINGS => self.bindings.push(binding),
+            None => return false,
+        }
+        true
+    }
+
+    pub(crate) fn unbind(&mut self, control: u32) -> usize {
+        let before = self.bindings.len();
+        self.bindings.retain(|b| b.control != control);
+        before - self.bindings.len()
+    }
+
+    // Drops a removed voice's bindings so its slot's next voice doesn't inherit them.
+    pub(crate) fn unbind_voice(&mut self, voice: usize) {
+        self.bindings.retain(|b| b.voice != voice);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.bindings)
+    }
+}
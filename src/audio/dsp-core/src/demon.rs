@@ -0,0 +1,630 @@
+use alloc::vec::Vec;
+use core::f32::consts::PI;
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+use crate::error::ensure;
+use crate::{host_floats, vec_bytes, DspError, DspResult};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Work units advance() charges for an entry in each of the window and twiddle tables, whose
+// cosines and sines cost about that many of the multiply-adds that make up the other units.
+const TABLE_ENTRY_COST: usize = 32;
+
+// Why an analysis has no spectrum, or that it has one. The spectrum of an analysis that
+// couldn't run reads zero throughout; min_input_seconds() says how much input its settings
+// need.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DemonStatus {
+    // The spectrum is ready, or nothing has been analysed yet.
+    #[default]
+    Ok = 0,
+    // A time-sliced analysis is still under way.
+    Pending = 1,
+    // The sample rate isn't a positive, finite number.
+    BadSampleRate = 2,
+    // Fewer than 64 input samples.
+    InputTooShort = 3,
+    // The input is long enough, but decimating it to the target rate leaves fewer than 8
+    // envelope samples: it needs more input or a higher decimated rate.
+    OverDecimated = 4,
+}
+
+// DEMON analysis (demodulated envelope spectrum) that keeps its buffers between calls, so a
+// host analysing every second doesn't allocate each time. They grow to the largest input seen
+// and stay that size. The window and twiddle tables are rebuilt only when the envelope length
+// or rate changes, which for a host analysing fixed windows is never.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Default)]
+pub struct DemonAnalyzer {
+    // The decimated envelope, high-passed and then windowed in place.
+    envelope: Vec<f32>,
+    spectrum: Vec<f32>,
+    // analyze_frames() output, frame after frame, each frame_bins long.
+    frames: Vec<f32>,
+    frame_bins: usize,
+    // Hann window over window.len() samples.
+    window: Vec<f32>,
+    // e^(-j 2π i / step_rate) for each sample i: one bin's phase advance at step_rate.
+    step: Vec<(f32, f32)>,
+    step_rate: f32,
+    // Each sample's twiddle for the bin being summed, advanced by `step` from bin to bin.
+    twiddle: Vec<(f32, f32)>,
+    // The time-sliced analysis begin() started, and its copy of the input.
+    job: Job,
+    input: Vec<f32>,
+    // How the last analysis set up, and the input it needed in seconds (0 at a bad rate).
+    status: DemonStatus,
+    min_input_s: f32,
+}
+
+// How far an analysis has got. run() takes one from start to finish in a single call; begin()
+// and advance() spread it over as many as the host likes.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum Stage {
+    // No time-sliced analysis, or a one-call analysis cancelled it.
+    #[default]
+    Idle,
+    // Summing the input for its mean.
+    Mean,
+    // Band-passing, rectifying and decimating the input into the envelope.
+    Envelope,
+    // Building the window and twiddle tables for the envelope's length, if they don't fit.
+    Tables,
+    // High-passing and windowing the envelope.
+    Window,
+    // Summing the spectrum bin by bin.
+    Bins,
+    Done,
+}
+
+// An analysis under way: the stage, the next input sample or bin it works on, and the filter
+// coefficients and state that carry over from one slice to the next.
+#[derive(Clone, Copy, Default)]
+struct Job {
+    stage: Stage,
+    pos: usize,
+    sum: f32,
+    mean: f32,
+    hp_alpha: f32,
+    lp_alpha: f32,
+    // Input samples per envelope sample.
+    decimation: usize,
+    decim_sr: f32,
+    env_hp: f32,
+    hp_y: f32,
+    hp_prev_x: f32,
+    lp_y: f32,
+    accum: f32,
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+impl DemonAnalyzer {
+    #[cfg_attr(feature = "std", wasm_bindgen(constructor))]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // As compute_demon_spectrum, but returns a pointer to the spectrum in this module's memory:
+    // spectrum_len() floats, bin f at f Hz, valid until the next call.
+    #[allow(clippy::too_many_arguments)]
+    pub fn analyze(
+        &mut self,
+        input: &[f32],
+        sample_rate: f32,
+        max_freq_hz: u32,
+        input_band_low_hz: f32,
+        input_band_high_hz: f32,
+        envelope_hp_hz: f32,
+        decimated_rate_target_hz: f32,
+    ) -> usize {
+        let mut spectrum = core::mem::take(&mut self.spectrum);
+        spectrum.clear();
+        spectrum.resize(max_freq_hz as usize + 1, 0.0);
+        self.run(
+            &mut spectrum,
+            input,
+            sample_rate,
+            input_band_low_hz,
+            input_band_high_hz,
+            envelope_hp_hz,
+            decimated_rate_target_hz,
+        );
+        self.spectrum = spectrum;
+        self.spectrum.as_ptr() as usize
+    }
+
+    /// As analyze, but writes the spectrum to `ptr`, a host-owned region of this module's
+    /// memory (see alloc_f32_buffer), rather than keeping it: `len` bins, so up to len - 1 Hz.
+    /// Fails with OutOfRange for a null, misaligned or empty buffer.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must address `len` writable floats, such as a live alloc_f32_buffer block, that
+    /// nothing else reads or writes during the call.
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn analyze_into(
+        &mut self,
+        input: &[f32],
+        sample_rate: f32,
+        input_band_low_hz: f32,
+        input_band_high_hz: f32,
+        envelope_hp_hz: f32,
+        decimated_rate_target_hz: f32,
+        ptr: usize,
+        len: usize,
+    ) -> DspResult {
+        // SAFETY: upheld by the caller, as above.
+        let spectrum = unsafe { host_floats(ptr, len)? };
+        spectrum.fill(0.0);
+        self.run(
+            spectrum,
+            input,
+            sample_rate,
+            input_band_low_hz,
+            input_band_high_hz,
+            envelope_hp_hz,
+            decimated_rate_target_hz,
+        );
+        Ok(())
+    }
+
+    pub fn spectrum_len(&self) -> usize {
+        self.spectrum.len()
+    }
+
+    // Analyses a whole recording in one call: a spectrum, as analyze would give it, for each
+    // `frame_len` samples starting every `hop` samples, as many as fit. Returns a pointer to
+    // frame_count() spectra of max_freq_hz + 1 floats each, one after another, valid until the
+    // next call. Fails with OutOfRange for a zero frame length or hop.
+    #[allow(clippy::too_many_arguments)]
+    pub fn analyze_frames(
+        &mut self,
+        input: &[f32],
+        sample_rate: f32,
+        max_freq_hz: u32,
+        frame_len: usize,
+        hop: usize,
+        input_band_low_hz: f32,
+        input_band_high_hz: f32,
+        envelope_hp_hz: f32,
+        decimated_rate_target_hz: f32,
+    ) -> DspResult<usize> {
+        ensure(frame_len > 0 && hop > 0, DspError::OutOfRange)?;
+        let bins = max_freq_hz as usize + 1;
+        let count = match input.len().checked_sub(frame_len) {
+            Some(last) => last / hop + 1,
+            None => 0,
+        };
+        let mut frames = core::mem::take(&mut self.frames);
+        frames.clear();
+        frames.resize(count * bins, 0.0);
+        for (k, spectrum) in frames.chunks_exact_mut(bins).enumerate() {
+            self.run(
+                spectrum,
+                &input[k * hop..][..frame_len],
+                sample_rate,
+                input_band_low_hz,
+                input_band_high_hz,
+                envelope_hp_hz,
+                decimated_rate_target_hz,
+            );
+        }
+        self.frames = frames;
+        self.frame_bins = bins;
+        Ok(self.frames.as_ptr() as usize)
+    }
+
+    // Starts a time-sliced analysis of `input`, which is copied, for advance() to carry out a
+    // slice at a time: a host running audio and analysis on one thread can spread a large
+    // spectrum over its audio callbacks. Replaces any still under way, and the one-call
+    // analyses on this analyser cancel it. The spectrum reads zero until it completes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn begin(
+        &mut self,
+        input: &[f32],
+        sample_rate: f32,
+        max_freq_hz: u32,
+        input_band_low_hz: f32,
+        input_band_high_hz: f32,
+        envelope_hp_hz: f32,
+        decimated_rate_target_hz: f32,
+    ) {
+        self.input.clear();
+        self.input.extend_from_slice(input);
+        self.spectrum.clear();
+        self.spectrum.resize(max_freq_hz as usize + 1, 0.0);
+        self.job = self.start(
+            input.len(),
+            sample_rate,
+            input_band_low_hz,
+            input_band_high_hz,
+            envelope_hp_hz,
+            decimated_rate_target_hz,
+        );
+    }
+
+    // Does about `budget` units of work on the analysis begin() started: one for each input
+    // sample in each of its two passes, a few dozen per table entry when the envelope length
+    // is new, and the envelope's length for each bin, with at least one bin per call while
+    // any remain. Returns is_complete().
+    pub fn advance(&mut self, budget: usize) -> bool {
+        let mut job = self.job;
+        let mut spectrum = core::mem::take(&mut self.spectrum);
+        let input = core::mem::take(&mut self.input);
+        self.work(&mut job, &mut spectrum, &input, budget);
+        self.job = job;
+        self.spectrum = spectrum;
+        self.input = input;
+        self.is_complete()
+    }
+
+    // Whether the analysis begin() started has finished, so spectrum_ptr() holds its spectrum.
+    pub fn is_complete(&self) -> bool {
+        self.job.stage == Stage::Done
+    }
+
+    // The spectrum analyze() or begin() fills: spectrum_len() floats, bin f at f Hz.
+    pub fn spectrum_ptr(&self) -> usize {
+        self.spectrum.as_ptr() as usize
+    }
+
+    // Spectra the last analyze_frames() call produced.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len() / self.frame_bins.max(1)
+    }
+
+    // Whether the last analysis produced a spectrum, and if not, why not. A time-sliced one
+    // reads Pending until it completes.
+    pub fn status(&self) -> DemonStatus {
+        match self.job.stage {
+            Stage::Idle | Stage::Done => self.status,
+            _ => DemonStatus::Pending,
+        }
+    }
+
+    // Least input, in seconds, the last analysis' sample rate and decimated rate can make a
+    // spectrum from; 0 when the sample rate made no sense.
+    pub fn min_input_seconds(&self) -> f32 {
+        self.min_input_s
+    }
+
+    // Bytes this analyser holds, its buffers and tables included.
+    pub fn memory_bytes(&self) -> usize {
+        core::mem::size_of::<Self>() + self.heap_bytes()
+    }
+}
+
+impl DemonAnalyzer {
+    pub(crate) fn heap_bytes(&self) -> usize {
+        let floats = [&self.envelope, &self.spectrum, &self.frames, &self.window, &self.input];
+        let tables = [&self.step, &self.twiddle];
+        floats.into_iter().map(vec_bytes).sum::<usize>()
+            + tables.into_iter().map(vec_bytes).sum::<usize>()
+    }
+
+    // Fills the zeroed `spectrum`, one bin per Hz; it stays zero for input too short or a
+    // sample rate that makes no sense.
+    #[allow(clippy::too_many_arguments)]
+    fn run(
+        &mut self,
+        spectrum: &mut [f32],
+        input: &[f32],
+        sample_rate: f32,
+        input_band_low_hz: f32,
+        input_band_high_hz: f32,
+        envelope_hp_hz: f32,
+        decimated_rate_target_hz: f32,
+    ) {
+        self.job = Job::default();
+        let mut job = self.start(
+            input.len(),
+            sample_rate,
+            input_band_low_hz,
+            input_band_high_hz,
+            envelope_hp_hz,
+            decimated_rate_target_hz,
+        );
+        self.work(&mut job, spectrum, input, usize::MAX);
+    }
+
+    // Sets up the analysis of `len` samples and sizes the envelope for it, noting in `status`
+    // whether it can run. Nothing is left to do for input too short or a sample rate that makes
+    // no sense.
+    fn start(
+        &mut self,
+        len: usize,
+        sample_rate: f32,
+        input_band_low_hz: f32,
+        input_band_high_hz: f32,
+        envelope_hp_hz: f32,
+        decimated_rate_target_hz: f32,
+    ) -> Job {
+        let done = Job {
+            stage: Stage::Done,
+            ..Job::default()
+        };
+        if !sample_rate.is_finite() || sample_rate <= 0.0 {
+            self.status = DemonStatus::BadSampleRate;
+            self.min_input_s = 0.0;
+            return done;
+        }
+
+        let band_low = if input_band_low_hz.is_finite() {
+            input_band_low_hz.max(1.0)
+        } else {
+            20.0
+        };
+        let band_high = if input_band_high_hz.is_finite() {
+            input_band_high_hz.max(band_low + 1.0)
+        } else {
+            1800.0
+        };
+        let env_hp = if envelope_hp_hz.is_finite() {
+            envelope_hp_hz.max(0.1)
+        } else {
+            1.0
+        };
+        let decim_target = if decimated_rate_target_hz.is_finite() {
+            decimated_rate_target_hz.max(100.0)
+        } else {
+            500.0
+        };
+
+        let hp_rc = 1.0 / (2.0 * PI * band_low);
+        let lp_rc = 1.0 / (2.0 * PI * band_high);
+        let dt = 1.0 / sample_rate;
+
+        let d = ((sample_rate / decim_target).floor() as usize).max(1);
+        let n_decim = len / d;
+        self.min_input_s = 64.max(8 * d) as f32 / sample_rate;
+        self.status = if len < 64 {
+            DemonStatus::InputTooShort
+        } else if n_decim < 8 {
+            DemonStatus::OverDecimated
+        } else {
+            DemonStatus::Ok
+        };
+        if self.status != DemonStatus::Ok {
+            return done;
+        }
+        self.envelope.clear();
+        self.envelope.resize(n_decim, 0.0);
+        Job {
+            stage: Stage::Mean,
+            // As Sum for f32 starts, so the mean matches summing the input in one go.
+            sum: -0.0,
+            hp_alpha: hp_rc / (hp_rc + dt),
+            lp_alpha: dt / (lp_rc + dt),
+            decimation: d,
+            decim_sr: sample_rate / d as f32,
+            env_hp,
+            ..Job::default()
+        }
+    }
+
+    // Carries `job` on through `input` into `spectrum` for about `budget` units of work, as
+    // advance() counts them.
+    fn work(&mut self, job: &mut Job, spectrum: &mut [f32], input: &[f32], mut budget: usize) {
+        let n_decim = self.envelope.len();
+        while budget > 0 {
+            match job.stage {
+                Stage::Idle | Stage::Done => return,
+                Stage::Mean => {
+                    let end = input.len().min(job.pos.saturating_add(budget));
+                    job.sum = input[job.pos..end].iter().fold(job.sum, |sum, &x| sum + x);
+                    budget -= end - job.pos;
+                    job.pos = end;
+                    if end == input.len() {
+                        job.mean = job.sum / input.len() as f32;
+                        job.stage = Stage::Envelope;
+                        job.pos = 0;
+                    }
+                }
+                Stage::Envelope => {
+                    let end = input.len().min(job.pos.saturating_add(budget));
+                    let d = job.decimation;
+                    let Job {
+                        mean,
+                        hp_alpha,
+                        lp_alpha,
+                        mut hp_y,
+                        mut hp_prev_x,
+                        mut lp_y,
+                        mut accum,
+                        ..
+                    } = *job;
+                    for (i, &raw) in input.iter().enumerate().take(end).skip(job.pos) {
+                        let x = raw - mean;
+                        hp_y = hp_alpha * (hp_y + x - hp_prev_x);
+                        hp_prev_x = x;
+                        lp_y += lp_alpha * (hp_y - lp_y);
+                        accum += lp_y.abs();
+                        if (i + 1) % d == 0 {
+                            let idx = (i + 1) / d - 1;
+                            self.envelope[idx] = accum / d as f32;
+                            accum = 0.0;
+                        }
+                    }
+                    (job.hp_y, job.hp_prev_x, job.lp_y, job.accum) = (hp_y, hp_prev_x, lp_y, accum);
+                    budget -= end - job.pos;
+                    job.pos = end;
+                    if end == input.len() {
+                        self.clear_tables(n_decim, job.decim_sr);
+                        job.stage = Stage::Tables;
+                    }
+                }
+                Stage::Tables => {
+                    let built = self.grow_tables(n_decim, (budget / TABLE_ENTRY_COST).max(1));
+                    budget = budget.saturating_sub(built * TABLE_ENTRY_COST);
+                    if self.window.len() == n_decim && self.step.len() == n_decim {
+                        job.stage = Stage::Window;
+                    }
+                }
+                Stage::Window => {
+                    let env_hp_rc = 1.0 / (2.0 * PI * job.env_hp);
+                    let decim_dt = 1.0 / job.decim_sr;
+                    let env_hp_alpha = env_hp_rc / (env_hp_rc + decim_dt);
+                    let mut env_hp_y = 0.0f32;
+                    let mut env_hp_prev_x = self.envelope[0];
+                    for sample in self.envelope.iter_mut() {
+                        let x = *sample;
+                        env_hp_y = env_hp_alpha * (env_hp_y + x - env_hp_prev_x);
+                        env_hp_prev_x = x;
+                        *sample = env_hp_y;
+                    }
+
+                    for (s, w) in self.envelope.iter_mut().zip(&self.window) {
+                        *s *= w;
+                    }
+                    // One bin per Hz from 1 Hz: bin 1 turns at `step`, and each bin after turns
+                    // one step further.
+                    self.twiddle.clear();
+                    self.twiddle.extend_from_slice(&self.step);
+                    budget = budget.saturating_sub(n_decim);
+                    job.stage = if spectrum.len() > 1 { Stage::Bins } else { Stage::Done };
+                    job.pos = 1;
+                }
+                Stage::Bins => {
+                    let mut re = 0.0f32;
+                    let mut im = 0.0f32;
+                    let bins = self.envelope.iter().zip(&mut self.twiddle).zip(&self.step);
+                    for ((&v, t), &(c, s)) in bins {
+                        re += v * t.0;
+                        im += v * t.1;
+                        *t = (t.0 * c - t.1 * s, t.0 * s + t.1 * c);
+                    }
+                    spectrum[job.pos] = (re.hypot(im)) / n_decim as f32;
+                    budget = budget.saturating_sub(n_decim);
+                    job.pos += 1;
+                    if job.pos == spectrum.len() {
+                        job.stage = Stage::Done;
+                    }
+                }
+            }
+        }
+    }
+
+    // Throws away whichever of the window and twiddle step tables don't fit `len` samples at
+    // `rate`, for grow_tables() to build again.
+    fn clear_tables(&mut self, len: usize, rate: f32) {
+        if self.window.len() != len {
+            self.window.clear();
+        }
+        if self.step.len() != len || self.step_rate != rate {
+            self.step_rate = rate;
+            self.step.clear();
+        }
+    }
+
+    // Adds up to `count` entries to each table on its way to `len`, returning how many the
+    // longer addition was.
+    fn grow_tables(&mut self, len: usize, count: usize) -> usize {
+        let start = self.window.len();
+        let end = len.min(start.saturating_add(count));
+        let built = end - start;
+        let denom = (len.saturating_sub(1)).max(1) as f32;
+        self.window
+            .extend((start..end).map(|i| 0.5 * (1.0 - ((2.0 * PI * i as f32) / denom).cos())));
+
+        let start = self.step.len();
+        let end = len.min(start.saturating_add(count));
+        // In f64, so the far end of a long window is as exact as its start.
+        let omega = core::f64::consts::TAU / self.step_rate as f64;
+        self.step.extend((start..end).map(|i| {
+            let (sin, cos) = (omega * i as f64).sin_cos();
+            (cos as f32, -sin as f32)
+        }));
+        built.max(end - start)
+    }
+}
+
+// Scratch for the one-shot functions, which like it need std.
+#[cfg(feature = "std")]
+thread_local! {
+    static ANALYZER: RefCell<DemonAnalyzer> = RefCell::new(DemonAnalyzer::new());
+}
+
+// One-shot DEMON spectrum: bins 0..=max_freq_hz, one per Hz. Its scratch is shared between
+// calls; only the returned spectrum is allocated each time. Hosts calling it on a timer can
+// keep a DemonAnalyzer instead and read the spectrum in place, or have it written into a
+// buffer of their own with compute_demon_spectrum_into.
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+pub fn compute_demon_spectrum(
+    input: &[f32],
+    sample_rate: f32,
+    max_freq_hz: u32,
+    input_band_low_hz: f32,
+    input_band_high_hz: f32,
+    envelope_hp_hz: f32,
+    decimated_rate_target_hz: f32,
+) -> Vec<f32> {
+    ANALYZER.with_borrow_mut(|analyzer| {
+        analyzer.analyze(
+            input,
+            sample_rate,
+            max_freq_hz,
+            input_band_low_hz,
+            input_band_high_hz,
+            envelope_hp_hz,
+            decimated_rate_target_hz,
+        );
+        analyzer.spectrum.clone()
+    })
+}
+
+// Status of the last compute_demon_spectrum or compute_demon_spectrum_into call (see
+// DemonAnalyzer::status), so a host handed zeros can tell the user why.
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+pub fn demon_spectrum_status() -> DemonStatus {
+    ANALYZER.with_borrow(|analyzer| analyzer.status())
+}
+
+// Least input, in seconds, the last one-shot call's settings needed (see
+// DemonAnalyzer::min_input_seconds).
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+pub fn demon_min_input_seconds() -> f32 {
+    ANALYZER.with_borrow(|analyzer| analyzer.min_input_seconds())
+}
+
+/// compute_demon_spectrum into a host-owned buffer of `len` floats at `ptr` (see
+/// alloc_f32_buffer), bins 0..len one per Hz, so nothing is allocated per call.
+///
+/// # Safety
+///
+/// As for DemonAnalyzer::analyze_into.
+#[cfg(feature = "std")]
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn compute_demon_spectrum_into(
+    input: &[f32],
+    sample_rate: f32,
+    input_band_low_hz: f32,
+    input_band_high_hz: f32,
+    envelope_hp_hz: f32,
+    decimated_rate_target_hz: f32,
+    ptr: usize,
+    len: usize,
+) -> DspResult {
+    // SAFETY: upheld by the caller, as above.
+    ANALYZER.with_borrow_mut(|analyzer| unsafe {
+        analyzer.analyze_into(
+            input,
+            sample_rate,
+            input_band_low_hz,
+            input_band_high_hz,
+            envelope_hp_hz,
+            decimated_rate_target_hz,
+            ptr,
+            len,
+        )
+    })
+}
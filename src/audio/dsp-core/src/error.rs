@@ -0,0 +1,65 @@
+use core::fmt;
+use alloc::string::{String, ToString};
+
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+// Why a graph call was refused. Methods returning DspResult throw the variant's number on the
+// JS side, so a host can compare the caught value against DspError.* directly.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DspError {
+    // Voice id out of range, or the voice isn't active.
+    InvalidVoice = 0,
+    // Unknown parameter id, or one that can't be used this way (e.g. modulating blades).
+    InvalidParam = 1,
+    // A non-finite value, or a selector, index or count outside its documented range.
+    OutOfRange = 2,
+    // Every voice slot is in use and the pool is at its voice limit.
+    PoolExhausted = 3,
+    // The automation queue already holds max_scheduled_events() changes.
+    QueueFull = 4,
+    // Malformed or stale snapshot, or one taken at another sample rate.
+    InvalidSnapshot = 5,
+    // The voice can't do this in its current configuration, or the build can't do it at all.
+    Unsupported = 6,
+}
+
+impl DspError {
+    pub(crate) fn message(self) -> &'static str {
+        match self {
+            Self::InvalidVoice => "invalid or inactive voice",
+            Self::InvalidParam => "invalid parameter",
+            Self::OutOfRange => "value out of range",
+            Self::PoolExhausted => "voice pool exhausted",
+            Self::QueueFull => "automation queue full",
+            Self::InvalidSnapshot => "invalid snapshot",
+            Self::Unsupported => "not supported by this voice or build",
+        }
+    }
+}
+
+impl fmt::Display for DspError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl core::error::Error for DspError {}
+
+pub type DspResult<T = ()> = Result<T, DspError>;
+
+#[inline]
+pub(crate) fn ensure(condition: bool, error: DspError) -> DspResult {
+    if condition {
+        Ok(())
+    } else {
+        Err(error)
+    }
+}
+
+// Human-readable text for a caught DspError, for logs and UI.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn dsp_error_message(error: DspError) -> String {
+    error.message().to_string()
+}
@@ -0,0 +1,80 @@
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::vec_bytes;
+
+// Events held between drains. Past this the oldest are dropped, so a host that stops draining
+// only loses history, never memory.
+pub(crate) const MAX_GRAPH_EVENTS: usize = 1024;
+
+// Values per event in drain_events(): kind, voice, sample, detail, value, voice tag.
+pub(crate) const EVENT_STRIDE: usize = 6;
+
+// Marks events that belong to the whole graph rather than one voice.
+pub(crate) const NO_VOICE: f64 = -1.0;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct GraphEvent {
+    pub(crate) kind: u32,
+    pub(crate) voice: Option<usize>,
+    // The voice's set_voice_tag value when the event was raised; 0 if untagged or graph-wide.
+    pub(crate) tag: u64,
+    // Absolute sample index on the graph clock.
+    pub(crate) at: u64,
+    pub(crate) detail: u32,
+    pub(crate) value: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct EventLog {
+    events: Vec<GraphEvent>,
+}
+
+impl EventLog {
+    pub(crate) fn new() -> Self {
+        Self {
+            events: Vec::with_capacity(MAX_GRAPH_EVENTS),
+        }
+    }
+
+    pub(crate) fn push(&mut self, kind: u32, voice: Option<usize>, tag: u64, at: u64, detail: u32, value: f32) {
+        if self.events.len() >= MAX_GRAPH_EVENTS {
+            self.events.remove(0);
+        }
+        self.events.push(GraphEvent {
+            kind,
+            voice,
+            tag,
+            at,
+            detail,
+            value,
+        });
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    // Flattens and empties the log, oldest first. Events raised inside a block are pushed in
+    // render order, so they are sorted by sample before handing them out.
+    pub(crate) fn drain(&mut self) -> Vec<f64> {
+        self.events.sort_by_key(|e| e.at);
+        let mut out = Vec::with_capacity(self.events.len() * EVENT_STRIDE);
+        for e in self.events.drain(..) {
+            out.extend_from_slice(&[
+                e.kind as f64,
+                e.voice.map_or(NO_VOICE, |v| v as f64),
+                e.at as f64,
+                e.detail as f64,
+                e.value as f64,
+                e.tag as f64,
+            ]);
+        }
+        out
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.events)
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::propagation::SOUND_SPEED;
+use crate::TWO_PI;
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Model space follows the rest of the game: +X east, +Z north, headings clockwise from north.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct OwnShip {
+    pub(crate) x: f32,
+    pub(crate) z: f32,
+    pub(crate) vx: f32,
+    pub(crate) vz: f32,
+    pub(crate) heading: f32,
+}
+
+impl OwnShip {
+    pub(crate) fn new() -> Self {
+        Self {
+            x: 0.0,
+            z: 0.0,
+            vx: 0.0,
+            vz: 0.0,
+            heading: 0.0,
+        }
+    }
+
+    pub(crate) fn advance(&mut self, dt: f32) {
+        self.x += self.vx * dt;
+        self.z += self.vz * dt;
+    }
+}
+
+// Geometry derived for one voice from its track and own-ship's.
+pub(crate) struct Derived {
+    pub(crate) range: f32,
+    // Relative to own-ship's bow, clockwise, in radians.
+    pub(crate) bearing: f32,
+    pub(crate) doppler: f32,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Track {
+    pub(crate) enabled: bool,
+    pub(crate) x: f32,
+    pub(crate) z: f32,
+    pub(crate) vx: f32,
+    pub(crate) vz: f32,
+}
+
+impl Track {
+    pub(crate) fn new() -> Self {
+        Self {
+            enabled: false,
+            x: 0.0,
+            z: 0.0,
+            vx: 0.0,
+            vz: 0.0,
+        }
+    }
+
+    // Dead-reckons the track forward by `dt` seconds and derives range, bearing and Doppler
+    // against own-ship. Returns None when kinematics are off for this voice.
+    pub(crate) fn advance(&mut self, own: &OwnShip, dt: f32) -> Option<Derived> {
+        if !self.enabled {
+            return None;
+        }
+        self.x += self.vx * dt;
+        self.z += self.vz * dt;
+
+        let dx = self.x - own.x;
+        let dz = self.z - own.z;
+        let range = (dx * dx + dz * dz).sqrt();
+        let true_bearing = dx.atan2(dz);
+        let bearing = (true_bearing - own.heading).rem_euclid(TWO_PI);
+
+        let doppler = if range > 1.0e-3 {
+            // Unit vector pointing from the source toward own-ship.
+            let ux = -dx / range;
+            let uz = -dz / range;
+            let source_closing = self.vx * ux + self.vz * uz;
+            let receiver_closing = -(own.vx * ux + own.vz * uz);
+            let c = SOUND_SPEED;
+            ((c + receiver_closing) / (c - source_closing).max(1.0)).clamp(0.5, 2.0)
+        } else {
+            1.0
+        };
+
+        Some(Derived {
+            range,
+            bearing,
+            doppler,
+        })
+    }
+}
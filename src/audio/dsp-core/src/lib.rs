@@ -1,27 +1,232 @@
-use std::f32::consts::PI;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::{vec, vec::Vec};
+use core::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "std")]
 use wasm_bindgen::prelude::*;
 
+mod active;
+mod ambient;
+mod array;
+mod automation;
+mod builder;
+mod controls;
+mod demon;
+mod error;
+mod events;
+mod kinematics;
+mod low_rate;
+#[cfg(not(feature = "std"))]
+mod math;
+mod mixer;
+mod modulation;
+#[cfg(feature = "threads")]
+mod parallel;
+mod params;
+mod phase;
+mod precision;
+mod presets;
+mod profile;
+mod propagation;
+mod recorder;
+mod reverb;
+mod scenario;
+mod self_noise;
+mod simd;
+mod sine;
+mod sliding_dft;
+mod snapshot;
+mod spatial;
+
+use active::{Echo, EchoGeometry, Ping, PingReverb};
+use ambient::{DirectionalNoiseField, Weather, WeatherLevels, NOISE_SECTORS};
+use array::{ArrayDelay, TowedArray, MAX_ARRAY_ELEMENTS};
+use automation::{ParamEvent, ParamQueue, MAX_SCHEDULED_EVENTS};
+pub use builder::{DspGraphBuilder, VoiceConfig};
+use controls::{Binding, ControlMap, MAX_CONTROLS, MAX_CONTROL_BINDINGS};
+pub use controls::ControlCurve;
+pub use demon::{DemonAnalyzer, DemonStatus};
+#[cfg(feature = "std")]
+pub use demon::{
+    compute_demon_spectrum, compute_demon_spectrum_into, demon_min_input_seconds,
+    demon_spectrum_status,
+};
+use error::ensure;
+pub use error::{dsp_error_message, DspError, DspResult};
+use events::{EventLog, EVENT_STRIDE, MAX_GRAPH_EVENTS};
+use kinematics::{OwnShip, Track};
+use low_rate::{low_rate_factor, LowRate};
+#[cfg(not(feature = "std"))]
+use math::Float;
+use mixer::{
+    DcBlocker, Ducker, Headroom, HullFilter, Leveler, Limiter, MeterState, OperatorBand, VoiceGroup,
+    BUS_COUNT, MAX_GROUPS,
+};
+use modulation::{ModRoute, ModShape, Modulation, MAX_MOD_ROUTES, MOD_SOURCES};
+#[cfg(feature = "threads")]
+use parallel::PartialMix;
+use params::{db_to_linear, linear_to_db, Ramp, Smoothing, PARAM_COUNT, PARAM_SPECS};
+pub use params::{param_count, param_info, Param, ParamInfo};
+use phase::Phase;
+pub use precision::Precision;
+use precision::{FullMath, Math, ReducedMath};
+use presets::preset;
+pub use presets::{preset_count, preset_name};
+use profile::Profile;
+pub use profile::{ProfileStage, StageProfile};
+use propagation::{
+    depth_reverb_scale, predicted_loss_db, Bathymetry, BottomType, ChannelModel, Multipath,
+    VoicePropagation, MAX_MULTIPATH_TAPS,
+};
+use recorder::{Change, Recorder, Replay, MAX_RECORDED_CHANGES, RECORD_STRIDE};
+pub use recorder::RecordedChange;
+use reverb::Reverb;
+use scenario::{Scenario, Step, MAX_SCENARIO_STEPS, SCENARIO_STRIDE};
+pub use scenario::ScenarioAction;
+use self_noise::SelfNoise;
+pub use sliding_dft::SlidingSpectrum;
+use snapshot::{upgrade, Snapshot, SnapshotRef, SNAPSHOT_VERSION};
+use spatial::{
+    ring_gains, BearingNoise, Binaural, Directivity, Panner, ReceiverPattern, RingPanner,
+    MAX_RING_CHANNELS,
+};
+
 const TWO_PI: f32 = 2.0 * PI;
 
-pub const PARAM_RPM: u32 = 0;
-pub const PARAM_BLADES: u32 = 1;
-pub const PARAM_GAIN: u32 = 2;
-pub const PARAM_ENGINE_MIX: u32 = 3;
-pub const PARAM_CAV_MIX: u32 = 4;
-pub const PARAM_BIO_MIX: u32 = 5;
-pub const PARAM_BIO_TYPE: u32 = 6;
-pub const PARAM_BIO_RATE: u32 = 7;
-pub const PARAM_SHAFT_RATE: u32 = 8;
-pub const PARAM_LOAD: u32 = 9;
-pub const PARAM_RPM_JITTER: u32 = 10;
-pub const PARAM_CLASS_PROFILE: u32 = 11;
-pub const PARAM_CAVITATION_LEVEL: u32 = 12;
+// Fade applied when a voice is added or removed, long enough to hide the step.
+const VOICE_FADE_MS: f32 = 8.0;
+
+// A culled voice fades out and back in over this long, and only returns once it is this far
+// (6 dB) over the threshold it fell below.
+const CULL_FADE_MS: f32 = 10.0;
+const CULL_HYSTERESIS: f32 = 2.0;
+// Time constant of the de-zipper glides on a voice's gains, pans and delays.
+const DEZIPPER_MS: f32 = 10.0;
+
+// Largest block set_max_frames accepts.
+const MAX_BLOCK_FRAMES: usize = 16384;
+
+// Ceiling for set_voice_limit, however much memory the host is willing to spend.
+const MAX_VOICE_LIMIT: usize = 1024;
+
+// Floats per change in set_params(): voice id, Param id, value.
+const PARAM_UPDATE_STRIDE: usize = 3;
+
+// Work units a requested DEMON spectrum may take per process() call unless the host sets
+// otherwise; at 48 kHz, a few percent of a 128-frame callback on a typical core.
+const DEFAULT_ANALYSIS_BUDGET: u32 = 16_384;
+
+// Raw parameter ids from before the Param enum; the numbering is unchanged.
+#[deprecated(note = "use Param::Rpm")]
+pub const PARAM_RPM: u32 = Param::Rpm as u32;
+#[deprecated(note = "use Param::Blades")]
+pub const PARAM_BLADES: u32 = Param::Blades as u32;
+#[deprecated(note = "use Param::Gain")]
+pub const PARAM_GAIN: u32 = Param::Gain as u32;
+#[deprecated(note = "use Param::EngineMix")]
+pub const PARAM_ENGINE_MIX: u32 = Param::EngineMix as u32;
+#[deprecated(note = "use Param::CavMix")]
+pub const PARAM_CAV_MIX: u32 = Param::CavMix as u32;
+#[deprecated(note = "use Param::BioMix")]
+pub const PARAM_BIO_MIX: u32 = Param::BioMix as u32;
+#[deprecated(note = "use Param::BioType")]
+pub const PARAM_BIO_TYPE: u32 = Param::BioType as u32;
+#[deprecated(note = "use Param::BioRate")]
+pub const PARAM_BIO_RATE: u32 = Param::BioRate as u32;
+#[deprecated(note = "use Param::ShaftRate")]
+pub const PARAM_SHAFT_RATE: u32 = Param::ShaftRate as u32;
+#[deprecated(note = "use Param::Load")]
+pub const PARAM_LOAD: u32 = Param::Load as u32;
+#[deprecated(note = "use Param::RpmJitter")]
+pub const PARAM_RPM_JITTER: u32 = Param::RpmJitter as u32;
+#[deprecated(note = "use Param::ClassProfile")]
+pub const PARAM_CLASS_PROFILE: u32 = Param::ClassProfile as u32;
+#[deprecated(note = "use Param::CavitationLevel")]
+pub const PARAM_CAVITATION_LEVEL: u32 = Param::CavitationLevel as u32;
+#[deprecated(note = "use Param::Range")]
+pub const PARAM_RANGE: u32 = Param::Range as u32;
+#[deprecated(note = "use Param::SourceDepth")]
+pub const PARAM_SOURCE_DEPTH: u32 = Param::SourceDepth as u32;
+#[deprecated(note = "use Param::ReverbSend")]
+pub const PARAM_REVERB_SEND: u32 = Param::ReverbSend as u32;
+#[deprecated(note = "use Param::Bearing")]
+pub const PARAM_BEARING: u32 = Param::Bearing as u32;
+#[deprecated(note = "use Param::TargetStrength")]
+pub const PARAM_TARGET_STRENGTH: u32 = Param::TargetStrength as u32;
+#[deprecated(note = "use Param::TargetLength")]
+pub const PARAM_TARGET_LENGTH: u32 = Param::TargetLength as u32;
+#[deprecated(note = "use Param::BearingWander")]
+pub const PARAM_BEARING_WANDER: u32 = Param::BearingWander as u32;
+#[deprecated(note = "use Param::BearingJitter")]
+pub const PARAM_BEARING_JITTER: u32 = Param::BearingJitter as u32;
+#[deprecated(note = "use Param::Elevation")]
+pub const PARAM_ELEVATION: u32 = Param::Elevation as u32;
+
+pub const BUS_REVERB: u32 = 0;
+pub const BUS_HULL: u32 = 1;
+
+pub const PRESET_TRAWLER: u32 = 0;
+pub const PRESET_MERCHANT: u32 = 1;
+pub const PRESET_DESTROYER: u32 = 2;
+pub const PRESET_DIESEL_SUB: u32 = 3;
+pub const PRESET_SSN: u32 = 4;
+
+pub const EVENT_VOICE_ACTIVATED: u32 = 0;
+pub const EVENT_VOICE_DEACTIVATED: u32 = 1;
+pub const EVENT_PING: u32 = 2;
+pub const EVENT_TRANSIENT: u32 = 3;
+pub const EVENT_AUTOMATION: u32 = 4;
+pub const EVENT_BIO: u32 = 5;
+pub const EVENT_QUARANTINE: u32 = 6;
 
 #[inline]
 fn clamp(v: f32, lo: f32, hi: f32) -> f32 {
     v.max(lo).min(hi)
 }
 
+// Below this (-400 dB) a decaying envelope, glide or filter state is taken as zero. Left alone
+// it would end up in the denormal range, where it can stick for good (the step rounds away) and
+// where each operation costs many times more on x86 without flush-to-zero.
+const DENORMAL_FLOOR: f32 = 1.0e-20;
+
+#[inline]
+fn flush_denormal(x: f32) -> f32 {
+    if x.abs() < DENORMAL_FLOOR {
+        0.0
+    } else {
+        x
+    }
+}
+
+// The per-sample factor that decays a value to 1/e over `ms` milliseconds at `sample_rate`.
+// Envelopes and glides take their timing from this rather than from a fixed factor, so they
+// last as long at any rate. It costs an exp, so voice generators work theirs out once a block.
+#[inline]
+fn decay(ms: f32, sample_rate: f32) -> f32 {
+    (-1000.0 / (ms * sample_rate).max(1.0e-6)).exp()
+}
+
+// The one-pole coefficient that closes all but 1/e of the gap to a target in `ms`.
+#[inline]
+fn smoothing(ms: f32, sample_rate: f32) -> f32 {
+    1.0 - decay(ms, sample_rate)
+}
+
+// Coefficient of a one-pole low-pass with its corner at `cutoff_hz`.
+#[inline]
+fn lowpass(cutoff_hz: f32, sample_rate: f32) -> f32 {
+    let rc = 1.0 / (TWO_PI * cutoff_hz.max(1.0));
+    let dt = 1.0 / sample_rate.max(1.0);
+    (dt / (rc + dt)).clamp(0.0, 1.0)
+}
+
 #[inline]
 fn xorshift32(state: &mut u32) -> u32 {
     let mut x = *state;
@@ -38,144 +243,176 @@ fn rand_signed(state: &mut u32) -> f32 {
     (x as f32 / u32::MAX as f32) * 2.0 - 1.0
 }
 
-#[wasm_bindgen]
-pub fn compute_demon_spectrum(
-    input: &[f32],
-    sample_rate: f32,
-    max_freq_hz: u32,
-    input_band_low_hz: f32,
-    input_band_high_hz: f32,
-    envelope_hp_hz: f32,
-    decimated_rate_target_hz: f32,
-) -> Vec<f32> {
-    let max_freq = max_freq_hz as usize;
-    let mut spectrum = vec![0.0f32; max_freq + 1];
+// One SplitMix64 step. Its outputs make unrelated xorshift32 seeds: successive xorshift32
+// outputs would seed the same stream a step apart.
+#[inline]
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// Separate noise streams for each part of a voice that draws random numbers (echo scheduling,
+// engine, cavitation, biologic generators, bearing noise, modulation, variation), drawn from
+// the voice seed so that none repeats or shifts what another draws.
+fn voice_seeds(seed: u32) -> [u32; 7] {
+    let mut state = seed as u64;
+    [(); 7].map(|_| (splitmix64(&mut state) >> 32) as u32 | 1)
+}
+
+// An id sent from JS as a float; only whole numbers in range count.
+#[inline]
+fn whole_id(value: f32) -> Option<u32> {
+    (value >= 0.0 && value.fract() == 0.0 && value <= u32::MAX as f32).then_some(value as u32)
+}
+
+// A duration counted in samples, carried over to a rate `ratio` times the old one.
+#[inline]
+fn scale_samples(samples: u32, ratio: f32) -> u32 {
+    (samples as f64 * ratio as f64).round() as u32
+}
+
+// Whole samples in `seconds` at `sample_rate`, rounded, with what the rounding took or added
+// held in `carry` and settled on the next call. Event schedulers time their gaps and units
+// this way, so over a run of events they keep to the seconds asked for at any rate instead of
+// each interval coming up to a sample short.
+#[inline]
+fn event_samples(seconds: f32, sample_rate: f32, carry: &mut f32) -> u32 {
+    let exact = (seconds * sample_rate + *carry).max(0.0);
+    let whole = exact.round();
+    *carry = exact - whole;
+    whole.min(u32::MAX as f32) as u32
+}
+
+// As event_samples, for a countdown set on the sample its event fires, which doesn't count
+// down itself (see control_stretch).
+#[inline]
+fn event_countdown(seconds: f32, sample_rate: f32, carry: &mut f32) -> u32 {
+    event_samples(seconds, sample_rate, carry).saturating_sub(1)
+}
+
+// Heap bytes behind a Vec, counting capacity reserved but not yet used.
+#[inline]
+fn vec_bytes<T>(v: &Vec<T>) -> usize {
+    v.capacity() * core::mem::size_of::<T>()
+}
+
+// Graph-wide conditions shared by every voice during a process() call.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct Environment {
+    // Douglas-style sea state, 0 (glassy) to 9 (phenomenal).
+    sea_state: f32,
+    // Receiver depth below the surface in metres.
+    listener_depth: f32,
+    // Range (m) at which a voice plays at its nominal level.
+    reference_range: f32,
+    // Range (m) where spreading turns from spherical to cylindrical.
+    spreading_transition: f32,
+    absorption_db_per_km: f32,
+    // Scale on the range-dependent absorption low-pass; 1 is physical seawater, 0 bypasses it.
+    absorption_filter: f32,
+    water_depth: f32,
+    // Thermocline depth (m); 0 means an isothermal column with no layer.
+    layer_depth: f32,
+    layer_loss_db: f32,
+    bottom_type: BottomType,
+    channel_model: ChannelModel,
+    // Two-layer sound-speed profile (m/s) above and below the layer, used by the ray model.
+    sound_speed_upper: f32,
+    sound_speed_lower: f32,
+    // Surface duct (mixed layer) depth in metres; 0 disables ducting.
+    duct_depth: f32,
+    duct_leak_db_per_km: f32,
+    // Convergence-zone spacing (m); 0 disables the zones and their shadow.
+    cz_interval: f32,
+    cz_width: f32,
+    cz_gain_db: f32,
+    // Surface/bottom bounce copies rendered per voice (0 disables multipath).
+    multipath_taps: usize,
+}
 
-    if input.len() < 64 || !sample_rate.is_finite() || sample_rate <= 0.0 {
-        return spectrum;
+impl Environment {
+    fn new() -> Self {
+        Self {
+            sea_state: 2.0,
+            listener_depth: 60.0,
+            reference_range: 100.0,
+            spreading_transition: 1000.0,
+            absorption_db_per_km: 0.1,
+            absorption_filter: 1.0,
+            water_depth: 200.0,
+            layer_depth: 0.0,
+            layer_loss_db: 15.0,
+            bottom_type: BottomType::Sand,
+            channel_model: ChannelModel::Spreading,
+            sound_speed_upper: 1500.0,
+            sound_speed_lower: 1490.0,
+            duct_depth: 0.0,
+            duct_leak_db_per_km: 0.2,
+            cz_interval: 0.0,
+            cz_width: 4000.0,
+            cz_gain_db: 15.0,
+            multipath_taps: 0,
+        }
     }
+}
 
-    let band_low = if input_band_low_hz.is_finite() {
-        input_band_low_hz.max(1.0)
-    } else {
-        20.0
-    };
-    let band_high = if input_band_high_hz.is_finite() {
-        input_band_high_hz.max(band_low + 1.0)
-    } else {
-        1800.0
-    };
-    let env_hp = if envelope_hp_hz.is_finite() {
-        envelope_hp_hz.max(0.1)
-    } else {
-        1.0
-    };
-    let decim_target = if decimated_rate_target_hz.is_finite() {
-        decimated_rate_target_hz.max(100.0)
-    } else {
-        500.0
-    };
-
-    let n_raw = input.len();
-    let mean_raw = input.iter().copied().sum::<f32>() / n_raw as f32;
-
-    let hp_rc = 1.0 / (2.0 * PI * band_low);
-    let lp_rc = 1.0 / (2.0 * PI * band_high);
-    let dt = 1.0 / sample_rate;
-    let hp_alpha = hp_rc / (hp_rc + dt);
-    let lp_alpha = dt / (lp_rc + dt);
-
-    let d = ((sample_rate / decim_target).floor() as usize).max(1);
-    let decim_sr = sample_rate / d as f32;
-    let n_decim = n_raw / d;
-    if n_decim < 8 {
-        return spectrum;
-    }
-
-    let mut decim_env = vec![0.0f32; n_decim];
-    let mut hp_y = 0.0f32;
-    let mut hp_prev_x = 0.0f32;
-    let mut lp_y = 0.0f32;
-    let mut accum = 0.0f32;
-    for i in 0..n_raw {
-        let x = input[i] - mean_raw;
-        hp_y = hp_alpha * (hp_y + x - hp_prev_x);
-        hp_prev_x = x;
-        lp_y += lp_alpha * (hp_y - lp_y);
-        accum += lp_y.abs();
-        if (i + 1) % d == 0 {
-            let idx = (i + 1) / d - 1;
-            decim_env[idx] = accum / d as f32;
-            accum = 0.0;
-        }
-    }
-
-    let env_hp_rc = 1.0 / (2.0 * PI * env_hp);
-    let decim_dt = 1.0 / decim_sr;
-    let env_hp_alpha = env_hp_rc / (env_hp_rc + decim_dt);
-    let mut env_hp_y = 0.0f32;
-    let mut env_hp_prev_x = decim_env[0];
-    let mut signal = vec![0.0f32; n_decim];
-    for i in 0..n_decim {
-        let x = decim_env[i];
-        env_hp_y = env_hp_alpha * (env_hp_y + x - env_hp_prev_x);
-        env_hp_prev_x = x;
-        signal[i] = env_hp_y;
-    }
-
-    let hann_denom = (n_decim.saturating_sub(1)).max(1) as f32;
-    for f in 1..=max_freq {
-        let omega = (2.0 * PI * f as f32) / decim_sr;
-        let mut re = 0.0f32;
-        let mut im = 0.0f32;
-        for i in 0..n_decim {
-            let hann = 0.5 * (1.0 - ((2.0 * PI * i as f32) / hann_denom).cos());
-            let v = signal[i] * hann;
-            let angle = omega * i as f32;
-            re += v * angle.cos();
-            im -= v * angle.sin();
-        }
-        spectrum[f] = (re.hypot(im)) / n_decim as f32;
-    }
-
-    spectrum
+// Level for an engine partial at `hz` when the engine is band-limited to `limit` Hz: full up
+// to 80% of the limit and fading to nothing at it, so a partial a rising RPM sweeps past the
+// limit leaves smoothly rather than clicking off.
+#[inline]
+fn partial_gain(hz: f32, limit: f32) -> f32 {
+    clamp((limit - hz) / (0.2 * limit), 0.0, 1.0)
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct EngineState {
-    shaft_phase: f32,
-    blade_phase: f32,
-    machinery_phase_a: f32,
-    machinery_phase_b: f32,
-    drift_phase: f32,
+    shaft_phase: Phase,
+    // Accumulated on its own at the blade rate rather than derived from the shaft, so a change
+    // of blade count bends its frequency without a jump in phase.
+    blade_phase: Phase,
+    machinery_phase_a: Phase,
+    machinery_phase_b: Phase,
+    drift_phase: Phase,
     drift_value: f32,
+    rng: u32,
+    // Live values, driven sample by sample from the owning voice's ramps.
     current_rpm: f32,
-    target_rpm: f32,
     current_shaft_rate: f32,
-    target_shaft_rate: f32,
     blades: f32,
+    // Blade count as heard, gliding toward `blades`; 0 until the first sample takes `blades`
+    // as it stands.
+    blade_count: f32,
     load: f32,
     rpm_jitter: f32,
     class_profile: u32,
+    low_rate: LowRate,
+    // Last input to the machinery and output waveshapers and its ln cosh, for band-limited
+    // shaping.
+    shaper_history: [(f32, f64); 2],
 }
 
 impl EngineState {
-    fn new() -> Self {
+    fn new(seed: u32) -> Self {
         Self {
-            shaft_phase: 0.0,
-            blade_phase: 0.0,
-            machinery_phase_a: 0.0,
-            machinery_phase_b: 0.0,
-            drift_phase: 0.0,
+            shaft_phase: Phase::default(),
+            blade_phase: Phase::default(),
+            machinery_phase_a: Phase::default(),
+            machinery_phase_b: Phase::default(),
+            drift_phase: Phase::default(),
             drift_value: 0.0,
+            rng: seed,
             current_rpm: 0.0,
-            target_rpm: 0.0,
             current_shaft_rate: 0.0,
-            target_shaft_rate: 0.0,
             blades: 5.0,
+            blade_count: 0.0,
             load: 0.45,
             rpm_jitter: 0.12,
             class_profile: 0,
+            low_rate: LowRate::default(),
+            shaper_history: [(0.0, 0.0); 2],
         }
     }
 
@@ -190,15 +427,23 @@ impl EngineState {
         }
     }
 
+    // Advances the engine a sample and returns its waveform, or just advances it when not
+    // `voiced`. Given a `limit` in Hz, partials fade out as they near it and the waveshapers
+    // are anti-aliased. Takes the smoothing coefficients of the rate wander and the blade
+    // count's glide after the rate.
     #[inline]
-    fn tick(&mut self, sample_rate: f32, rng: &mut u32) -> f32 {
-        self.current_rpm = self.current_rpm * 0.992 + self.target_rpm * 0.008;
-        let target_shaft_rate = if self.target_shaft_rate > 0.01 {
-            self.target_shaft_rate
-        } else {
-            self.target_rpm / 60.0
-        };
-        self.current_shaft_rate = self.current_shaft_rate * 0.992 + target_shaft_rate * 0.008;
+    fn tick<M: Math>(
+        &mut self,
+        sample_rate: f32,
+        (drift, blade_glide): (f32, f32),
+        voiced: bool,
+        limit: Option<f32>,
+    ) -> f32 {
+        let blades = self.blades.max(1.0);
+        if self.blade_count <= 0.0 {
+            self.blade_count = blades;
+        }
+        self.blade_count += blade_glide * (blades - self.blade_count);
         if self.current_rpm < 0.05 {
             return 0.0;
         }
@@ -207,61 +452,137 @@ impl EngineState {
         let jitter = clamp(self.rpm_jitter, 0.0, 1.0);
         let (shaft_weight, blade_weight, machinery_weight, brightness) = self.class_weights();
 
-        let drift_target = rand_signed(rng) * (0.25 + jitter * 0.75);
-        self.drift_value += 0.0009 * (drift_target - self.drift_value);
-        self.drift_phase += TWO_PI * (0.11 + 0.22 * jitter) / sample_rate;
-        if self.drift_phase >= TWO_PI {
-            self.drift_phase -= TWO_PI;
-        }
+        let drift_target = rand_signed(&mut self.rng) * (0.25 + jitter * 0.75);
+        self.drift_value += drift * (drift_target - self.drift_value);
+        self.drift_phase.advance(0.11 + 0.22 * jitter, sample_rate);
 
         let wander = 1.0
-            + (0.004 + 0.02 * jitter) * self.drift_phase.sin()
+            + (0.004 + 0.02 * jitter) * M::sin(self.drift_phase.radians())
             + self.drift_value * (0.002 + 0.012 * jitter);
         let shaft_hz = (self.current_shaft_rate.max(0.05) * wander).max(0.05);
-        let bpf_hz = (shaft_hz * self.blades.max(1.0)).max(0.1);
+        let bpf_hz = (shaft_hz * self.blade_count).max(0.1);
 
-        self.shaft_phase += TWO_PI * shaft_hz / sample_rate;
-        if self.shaft_phase >= TWO_PI {
-            self.shaft_phase -= TWO_PI;
-        }
-        self.blade_phase = (self.shaft_phase * self.blades.max(1.0)).rem_euclid(TWO_PI);
+        self.shaft_phase.advance(shaft_hz, sample_rate);
+        self.blade_phase.advance(bpf_hz, sample_rate);
+        let blade_phase = self.blade_phase.radians();
 
         let machinery_hz_a = 24.0 + shaft_hz * (11.0 + 5.0 * brightness) + 28.0 * load;
         let machinery_hz_b = 70.0 + bpf_hz * 0.5 + 55.0 * brightness + 36.0 * load;
-        self.machinery_phase_a += TWO_PI * machinery_hz_a / sample_rate;
-        self.machinery_phase_b += TWO_PI * machinery_hz_b / sample_rate;
-        if self.machinery_phase_a >= TWO_PI {
-            self.machinery_phase_a -= TWO_PI;
-        }
-        if self.machinery_phase_b >= TWO_PI {
-            self.machinery_phase_b -= TWO_PI;
-        }
-
-        let shaft = self.shaft_phase.sin() * 0.65
-            + (2.0 * self.shaft_phase).sin() * 0.24
-            + (3.0 * self.shaft_phase).sin() * 0.11;
-        let blade = self.blade_phase.sin() * 0.70
-            + (2.0 * self.blade_phase).sin() * 0.18
-            + (3.0 * self.blade_phase).sin() * 0.08
-            + (4.0 * self.blade_phase).sin() * 0.05;
-        let machinery = self.machinery_phase_a.sin() * 0.75
-            + (1.11 * self.machinery_phase_b).sin() * 0.23
-            + (self.machinery_phase_a + self.blade_phase * 0.16).sin() * 0.14;
-        let machinery = (machinery * (1.18 + 0.24 * load)).tanh();
+        self.machinery_phase_a.advance(machinery_hz_a, sample_rate);
+        self.machinery_phase_b.advance(machinery_hz_b, sample_rate);
+        if !voiced {
+            return 0.0;
+        }
+
+        // Exactly 1 without a limit, so the unlimited engine is untouched.
+        let g = |hz: f32| limit.map_or(1.0, |limit| partial_gain(hz, limit));
+        let shaft = M::sin(self.shaft_phase.radians()) * 0.65 * g(shaft_hz)
+            + M::sin(2.0 * self.shaft_phase.radians()) * 0.24 * g(2.0 * shaft_hz)
+            + M::sin(3.0 * self.shaft_phase.radians()) * 0.11 * g(3.0 * shaft_hz);
+        let blade = M::sin(blade_phase) * 0.70 * g(bpf_hz)
+            + M::sin(2.0 * blade_phase) * 0.18 * g(2.0 * bpf_hz)
+            + M::sin(3.0 * blade_phase) * 0.08 * g(3.0 * bpf_hz)
+            + M::sin(4.0 * blade_phase) * 0.05 * g(4.0 * bpf_hz);
+        let machinery = M::sin(self.machinery_phase_a.radians()) * 0.75 * g(machinery_hz_a)
+            + M::sin(1.11 * self.machinery_phase_b.radians()) * 0.23 * g(1.11 * machinery_hz_b)
+            + M::sin(self.machinery_phase_a.radians() + blade_phase * 0.16)
+                * 0.14
+                * g(machinery_hz_a + 0.16 * bpf_hz);
+        let machinery = self.shape::<M>(0, machinery * (1.18 + 0.24 * load), limit.is_some());
 
         let envelope = 0.80
-            + 0.14 * self.blade_phase.sin().abs()
-            + 0.05 * self.drift_phase.sin();
+            + 0.14 * M::sin(blade_phase).abs()
+            + 0.05 * M::sin(self.drift_phase.radians());
         let harmonic_signal = shaft * shaft_weight
             + blade * blade_weight * (0.72 + 0.38 * load)
             + machinery * machinery_weight * (0.55 + 0.55 * load);
         let amplitude = (0.035 + (self.current_rpm / 420.0).min(0.22)) * (0.88 + 0.24 * load);
 
-        (harmonic_signal * envelope * 1.25).tanh() * amplitude
+        self.shape::<M>(1, harmonic_signal * envelope * 1.25, limit.is_some()) * amplitude
+    }
+
+    // tanh, or when `band_limited` its first-order antiderivative anti-aliased form: the mean
+    // of tanh along the line from the shaper's last input to `x`, which is the slope of
+    // ln cosh between them. Harmonics folding back past Nyquist come out far weaker than from
+    // sampling tanh directly, for half a sample of delay.
+    #[inline]
+    fn shape<M: Math>(&mut self, shaper: usize, x: f32, band_limited: bool) -> f32 {
+        if !band_limited {
+            return M::tanh(x);
+        }
+        let (prev, prev_integral) = self.shaper_history[shaper];
+        // In f64, as the difference of two nearby values is divided by their small distance.
+        let (x64, prev64) = (x as f64, prev as f64);
+        let integral =
+            x64.abs() + (1.0 + (-2.0 * x64.abs()).exp()).ln() - core::f64::consts::LN_2;
+        let dx = x64 - prev64;
+        let y = if dx.abs() > 1.0e-6 {
+            (integral - prev_integral) / dx
+        } else {
+            (0.5 * (x64 + prev64)).tanh()
+        };
+        self.shaper_history[shaper] = (x, integral);
+        y as f32
+    }
+
+    // Runs the engine over the first `frames` of the control lanes, writing its output and the
+    // shaft and blade phases it leaves behind at each sample. It only turns while its own gate
+    // or the cavitation gate is open, and only computes its waveform while its own gate is:
+    // with cavitation alone, just the phases advance. The waveform is computed every `factor`
+    // samples and interpolated between; the phases are exact at every sample. When
+    // `band_limited` it keeps below the Nyquist frequency of that slower rate.
+    fn render<M: Math>(
+        &mut self,
+        sample_rate: f32,
+        lanes: &mut VoiceLanes,
+        frames: usize,
+        factor: u32,
+        band_limited: bool,
+    ) {
+        let limit = band_limited.then(|| 0.5 * sample_rate / factor.max(1) as f32);
+        // A new blade count settles in about a quarter of a second.
+        let glides = (smoothing(23.0, sample_rate), smoothing(80.0, sample_rate));
+        let [engine_gate, cav_gate, _] = &lanes.gates;
+        for i in 0..frames {
+            self.current_rpm = lanes.rpm[i];
+            self.current_shaft_rate = lanes.shaft_rate[i];
+            self.load = lanes.load[i];
+            lanes.engine[i] = if engine_gate[i] > 0.0 || cav_gate[i] > 0.0 {
+                let voiced = engine_gate[i] > 0.0 && self.low_rate.is_due();
+                let value = self.tick::<M>(sample_rate, glides, voiced, limit);
+                self.low_rate.next(value, factor)
+            } else {
+                0.0
+            };
+            lanes.shaft_phase[i] = self.shaft_phase.radians();
+            lanes.blade_phase[i] = self.blade_phase.radians();
+        }
     }
 }
 
-#[derive(Clone, Copy)]
+// Cavitation's filter and envelope coefficients at one sample rate, worked out once a block.
+struct CavTiming {
+    // The 1 kHz low-pass shaping the broadband noise.
+    broadband: f32,
+    // How quickly the burst detector follows the blade pulses.
+    drive: f32,
+    // Burst decay with no cavitation and at its heaviest; the regime moves between the two.
+    burst_calm: f32,
+    burst_heavy: f32,
+}
+
+impl CavTiming {
+    fn new(sample_rate: f32) -> Self {
+        Self {
+            broadband: lowpass(1000.0, sample_rate),
+            drive: smoothing(0.68, sample_rate),
+            burst_calm: decay(0.2, sample_rate),
+            burst_heavy: decay(0.105, sample_rate),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct CavState {
     broadband_lp_a: f32,
     broadband_lp_b: f32,
@@ -275,10 +596,11 @@ struct CavState {
     shaped_noise: f32,
     burst_env: f32,
     burst_drive: f32,
+    rng: u32,
 }
 
 impl CavState {
-    fn new() -> Self {
+    fn new(seed: u32) -> Self {
         Self {
             broadband_lp_a: 0.0,
             broadband_lp_b: 0.0,
@@ -292,6 +614,7 @@ impl CavState {
             shaped_noise: 0.0,
             burst_env: 0.0,
             burst_drive: 0.0,
+            rng: seed,
         }
     }
 
@@ -318,9 +641,10 @@ impl CavState {
     }
 
     #[inline]
-    fn tick(
+    #[allow(clippy::too_many_arguments)]
+    fn tick<M: Math>(
         &mut self,
-        sample_rate: f32,
+        timing: &CavTiming,
         rpm: f32,
         shaft_phase: f32,
         blade_phase: f32,
@@ -328,7 +652,6 @@ impl CavState {
         load: f32,
         cavitation_level: f32,
         class_profile: u32,
-        rng: &mut u32,
     ) -> f32 {
         if rpm < 1.0 {
             self.broadband_lp_a = 0.0;
@@ -342,11 +665,8 @@ impl CavState {
             return 0.0;
         }
 
-        let white = rand_signed(rng);
-        let cutoff_hz = 1000.0;
-        let rc = 1.0 / (TWO_PI * cutoff_hz);
-        let dt = 1.0 / sample_rate.max(1.0);
-        let broadband_alpha = (dt / (rc + dt)).clamp(0.0, 1.0);
+        let white = rand_signed(&mut self.rng);
+        let broadband_alpha = timing.broadband;
         self.broadband_lp_a += broadband_alpha * (white - self.broadband_lp_a);
         self.broadband_lp_b += broadband_alpha * (self.broadband_lp_a - self.broadband_lp_b);
         self.broadband_lp_c += broadband_alpha * (self.broadband_lp_b - self.broadband_lp_c);
@@ -374,31 +694,33 @@ impl CavState {
         let fizz = hp - self.slow_noise * (0.25 + 0.2 * regime_drive);
         self.shaped_noise += (0.22 + 0.18 * regime_drive) * (fizz - self.shaped_noise);
 
-        let blade_mod = (0.5 + 0.5 * blade_phase.cos()).powf(10.0);
+        let blade_mod = M::powf(0.5 + 0.5 * M::cos(blade_phase), 10.0);
         let blade_pulse = blade_mod;
         let discrete_blades = clamp(blade_count.round(), 1.0, 12.0) as usize;
         self.refresh_blade_cache(discrete_blades);
         let pulse_power = 10.0 + regime_drive * 8.0;
         let mut blade_packet = 0.0;
-        let shaft_cos = shaft_phase.cos();
-        let shaft_sin = shaft_phase.sin();
+        let shaft_cos = M::cos(shaft_phase);
+        let shaft_sin = M::sin(shaft_phase);
         for blade_idx in 0..discrete_blades {
             let phase_cos = shaft_cos * self.blade_offset_cos[blade_idx]
                 - shaft_sin * self.blade_offset_sin[blade_idx];
-            let passage = (0.5 + 0.5 * phase_cos).powf(pulse_power);
+            // The rotated cosine can round just below -1; keep powf's base non-negative.
+            let passage = M::powf((0.5 + 0.5 * phase_cos).max(0.0), pulse_power);
             blade_packet += passage * self.blade_weight_cache[blade_idx];
         }
         blade_packet /= discrete_blades.max(1) as f32;
         let modulation_depth = 0.18 + regime_drive * 0.72;
         let blade_envelope = (1.0 - modulation_depth)
             + modulation_depth * (0.18 + blade_mod * 0.34 + blade_packet * 1.48);
-        self.burst_drive += 0.03 * ((blade_pulse * regime_drive) - self.burst_drive);
+        self.burst_drive += timing.drive * ((blade_pulse * regime_drive) - self.burst_drive);
         let burst_threshold = 0.76 - regime_drive * 0.26;
         if self.burst_drive > burst_threshold {
             self.burst_env = (self.burst_env + 0.42 * regime_drive).min(1.0);
             self.burst_drive *= 0.65;
         }
-        self.burst_env *= 0.90 - regime_drive * 0.08;
+        let burst_decay = timing.burst_calm + (timing.burst_heavy - timing.burst_calm) * regime_drive;
+        self.burst_env = flush_denormal(self.burst_env * burst_decay);
 
         let regime_none = (1.0 - regime_drive * 2.5).clamp(0.0, 1.0);
         let regime_incipient = (1.0 - ((regime_drive - 0.28) / 0.22).abs()).clamp(0.0, 1.0);
@@ -412,7 +734,7 @@ impl CavState {
         let heavy_texture = (self.shaped_noise * (0.32 + 0.56 * blade_mod)
             + hp * (0.12 + 0.1 * blade_mod))
             * blade_envelope
-            + self.burst_env * rand_signed(rng) * 0.55;
+            + self.burst_env * rand_signed(&mut self.rng) * 0.55;
 
         let intensity = 0.008
             + regime_none * 0.012
@@ -426,16 +748,40 @@ impl CavState {
 
         texture * intensity * (0.55 + 0.45 * load + 0.18 * speed_norm)
     }
+
+    // Runs cavitation over the first `frames` of the lanes, after the engine has filled in
+    // the phases it locks to.
+    fn render<M: Math>(&mut self, sample_rate: f32, engine: &EngineState, lanes: &mut VoiceLanes, frames: usize) {
+        let timing = CavTiming::new(sample_rate);
+        for i in 0..frames {
+            lanes.cav[i] = if lanes.gates[1][i] > 0.0 {
+                self.tick::<M>(
+                    &timing,
+                    lanes.rpm[i],
+                    lanes.shaft_phase[i],
+                    lanes.blade_phase[i],
+                    engine.blade_count.max(1.0),
+                    lanes.load[i],
+                    lanes.cavitation[i],
+                    engine.class_profile,
+                )
+            } else {
+                0.0
+            };
+        }
+    }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct ChirpState {
     // Samples until next trigger.
     samples_to_next: u32,
+    // Rounding left over from the last interval scheduled.
+    carry: f32,
     // Envelope for current event.
     env: f32,
     // Current oscillator phase.
-    phase: f32,
+    phase: Phase,
     // Chirp start frequency.
     start_hz: f32,
     // Chirp end frequency.
@@ -448,14 +794,19 @@ impl ChirpState {
     fn new() -> Self {
         Self {
             samples_to_next: 0,
+            carry: 0.0,
             env: 0.0,
-            phase: 0.0,
+            phase: Phase::default(),
             start_hz: 1600.0,
             end_hz: 450.0,
             progress: 1.0,
         }
     }
 
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+    }
+
     #[inline]
     fn schedule_next(&mut self, sample_rate: f32, rpm: f32, bio_rate: f32, rng: &mut u32) {
         let speed = clamp(rpm / 280.0, 0.0, 1.0);
@@ -463,7 +814,7 @@ impl ChirpState {
         let base_ms = 110.0 - 70.0 * speed;
         let jitter = 0.55 + 0.9 * ((xorshift32(rng) as f32) / u32::MAX as f32);
         let ms = (base_ms * rate_scale * jitter).max(12.0);
-        self.samples_to_next = (sample_rate * ms * 0.001) as u32;
+        self.samples_to_next = event_countdown(ms * 0.001, sample_rate, &mut self.carry);
     }
 
     #[inline]
@@ -471,43 +822,65 @@ impl ChirpState {
         let speed = clamp(rpm / 280.0, 0.0, 1.0);
         let rnd = (xorshift32(rng) as f32) / u32::MAX as f32;
         self.env = 0.9;
-        self.phase = 0.0;
+        self.phase = Phase::default();
         self.progress = 0.0;
         self.start_hz = 1400.0 + 1200.0 * speed + 300.0 * rnd;
         self.end_hz = 300.0 + 450.0 * (1.0 - speed);
     }
 
-    #[inline]
-    fn tick(&mut self, sample_rate: f32, rpm: f32, bio_rate: f32, rng: &mut u32) -> f32 {
-        if self.samples_to_next == 0 {
-            self.trigger_click(rpm, rng);
-            self.schedule_next(sample_rate, rpm, bio_rate, rng);
-        } else {
-            self.samples_to_next -= 1;
+    fn render<M: Math>(
+        &mut self,
+        out: &mut [f32],
+        sample_rate: f32,
+        rpm: &[f32],
+        bio_rate: f32,
+        rng: &mut u32,
+    ) {
+        // A chirp sweeps in under half a millisecond and rings on about as long again.
+        let env_decay = decay(0.34, sample_rate);
+        let sweep = 1000.0 / (0.46 * sample_rate);
+        let mut i = 0;
+        while i < out.len() {
+            let fired = self.samples_to_next == 0;
+            if fired {
+                self.trigger_click(rpm[i], rng);
+                self.schedule_next(sample_rate, rpm[i], bio_rate, rng);
+            }
+            let len = control_stretch(&mut self.samples_to_next, fired, out.len() - i);
+            let stretch = &mut out[i..i + len];
+            if self.env <= 0.0001 {
+                stretch.fill(0.0);
+            } else {
+                stretch
+                    .iter_mut()
+                    .for_each(|s| *s = self.tick::<M>(sample_rate, env_decay, sweep));
+            }
+            i += len;
         }
+    }
 
+    #[inline]
+    fn tick<M: Math>(&mut self, sample_rate: f32, env_decay: f32, sweep: f32) -> f32 {
         if self.env <= 0.0001 {
             return 0.0;
         }
 
         let t = self.progress;
         let chirp_hz = self.start_hz + (self.end_hz - self.start_hz) * t;
-        self.phase += TWO_PI * chirp_hz / sample_rate;
-        if self.phase >= TWO_PI {
-            self.phase -= TWO_PI;
-        }
+        self.phase.advance(chirp_hz, sample_rate);
 
         // Fast decay envelope for short transients.
-        self.env *= 0.94;
-        self.progress = (self.progress + 0.045).min(1.0);
+        self.env = flush_denormal(self.env * env_decay);
+        self.progress = (self.progress + sweep).min(1.0);
 
-        self.phase.sin() * self.env
+        M::sin(self.phase.radians()) * self.env
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct SnappingShrimpState {
     samples_to_next: u32,
+    carry: f32,
     burst_left: u32,
     env: f32,
     hp_state: f32,
@@ -517,18 +890,24 @@ impl SnappingShrimpState {
     fn new() -> Self {
         Self {
             samples_to_next: 0,
+            carry: 0.0,
             burst_left: 0,
             env: 0.0,
             hp_state: 0.0,
         }
     }
 
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+        self.burst_left = scale_samples(self.burst_left, ratio);
+    }
+
     #[inline]
     fn schedule_next(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
         let jitter = 0.4 + 1.2 * ((xorshift32(rng) as f32) / u32::MAX as f32);
         let base_ms = 140.0 - 136.0 * bio_rate;
         let ms = (base_ms * jitter).max(1.0);
-        self.samples_to_next = (sample_rate * ms * 0.001) as u32;
+        self.samples_to_next = event_countdown(ms * 0.001, sample_rate, &mut self.carry);
     }
 
     #[inline]
@@ -538,16 +917,35 @@ impl SnappingShrimpState {
         self.env = 1.0;
     }
 
-    #[inline]
-    fn tick(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) -> f32 {
-        if self.samples_to_next == 0 {
-            self.trigger_snap(sample_rate, rng);
-            self.schedule_next(sample_rate, bio_rate, rng);
-        } else {
-            self.samples_to_next -= 1;
+    fn render<M: Math>(&mut self, out: &mut [f32], sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        // The snap holds up through its burst and is cut off sharply after it.
+        let decays = (decay(0.063, sample_rate), decay(0.013, sample_rate));
+        let mut i = 0;
+        while i < out.len() {
+            let fired = self.samples_to_next == 0;
+            if fired {
+                self.trigger_snap(sample_rate, rng);
+                self.schedule_next(sample_rate, bio_rate, rng);
+            }
+            let len = control_stretch(&mut self.samples_to_next, fired, out.len() - i);
+            let stretch = &mut out[i..i + len];
+            if self.is_idle() {
+                stretch.fill(0.0);
+            } else {
+                stretch.iter_mut().for_each(|s| *s = self.tick::<M>(decays, rng));
+            }
+            i += len;
         }
+    }
+
+    #[inline]
+    fn is_idle(&self) -> bool {
+        self.burst_left == 0 && self.env < 0.0001
+    }
 
-        if self.burst_left == 0 && self.env < 0.0001 {
+    #[inline]
+    fn tick<M: Math>(&mut self, (burst, tail): (f32, f32), rng: &mut u32) -> f32 {
+        if self.is_idle() {
             return 0.0;
         }
 
@@ -558,86 +956,96 @@ impl SnappingShrimpState {
 
         if self.burst_left > 0 {
             self.burst_left -= 1;
-            self.env *= 0.72;
+            self.env = flush_denormal(self.env * burst);
         } else {
-            self.env *= 0.2;
+            self.env = flush_denormal(self.env * tail);
         }
 
         transient * self.env * 0.9
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct WhaleMoanState {
-    phase: f32,
-    lfo_phase: f32,
+    phase: Phase,
+    lfo_phase: Phase,
     drift: f32,
 }
 
 impl WhaleMoanState {
     fn new() -> Self {
         Self {
-            phase: 0.0,
-            lfo_phase: 0.0,
+            phase: Phase::default(),
+            lfo_phase: Phase::default(),
             drift: 0.0,
         }
     }
 
     #[inline]
-    fn tick(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) -> f32 {
+    fn tick<M: Math>(
+        &mut self,
+        sample_rate: f32,
+        drift: f32,
+        bio_rate: f32,
+        voiced: bool,
+        rng: &mut u32,
+    ) -> f32 {
         let drift_target = rand_signed(rng) * 0.02;
-        self.drift += 0.0008 * (drift_target - self.drift);
+        self.drift += drift * (drift_target - self.drift);
 
         let lfo_hz = 0.08 + bio_rate * 0.32;
-        self.lfo_phase += TWO_PI * lfo_hz / sample_rate;
-        if self.lfo_phase >= TWO_PI {
-            self.lfo_phase -= TWO_PI;
-        }
+        self.lfo_phase.advance(lfo_hz, sample_rate);
 
         let base_hz = 40.0 + 110.0 * bio_rate;
-        let wobble = self.lfo_phase.sin() * (10.0 + 20.0 * bio_rate);
+        let wobble = M::sin(self.lfo_phase.radians()) * (10.0 + 20.0 * bio_rate);
         let inst_hz = (base_hz + wobble + self.drift * 140.0).max(20.0);
-        self.phase += TWO_PI * inst_hz / sample_rate;
-        if self.phase >= TWO_PI {
-            self.phase -= TWO_PI;
+        self.phase.advance(inst_hz, sample_rate);
+        if !voiced {
+            return 0.0;
         }
 
-        let p = self.phase;
-        let moan = p.sin() * 0.75 + (0.5 * p).sin() * 0.35 + (1.5 * p).sin() * 0.12;
+        let p = self.phase.radians();
+        let moan = M::sin(p) * 0.75 + M::sin(0.5 * p) * 0.35 + M::sin(1.5 * p) * 0.12;
         moan * 0.42
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct DolphinWhistleState {
     samples_to_next: u32,
+    carry: f32,
     env: f32,
-    phase: f32,
+    phase: Phase,
     start_hz: f32,
     end_hz: f32,
     progress: f32,
-    vibrato_phase: f32,
+    vibrato_phase: Phase,
 }
 
 impl DolphinWhistleState {
     fn new() -> Self {
         Self {
             samples_to_next: 0,
+            carry: 0.0,
             env: 0.0,
-            phase: 0.0,
+            phase: Phase::default(),
             start_hz: 5000.0,
             end_hz: 7600.0,
             progress: 1.0,
-            vibrato_phase: 0.0,
+            vibrato_phase: Phase::default(),
         }
     }
 
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+    }
+
     #[inline]
     fn schedule_next(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
         let jitter = 0.6 + ((xorshift32(rng) as f32) / u32::MAX as f32);
         let base_ms = 320.0 - 210.0 * bio_rate;
         let ms = (base_ms * jitter).max(35.0);
-        self.samples_to_next = (sample_rate * ms * 0.001) as u32;
+        self.samples_to_next = event_countdown(ms * 0.001, sample_rate, &mut self.carry);
     }
 
     #[inline]
@@ -648,20 +1056,37 @@ impl DolphinWhistleState {
         let span = (r1 * 2.0 - 1.0) * (1500.0 + 1900.0 * bio_rate);
         self.start_hz = start;
         self.end_hz = clamp(start + span, 3000.0, 15000.0);
-        self.phase = 0.0;
+        self.phase = Phase::default();
         self.progress = 0.0;
         self.env = 1.0;
     }
 
-    #[inline]
-    fn tick(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) -> f32 {
-        if self.samples_to_next == 0 {
-            self.trigger_whistle(rng, bio_rate);
-            self.schedule_next(sample_rate, bio_rate, rng);
-        } else {
-            self.samples_to_next -= 1;
+    fn render<M: Math>(&mut self, out: &mut [f32], sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        // The glide takes about 12 ms at the slowest rate and 7 ms at the fastest.
+        let env_decay = decay(5.9, sample_rate);
+        let glide = (86.0 + 58.0 * bio_rate) / sample_rate;
+        let mut i = 0;
+        while i < out.len() {
+            let fired = self.samples_to_next == 0;
+            if fired {
+                self.trigger_whistle(rng, bio_rate);
+                self.schedule_next(sample_rate, bio_rate, rng);
+            }
+            let len = control_stretch(&mut self.samples_to_next, fired, out.len() - i);
+            let stretch = &mut out[i..i + len];
+            if self.env <= 0.0001 {
+                stretch.fill(0.0);
+            } else {
+                stretch
+                    .iter_mut()
+                    .for_each(|s| *s = self.tick::<M>(sample_rate, bio_rate, env_decay, glide));
+            }
+            i += len;
         }
+    }
 
+    #[inline]
+    fn tick<M: Math>(&mut self, sample_rate: f32, bio_rate: f32, env_decay: f32, glide: f32) -> f32 {
         if self.env <= 0.0001 {
             return 0.0;
         }
@@ -669,27 +1094,22 @@ impl DolphinWhistleState {
         let t = self.progress;
         let curved = t * t * (3.0 - 2.0 * t);
         let glide_hz = self.start_hz + (self.end_hz - self.start_hz) * curved;
-        self.vibrato_phase += TWO_PI * (5.0 + 3.0 * bio_rate) / sample_rate;
-        if self.vibrato_phase >= TWO_PI {
-            self.vibrato_phase -= TWO_PI;
-        }
-        let vib = 1.0 + 0.015 * self.vibrato_phase.sin();
-        self.phase += TWO_PI * glide_hz * vib / sample_rate;
-        if self.phase >= TWO_PI {
-            self.phase -= TWO_PI;
-        }
+        self.vibrato_phase.advance(5.0 + 3.0 * bio_rate, sample_rate);
+        let vib = 1.0 + 0.015 * M::sin(self.vibrato_phase.radians());
+        self.phase.advance(glide_hz * vib, sample_rate);
 
-        self.env *= 0.9965;
-        self.progress = (self.progress + (0.0018 + 0.0012 * bio_rate)).min(1.0);
-        self.phase.sin() * self.env * 0.30
+        self.env = flush_denormal(self.env * env_decay);
+        self.progress = (self.progress + glide).min(1.0);
+        M::sin(self.phase.radians()) * self.env * 0.30
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct EcholocationClickState {
     samples_to_next: u32,
+    carry: f32,
     burst_left: u32,
-    phase: f32,
+    phase: Phase,
     env: f32,
     click_hz: f32,
 }
@@ -698,19 +1118,25 @@ impl EcholocationClickState {
     fn new() -> Self {
         Self {
             samples_to_next: 0,
+            carry: 0.0,
             burst_left: 0,
-            phase: 0.0,
+            phase: Phase::default(),
             env: 0.0,
             click_hz: 9500.0,
         }
     }
 
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+        self.burst_left = scale_samples(self.burst_left, ratio);
+    }
+
     #[inline]
     fn schedule_next(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
         let jitter = 0.7 + 0.6 * ((xorshift32(rng) as f32) / u32::MAX as f32);
         let base_ms = 60.0 - 55.0 * bio_rate;
         let ms = (base_ms * jitter).max(1.2);
-        self.samples_to_next = (sample_rate * ms * 0.001) as u32;
+        self.samples_to_next = event_countdown(ms * 0.001, sample_rate, &mut self.carry);
     }
 
     #[inline]
@@ -718,41 +1144,53 @@ impl EcholocationClickState {
         let r = (xorshift32(rng) as f32) / u32::MAX as f32;
         self.click_hz = 7000.0 + 7000.0 * (0.35 * bio_rate + 0.65 * r);
         self.burst_left = (sample_rate * 0.00025) as u32 + 1;
-        self.phase = 0.0;
+        self.phase = Phase::default();
         self.env = 1.0;
     }
 
-    #[inline]
-    fn tick(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) -> f32 {
-        if self.samples_to_next == 0 {
-            self.trigger_click(sample_rate, bio_rate, rng);
-            self.schedule_next(sample_rate, bio_rate, rng);
-        } else {
-            self.samples_to_next -= 1;
+    fn render<M: Math>(&mut self, out: &mut [f32], sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        let decays = (decay(0.026, sample_rate), decay(0.007, sample_rate));
+        let mut i = 0;
+        while i < out.len() {
+            let fired = self.samples_to_next == 0;
+            if fired {
+                self.trigger_click(sample_rate, bio_rate, rng);
+                self.schedule_next(sample_rate, bio_rate, rng);
+            }
+            let len = control_stretch(&mut self.samples_to_next, fired, out.len() - i);
+            let stretch = &mut out[i..i + len];
+            // The tail decays to exactly zero within a few samples of the burst.
+            if self.burst_left == 0 && self.env == 0.0 {
+                stretch.fill(0.0);
+            } else {
+                stretch.iter_mut().for_each(|s| *s = self.tick::<M>(sample_rate, decays));
+            }
+            i += len;
         }
+    }
 
+    #[inline]
+    fn tick<M: Math>(&mut self, sample_rate: f32, (burst, tail): (f32, f32)) -> f32 {
         if self.burst_left == 0 {
-            self.env *= 0.05;
+            self.env = flush_denormal(self.env * tail);
             return 0.0;
         }
 
         self.burst_left -= 1;
-        self.phase += TWO_PI * self.click_hz / sample_rate;
-        if self.phase >= TWO_PI {
-            self.phase -= TWO_PI;
-        }
-        self.env *= 0.45;
-        self.phase.sin() * self.env * 0.85
+        self.phase.advance(self.click_hz, sample_rate);
+        self.env = flush_denormal(self.env * burst);
+        M::sin(self.phase.radians()) * self.env * 0.85
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct HumpbackSongState {
     samples_to_next: u32,
+    carry: f32,
     unit_samples_left: u32,
     unit_kind: u32,
-    phase: f32,
-    mod_phase: f32,
+    phase: Phase,
+    mod_phase: Phase,
     env: f32,
     current_hz: f32,
     target_hz: f32,
@@ -763,10 +1201,11 @@ impl HumpbackSongState {
     fn new() -> Self {
         Self {
             samples_to_next: 0,
+            carry: 0.0,
             unit_samples_left: 0,
             unit_kind: 0,
-            phase: 0.0,
-            mod_phase: 0.0,
+            phase: Phase::default(),
+            mod_phase: Phase::default(),
             env: 0.0,
             current_hz: 220.0,
             target_hz: 220.0,
@@ -774,92 +1213,109 @@ impl HumpbackSongState {
         }
     }
 
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+        self.unit_samples_left = scale_samples(self.unit_samples_left, ratio);
+    }
+
     #[inline]
     fn schedule_gap(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
         let jitter = 0.6 + ((xorshift32(rng) as f32) / u32::MAX as f32);
         let base_ms = 320.0 - 220.0 * bio_rate;
         let gap_ms = (base_ms * jitter).max(30.0);
-        self.samples_to_next = (sample_rate * gap_ms * 0.001) as u32;
+        self.samples_to_next = event_samples(gap_ms * 0.001, sample_rate, &mut self.carry);
     }
 
     #[inline]
     fn trigger_unit(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
         self.unit_kind = xorshift32(rng) % 3;
-        self.phase = 0.0;
-        self.mod_phase = 0.0;
+        self.phase = Phase::default();
+        self.mod_phase = Phase::default();
         self.env = 1.0;
         self.unit_progress = 0.0;
 
-        match self.unit_kind {
+        let dur_ms = match self.unit_kind {
             // Low moan unit
             0 => {
                 let dur_ms = 450.0 + 650.0 * ((xorshift32(rng) as f32) / u32::MAX as f32);
-                self.unit_samples_left = (sample_rate * dur_ms * 0.001) as u32;
                 self.target_hz = 55.0 + 110.0 * bio_rate;
+                dur_ms
             }
             // Mid whistle unit
             1 => {
                 let dur_ms = 260.0 + 420.0 * ((xorshift32(rng) as f32) / u32::MAX as f32);
-                self.unit_samples_left = (sample_rate * dur_ms * 0.001) as u32;
                 self.target_hz = 380.0 + 620.0 * ((xorshift32(rng) as f32) / u32::MAX as f32);
+                dur_ms
             }
             // Rising/falling unit
             _ => {
                 let dur_ms = 320.0 + 480.0 * ((xorshift32(rng) as f32) / u32::MAX as f32);
-                self.unit_samples_left = (sample_rate * dur_ms * 0.001) as u32;
                 self.target_hz = 120.0 + 340.0 * ((xorshift32(rng) as f32) / u32::MAX as f32);
+                dur_ms
             }
-        }
+        };
+        self.unit_samples_left = event_samples(dur_ms * 0.001, sample_rate, &mut self.carry);
     }
 
-    #[inline]
-    fn tick(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) -> f32 {
-        if self.unit_samples_left == 0 {
-            if self.samples_to_next == 0 {
+    // Gaps are skipped whole; a unit plays sample by sample and schedules the gap after it
+    // once it has run out.
+    fn render<M: Math>(&mut self, out: &mut [f32], sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        let glide = smoothing(8.3, sample_rate);
+        let mut i = 0;
+        while i < out.len() {
+            let left = out.len() - i;
+            if self.unit_samples_left == 0 {
+                if self.samples_to_next > 0 {
+                    let len = left.min(self.samples_to_next as usize);
+                    self.samples_to_next -= len as u32;
+                    out[i..i + len].fill(0.0);
+                    i += len;
+                    continue;
+                }
                 self.trigger_unit(sample_rate, bio_rate, rng);
-            } else {
-                self.samples_to_next -= 1;
-                return 0.0;
             }
+            let len = left.min(self.unit_samples_left.max(1) as usize);
+            self.unit_samples_left = self.unit_samples_left.saturating_sub(len as u32);
+            let stretch = &mut out[i..i + len];
+            stretch.iter_mut().for_each(|s| *s = self.tick::<M>(sample_rate, bio_rate, glide));
+            if self.unit_samples_left == 0 {
+                self.schedule_gap(sample_rate, bio_rate, rng);
+            }
+            i += len;
         }
+    }
 
-        self.unit_samples_left = self.unit_samples_left.saturating_sub(1);
-        if self.unit_samples_left == 0 {
-            self.schedule_gap(sample_rate, bio_rate, rng);
-        }
-
-        self.current_hz += 0.0025 * (self.target_hz - self.current_hz);
-        self.mod_phase += TWO_PI * (0.2 + 0.9 * bio_rate) / sample_rate;
-        if self.mod_phase >= TWO_PI {
-            self.mod_phase -= TWO_PI;
-        }
-        let mod_scale = 1.0 + 0.10 * self.mod_phase.sin();
-        self.phase += TWO_PI * self.current_hz * mod_scale / sample_rate;
-        if self.phase >= TWO_PI {
-            self.phase -= TWO_PI;
-        }
+    #[inline]
+    fn tick<M: Math>(&mut self, sample_rate: f32, bio_rate: f32, glide: f32) -> f32 {
+        self.current_hz += glide * (self.target_hz - self.current_hz);
+        self.mod_phase.advance(0.2 + 0.9 * bio_rate, sample_rate);
+        let mod_scale = 1.0 + 0.10 * M::sin(self.mod_phase.radians());
+        self.phase.advance(self.current_hz * mod_scale, sample_rate);
 
         self.unit_progress = (self.unit_progress + 1.0 / (sample_rate * 0.7)).min(1.0);
         let attack = (self.unit_progress / 0.12).min(1.0);
-        let release = 1.0 - self.unit_progress.powf(1.8);
+        let release = 1.0 - M::powf(self.unit_progress, 1.8);
         self.env = attack * release.max(0.0);
 
-        let p = self.phase;
+        let p = self.phase.radians();
         match self.unit_kind {
-            0 => (p.sin() * 0.75 + (0.5 * p).sin() * 0.35 + (1.4 * p).sin() * 0.12) * self.env * 0.40,
-            1 => (p.sin() * 0.9 + (2.03 * p).sin() * 0.08) * self.env * 0.34,
+            0 => (M::sin(p) * 0.75 + M::sin(0.5 * p) * 0.35 + M::sin(1.4 * p) * 0.12) * self.env * 0.40,
+            1 => (M::sin(p) * 0.9 + M::sin(2.03 * p) * 0.08) * self.env * 0.34,
             _ => {
                 let rise = self.unit_progress * self.unit_progress;
                 let sweep = self.current_hz * (0.8 + 0.6 * rise);
-                self.phase += TWO_PI * (sweep - self.current_hz) / sample_rate;
-                (self.phase.sin() * 0.8 + (1.5 * self.phase).sin() * 0.15) * self.env * 0.36
+                self.phase.advance(sweep - self.current_hz, sample_rate);
+                let p = self.phase.radians();
+                (M::sin(p) * 0.8 + M::sin(1.5 * p) * 0.15) * self.env * 0.36
             }
         }
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum BioType {
+// Biologic and ambient generators, selected per voice through Param::BioType.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BioType {
     Chirp = 0,
     SnappingShrimp = 1,
     WhaleMoan = 2,
@@ -882,12 +1338,30 @@ enum BioType {
     GeologicalNoise = 19,
     MinkePulse = 20,
     DolphinSchool = 21,
+    HydrothermalVent = 22,
+    SeismicTPhase = 23,
+    SurfAgitation = 24,
 }
 
 impl BioType {
+    // Types with discrete calls or transients that can be fired on demand.
+    fn fires_events(self) -> bool {
+        matches!(
+            self,
+            Self::Chirp
+                | Self::SnappingShrimp
+                | Self::EcholocationClick
+                | Self::DolphinWhistle
+                | Self::HumpbackSong
+                | Self::HydrothermalVent
+                | Self::SeismicTPhase
+                | Self::SurfAgitation
+        )
+    }
+
     #[inline]
     fn from_param(value: f32) -> Self {
-        match clamp(value.round(), 0.0, 21.0) as u32 {
+        match clamp(value.round(), 0.0, 24.0) as u32 {
             1 => Self::SnappingShrimp,
             2 => Self::WhaleMoan,
             3 => Self::DolphinWhistle,
@@ -909,18 +1383,22 @@ impl BioType {
             19 => Self::GeologicalNoise,
             20 => Self::MinkePulse,
             21 => Self::DolphinSchool,
+            22 => Self::HydrothermalVent,
+            23 => Self::SeismicTPhase,
+            24 => Self::SurfAgitation,
             _ => Self::Chirp,
         }
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct LowCallState {
     samples_to_next: u32,
+    carry: f32,
     unit_left: u32,
     env: f32,
-    phase: f32,
-    lfo_phase: f32,
+    phase: Phase,
+    lfo_phase: Phase,
     current_hz: f32,
     target_hz: f32,
 }
@@ -929,103 +1407,152 @@ impl LowCallState {
     fn new() -> Self {
         Self {
             samples_to_next: 0,
+            carry: 0.0,
             unit_left: 0,
             env: 0.0,
-            phase: 0.0,
-            lfo_phase: 0.0,
+            phase: Phase::default(),
+            lfo_phase: Phase::default(),
             current_hz: 40.0,
             target_hz: 40.0,
         }
     }
 
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+        self.unit_left = scale_samples(self.unit_left, ratio);
+    }
+
     #[inline]
     fn schedule_next(&mut self, sample_rate: f32, bio_rate: f32, min_ms: f32, max_ms: f32, rng: &mut u32) {
         let jitter = 0.65 + 0.7 * ((xorshift32(rng) as f32) / u32::MAX as f32);
         let span = (max_ms - min_ms).max(1.0);
         let ms = (max_ms - span * bio_rate) * jitter;
-        self.samples_to_next = (sample_rate * ms.max(5.0) * 0.001) as u32;
+        self.samples_to_next = event_samples(ms.max(5.0) * 0.001, sample_rate, &mut self.carry);
     }
 
     #[inline]
     fn trigger(&mut self, sample_rate: f32, hz: f32, dur_ms: f32) {
-        self.unit_left = (sample_rate * dur_ms.max(10.0) * 0.001) as u32;
+        self.unit_left = event_samples(dur_ms.max(10.0) * 0.001, sample_rate, &mut self.carry);
         self.current_hz = hz;
         self.target_hz = hz;
         self.env = 1.0;
     }
 
-    #[inline]
-    fn tick(&mut self, mode: BioType, sample_rate: f32, bio_rate: f32, rng: &mut u32) -> f32 {
-        if self.unit_left == 0 {
-            if self.samples_to_next == 0 {
-                match mode {
-                    BioType::BlueWhale => {
-                        let base = 14.0 + 6.0 * ((xorshift32(rng) as f32) / u32::MAX as f32);
-                        self.trigger(sample_rate, base, 2600.0 + bio_rate * 1800.0);
-                        self.target_hz = base + 2.2 + bio_rate * 1.4;
-                        self.schedule_next(sample_rate, bio_rate, 2800.0, 8000.0, rng);
-                    }
-                    BioType::FinWhale => {
-                        self.trigger(sample_rate, 18.0 + 4.0 * bio_rate, 650.0);
-                        self.target_hz = 20.0 + 2.0 * bio_rate;
-                        self.schedule_next(sample_rate, bio_rate, 900.0, 2500.0, rng);
-                    }
-                    BioType::MinkePulse => {
-                        self.trigger(sample_rate, 85.0 + 70.0 * bio_rate, 180.0);
-                        self.target_hz = self.current_hz * (1.1 + 0.2 * bio_rate);
-                        self.schedule_next(sample_rate, bio_rate, 120.0, 520.0, rng);
-                    }
-                    _ => {
-                        self.trigger(sample_rate, 160.0 + 260.0 * bio_rate, 420.0 + 420.0 * bio_rate);
-                        self.target_hz = self.current_hz * (0.9 + 0.15 * bio_rate);
-                        self.schedule_next(sample_rate, bio_rate, 350.0, 1200.0, rng);
-                    }
+    // Gaps are skipped whole; a call plays sample by sample, its waveform computed every
+    // `factor` samples through `low_rate`.
+    #[allow(clippy::too_many_arguments)]
+    fn render<M: Math>(
+        &mut self,
+        mode: BioType,
+        out: &mut [f32],
+        sample_rate: f32,
+        bio_rate: f32,
+        low_rate: &mut LowRate,
+        factor: u32,
+        rng: &mut u32,
+    ) {
+        let glide = smoothing(13.9, sample_rate);
+        let env_ms = match mode {
+            BioType::BlueWhale => 139.0,
+            BioType::FinWhale => 26.0,
+            BioType::MinkePulse => 6.9,
+            _ => 13.0,
+        };
+        let timing = (glide, decay(env_ms, sample_rate));
+        let mut i = 0;
+        while i < out.len() {
+            let left = out.len() - i;
+            if self.unit_left == 0 {
+                if self.samples_to_next > 0 {
+                    let len = left.min(self.samples_to_next as usize);
+                    self.samples_to_next -= len as u32;
+                    out[i..i + len].fill(0.0);
+                    i += len;
+                    continue;
                 }
-            } else {
-                self.samples_to_next -= 1;
-                return 0.0;
+                self.trigger_call(mode, sample_rate, bio_rate, rng);
             }
+            let len = left.min(self.unit_left.max(1) as usize);
+            self.unit_left = self.unit_left.saturating_sub(len as u32);
+            for s in &mut out[i..i + len] {
+                let value = self.tick::<M>(mode, sample_rate, timing, bio_rate, low_rate.is_due(), rng);
+                *s = low_rate.next(value, factor);
+            }
+            i += len;
         }
+    }
 
-        self.unit_left = self.unit_left.saturating_sub(1);
-        self.current_hz += 0.0015 * (self.target_hz - self.current_hz);
-        self.lfo_phase += TWO_PI * (0.04 + bio_rate * 0.25) / sample_rate;
-        if self.lfo_phase >= TWO_PI {
-            self.lfo_phase -= TWO_PI;
+    #[inline]
+    fn trigger_call(&mut self, mode: BioType, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        match mode {
+            BioType::BlueWhale => {
+                let base = 14.0 + 6.0 * ((xorshift32(rng) as f32) / u32::MAX as f32);
+                self.trigger(sample_rate, base, 2600.0 + bio_rate * 1800.0);
+                self.target_hz = base + 2.2 + bio_rate * 1.4;
+                self.schedule_next(sample_rate, bio_rate, 2800.0, 8000.0, rng);
+            }
+            BioType::FinWhale => {
+                self.trigger(sample_rate, 18.0 + 4.0 * bio_rate, 650.0);
+                self.target_hz = 20.0 + 2.0 * bio_rate;
+                self.schedule_next(sample_rate, bio_rate, 900.0, 2500.0, rng);
+            }
+            BioType::MinkePulse => {
+                self.trigger(sample_rate, 85.0 + 70.0 * bio_rate, 180.0);
+                self.target_hz = self.current_hz * (1.1 + 0.2 * bio_rate);
+                self.schedule_next(sample_rate, bio_rate, 120.0, 520.0, rng);
+            }
+            _ => {
+                self.trigger(sample_rate, 160.0 + 260.0 * bio_rate, 420.0 + 420.0 * bio_rate);
+                self.target_hz = self.current_hz * (0.9 + 0.15 * bio_rate);
+                self.schedule_next(sample_rate, bio_rate, 350.0, 1200.0, rng);
+            }
         }
+    }
+
+    #[inline]
+    // Takes the pitch glide's smoothing coefficient and the call's decay factor after the rate.
+    fn tick<M: Math>(
+        &mut self,
+        mode: BioType,
+        sample_rate: f32,
+        (glide, env_decay): (f32, f32),
+        bio_rate: f32,
+        voiced: bool,
+        rng: &mut u32,
+    ) -> f32 {
+        self.current_hz += glide * (self.target_hz - self.current_hz);
+        self.lfo_phase.advance(0.04 + bio_rate * 0.25, sample_rate);
         let wobble = match mode {
             BioType::BlueWhale => 0.9,
             BioType::FinWhale => 0.25,
             BioType::MinkePulse => 2.8,
             _ => 6.0,
         };
-        self.phase += TWO_PI * (self.current_hz + wobble * self.lfo_phase.sin()) / sample_rate;
-        if self.phase >= TWO_PI {
-            self.phase -= TWO_PI;
-        }
+        let hz = self.current_hz + wobble * M::sin(self.lfo_phase.radians());
+        self.phase.advance(hz, sample_rate);
 
-        self.env *= match mode {
-            BioType::BlueWhale => 0.99985,
-            BioType::FinWhale => 0.9992,
-            BioType::MinkePulse => 0.997,
-            _ => 0.9984,
-        };
+        self.env = flush_denormal(self.env * env_decay);
+        if !voiced {
+            return 0.0;
+        }
 
+        let p = self.phase.radians();
         let tone = match mode {
-            BioType::BlueWhale => self.phase.sin() * 0.9 + (0.5 * self.phase).sin() * 0.22,
-            BioType::FinWhale => self.phase.sin() * 0.95,
-            BioType::MinkePulse => self.phase.sin() * 0.65 + rand_signed(rng) * 0.08,
-            _ => self.phase.sin() * 0.75 + (1.4 * self.phase).sin() * 0.2,
+            BioType::BlueWhale => M::sin(p) * 0.9 + M::sin(0.5 * p) * 0.22,
+            BioType::FinWhale => M::sin(p) * 0.95,
+            BioType::MinkePulse => M::sin(p) * 0.65 + rand_signed(rng) * 0.08,
+            _ => M::sin(p) * 0.75 + M::sin(1.4 * p) * 0.2,
         };
         tone * self.env * 0.42
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct ClickTrainState {
     samples_to_next: u32,
+    carry: f32,
     burst_left: u32,
-    click_phase: f32,
+    click_phase: Phase,
     env: f32,
     click_hz: f32,
 }
@@ -1034,60 +1561,92 @@ impl ClickTrainState {
     fn new() -> Self {
         Self {
             samples_to_next: 0,
+            carry: 0.0,
             burst_left: 0,
-            click_phase: 0.0,
+            click_phase: Phase::default(),
             env: 0.0,
             click_hz: 6000.0,
         }
     }
 
-    #[inline]
-    fn tick(&mut self, mode: BioType, sample_rate: f32, bio_rate: f32, rng: &mut u32) -> f32 {
-        if self.samples_to_next == 0 {
-            let r = (xorshift32(rng) as f32) / u32::MAX as f32;
-            self.click_hz = match mode {
-                BioType::SpermWhaleClick => 1800.0 + 1800.0 * r,
-                _ => 6000.0 + 8000.0 * r,
-            };
-            self.burst_left = match mode {
-                BioType::SpermWhaleClick => (sample_rate * 0.00065) as u32 + 1,
-                _ => (sample_rate * 0.00025) as u32 + 1,
-            };
-            self.env = 1.0;
-            let base_ms = match mode {
-                BioType::SpermWhaleClick => 120.0 - 105.0 * bio_rate,
-                _ => 35.0 - 28.0 * bio_rate,
-            };
-            let jitter = 0.65 + 0.8 * r;
-            self.samples_to_next = (sample_rate * (base_ms * jitter).max(1.0) * 0.001) as u32;
-        } else {
-            self.samples_to_next -= 1;
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+        self.burst_left = scale_samples(self.burst_left, ratio);
+    }
+
+    fn render<M: Math>(
+        &mut self,
+        mode: BioType,
+        out: &mut [f32],
+        sample_rate: f32,
+        bio_rate: f32,
+        rng: &mut u32,
+    ) {
+        // Sperm whale clicks ring a little longer than the small odontocetes'.
+        let env_ms = match mode {
+            BioType::SpermWhaleClick => 0.044,
+            _ => 0.028,
+        };
+        let env_decay = decay(env_ms, sample_rate);
+        let mut i = 0;
+        while i < out.len() {
+            let fired = self.samples_to_next == 0;
+            if fired {
+                self.trigger_click(mode, sample_rate, bio_rate, rng);
+            }
+            let len = control_stretch(&mut self.samples_to_next, fired, out.len() - i);
+            let stretch = &mut out[i..i + len];
+            if self.burst_left == 0 {
+                stretch.fill(0.0);
+            } else {
+                stretch.iter_mut().for_each(|s| *s = self.tick::<M>(sample_rate, env_decay, rng));
+            }
+            i += len;
         }
+    }
+
+    #[inline]
+    fn trigger_click(&mut self, mode: BioType, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        let r = (xorshift32(rng) as f32) / u32::MAX as f32;
+        self.click_hz = match mode {
+            BioType::SpermWhaleClick => 1800.0 + 1800.0 * r,
+            _ => 6000.0 + 8000.0 * r,
+        };
+        self.burst_left = match mode {
+            BioType::SpermWhaleClick => (sample_rate * 0.00065) as u32 + 1,
+            _ => (sample_rate * 0.00025) as u32 + 1,
+        };
+        self.env = 1.0;
+        let base_ms = match mode {
+            BioType::SpermWhaleClick => 120.0 - 105.0 * bio_rate,
+            _ => 35.0 - 28.0 * bio_rate,
+        };
+        let jitter = 0.65 + 0.8 * r;
+        let ms = (base_ms * jitter).max(1.0);
+        self.samples_to_next = event_countdown(ms * 0.001, sample_rate, &mut self.carry);
+    }
 
+    #[inline]
+    fn tick<M: Math>(&mut self, sample_rate: f32, env_decay: f32, rng: &mut u32) -> f32 {
         if self.burst_left == 0 {
             return 0.0;
         }
 
         self.burst_left -= 1;
-        self.click_phase += TWO_PI * self.click_hz / sample_rate;
-        if self.click_phase >= TWO_PI {
-            self.click_phase -= TWO_PI;
-        }
-        self.env *= match mode {
-            BioType::SpermWhaleClick => 0.62,
-            _ => 0.48,
-        };
-        (self.click_phase.sin() + rand_signed(rng) * 0.15) * self.env * 0.78
+        self.click_phase.advance(self.click_hz, sample_rate);
+        self.env = flush_denormal(self.env * env_decay);
+        (M::sin(self.click_phase.radians()) + rand_signed(rng) * 0.15) * self.env * 0.78
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct SocialCallState {
     samples_to_next: u32,
+    carry: f32,
     unit_left: u32,
     env: f32,
-    phase_a: f32,
-    phase_b: f32,
+    phase_a: Phase,
+    phase_b: Phase,
     start_hz: f32,
     end_hz: f32,
     progress: f32,
@@ -1097,89 +1656,124 @@ impl SocialCallState {
     fn new() -> Self {
         Self {
             samples_to_next: 0,
+            carry: 0.0,
             unit_left: 0,
             env: 0.0,
-            phase_a: 0.0,
-            phase_b: 0.0,
+            phase_a: Phase::default(),
+            phase_b: Phase::default(),
             start_hz: 1200.0,
             end_hz: 1600.0,
             progress: 1.0,
         }
     }
 
-    #[inline]
-    fn tick(&mut self, mode: BioType, sample_rate: f32, bio_rate: f32, rng: &mut u32) -> f32 {
-        if self.unit_left == 0 {
-            if self.samples_to_next == 0 {
-                let r0 = (xorshift32(rng) as f32) / u32::MAX as f32;
-                let r1 = (xorshift32(rng) as f32) / u32::MAX as f32;
-                self.start_hz = match mode {
-                    BioType::OrcaCall => 650.0 + 1800.0 * r0,
-                    BioType::BelugaCall => 1800.0 + 6200.0 * r0,
-                    BioType::DolphinSchool => 2500.0 + 4500.0 * r0,
-                    BioType::HerringSchool => 80.0 + 120.0 * r0,
-                    _ => 1100.0 + 1800.0 * r0,
-                };
-                let span = match mode {
-                    BioType::OrcaCall => 900.0 + 1300.0 * bio_rate,
-                    BioType::BelugaCall => 2000.0 + 3000.0 * bio_rate,
-                    BioType::DolphinSchool => 2600.0 + 3600.0 * bio_rate,
-                    BioType::HerringSchool => 20.0 + 35.0 * bio_rate,
-                    _ => 1200.0,
-                };
-                self.end_hz = if r1 > 0.5 {
-                    self.start_hz + span
-                } else {
-                    self.start_hz - span * 0.6
-                };
-                let unit_ms = match mode {
-                    BioType::OrcaCall => 260.0 + 420.0 * r0,
-                    BioType::BelugaCall => 120.0 + 260.0 * r0,
-                    BioType::DolphinSchool => 90.0 + 200.0 * r0,
-                    BioType::HerringSchool => 320.0 + 240.0 * r0,
-                    _ => 240.0,
-                };
-                self.unit_left = (sample_rate * unit_ms * 0.001) as u32;
-                self.samples_to_next =
-                    (sample_rate * (120.0 + 260.0 * (1.0 - bio_rate)).max(10.0) * 0.001) as u32;
-                self.progress = 0.0;
-                self.env = 1.0;
-            } else {
-                self.samples_to_next -= 1;
-                return 0.0;
-            }
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+        self.unit_left = scale_samples(self.unit_left, ratio);
+    }
+
+    // Gaps are skipped whole; a call plays sample by sample.
+    fn render<M: Math>(
+        &mut self,
+        mode: BioType,
+        out: &mut [f32],
+        sample_rate: f32,
+        bio_rate: f32,
+        rng: &mut u32,
+    ) {
+        // Busier groups sweep faster and cut each call shorter.
+        let sweep = (168.0 + 96.0 * bio_rate) / sample_rate;
+        let timing = (sweep, decay(6.9 - 1.7 * bio_rate, sample_rate));
+        let mut i = 0;
+        while i < out.len() {
+            let left = out.len() - i;
+            if self.unit_left == 0 {
+                if self.samples_to_next > 0 {
+                    let len = left.min(self.samples_to_next as usize);
+                    self.samples_to_next -= len as u32;
+                    out[i..i + len].fill(0.0);
+                    i += len;
+                    continue;
+                }
+                self.trigger_call(mode, sample_rate, bio_rate, rng);
+            }
+            let len = left.min(self.unit_left.max(1) as usize);
+            self.unit_left = self.unit_left.saturating_sub(len as u32);
+            let stretch = &mut out[i..i + len];
+            stretch.iter_mut().for_each(|s| *s = self.tick::<M>(mode, sample_rate, timing, rng));
+            i += len;
         }
+    }
+
+    #[inline]
+    fn trigger_call(&mut self, mode: BioType, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        let r0 = (xorshift32(rng) as f32) / u32::MAX as f32;
+        let r1 = (xorshift32(rng) as f32) / u32::MAX as f32;
+        self.start_hz = match mode {
+            BioType::OrcaCall => 650.0 + 1800.0 * r0,
+            BioType::BelugaCall => 1800.0 + 6200.0 * r0,
+            BioType::DolphinSchool => 2500.0 + 4500.0 * r0,
+            BioType::HerringSchool => 80.0 + 120.0 * r0,
+            _ => 1100.0 + 1800.0 * r0,
+        };
+        let span = match mode {
+            BioType::OrcaCall => 900.0 + 1300.0 * bio_rate,
+            BioType::BelugaCall => 2000.0 + 3000.0 * bio_rate,
+            BioType::DolphinSchool => 2600.0 + 3600.0 * bio_rate,
+            BioType::HerringSchool => 20.0 + 35.0 * bio_rate,
+            _ => 1200.0,
+        };
+        self.end_hz = if r1 > 0.5 {
+            self.start_hz + span
+        } else {
+            self.start_hz - span * 0.6
+        };
+        let unit_ms = match mode {
+            BioType::OrcaCall => 260.0 + 420.0 * r0,
+            BioType::BelugaCall => 120.0 + 260.0 * r0,
+            BioType::DolphinSchool => 90.0 + 200.0 * r0,
+            BioType::HerringSchool => 320.0 + 240.0 * r0,
+            _ => 240.0,
+        };
+        self.unit_left = event_samples(unit_ms * 0.001, sample_rate, &mut self.carry);
+        let gap_ms = (120.0 + 260.0 * (1.0 - bio_rate)).max(10.0);
+        self.samples_to_next = event_samples(gap_ms * 0.001, sample_rate, &mut self.carry);
+        self.progress = 0.0;
+        self.env = 1.0;
+    }
 
-        self.unit_left = self.unit_left.saturating_sub(1);
-        self.progress = (self.progress + 0.0035 + bio_rate * 0.002).min(1.0);
+    #[inline]
+    fn tick<M: Math>(
+        &mut self,
+        mode: BioType,
+        sample_rate: f32,
+        (sweep, env_decay): (f32, f32),
+        rng: &mut u32,
+    ) -> f32 {
+        self.progress = (self.progress + sweep).min(1.0);
         let curved = self.progress * self.progress * (3.0 - 2.0 * self.progress);
         let hz = self.start_hz + (self.end_hz - self.start_hz) * curved;
-        self.phase_a += TWO_PI * hz / sample_rate;
-        self.phase_b += TWO_PI * (hz * 1.37) / sample_rate;
-        if self.phase_a >= TWO_PI {
-            self.phase_a -= TWO_PI;
-        }
-        if self.phase_b >= TWO_PI {
-            self.phase_b -= TWO_PI;
-        }
-        self.env *= 0.997 - 0.001 * bio_rate;
+        self.phase_a.advance(hz, sample_rate);
+        self.phase_b.advance(hz * 1.37, sample_rate);
+        self.env = flush_denormal(self.env * env_decay);
+        let (a, b) = (self.phase_a.radians(), self.phase_b.radians());
 
         match mode {
             BioType::OrcaCall => {
-                (self.phase_a.sin() * 0.74 + (self.phase_b * 0.5).sin() * 0.24 + rand_signed(rng) * 0.06)
+                (M::sin(a) * 0.74 + M::sin(b * 0.5) * 0.24 + rand_signed(rng) * 0.06)
                     * self.env
                     * 0.36
             }
             BioType::BelugaCall => {
-                (self.phase_a.sin() * 0.55 + self.phase_b.sin() * 0.32 + rand_signed(rng) * 0.08)
+                (M::sin(a) * 0.55 + M::sin(b) * 0.32 + rand_signed(rng) * 0.08)
                     * self.env
                     * 0.34
             }
             BioType::DolphinSchool => {
-                (self.phase_a.sin() * 0.42 + rand_signed(rng) * 0.12) * self.env * 0.28
+                (M::sin(a) * 0.42 + rand_signed(rng) * 0.12) * self.env * 0.28
             }
             BioType::HerringSchool => {
-                (self.phase_a.sin() * 0.26 + self.phase_b.sin() * 0.18 + rand_signed(rng) * 0.14)
+                (M::sin(a) * 0.26 + M::sin(b) * 0.18 + rand_signed(rng) * 0.14)
                     * self.env
                     * 0.30
             }
@@ -1188,11 +1782,11 @@ impl SocialCallState {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct RotorState {
-    phase_a: f32,
-    phase_b: f32,
-    phase_c: f32,
+    phase_a: Phase,
+    phase_b: Phase,
+    phase_c: Phase,
     noise_lp: f32,
     burst_env: f32,
 }
@@ -1200,16 +1794,25 @@ struct RotorState {
 impl RotorState {
     fn new() -> Self {
         Self {
-            phase_a: 0.0,
-            phase_b: 0.0,
-            phase_c: 0.0,
+            phase_a: Phase::default(),
+            phase_b: Phase::default(),
+            phase_c: Phase::default(),
             noise_lp: 0.0,
             burst_env: 0.0,
         }
     }
 
     #[inline]
-    fn tick(&mut self, mode: BioType, sample_rate: f32, bio_rate: f32, rpm: f32, rng: &mut u32) -> f32 {
+    #[allow(clippy::too_many_arguments)]
+    fn tick<M: Math>(
+        &mut self,
+        mode: BioType,
+        sample_rate: f32,
+        (slap_decay, hiss): (f32, f32),
+        bio_rate: f32,
+        rpm: f32,
+        rng: &mut u32,
+    ) -> f32 {
         let rate_base = if rpm > 1.0 { rpm / 60.0 } else { 4.0 + bio_rate * 8.0 };
         let main_rate = match mode {
             BioType::HelicopterRotor => rate_base * (0.8 + 0.3 * bio_rate),
@@ -1224,30 +1827,22 @@ impl RotorState {
             _ => 1.0,
         };
         let broadband = rand_signed(rng);
-        self.noise_lp += 0.06 * (broadband - self.noise_lp);
+        self.noise_lp += hiss * (broadband - self.noise_lp);
         let hp = broadband - self.noise_lp;
 
-        self.phase_a += TWO_PI * main_rate / sample_rate;
-        self.phase_b += TWO_PI * tail_rate / sample_rate;
-        self.phase_c += TWO_PI * (main_rate * 0.5 + 16.0 * bio_rate) / sample_rate;
-        if self.phase_a >= TWO_PI {
-            self.phase_a -= TWO_PI;
-        }
-        if self.phase_b >= TWO_PI {
-            self.phase_b -= TWO_PI;
-        }
-        if self.phase_c >= TWO_PI {
-            self.phase_c -= TWO_PI;
-        }
+        self.phase_a.advance(main_rate, sample_rate);
+        self.phase_b.advance(tail_rate, sample_rate);
+        self.phase_c.advance(main_rate * 0.5 + 16.0 * bio_rate, sample_rate);
 
-        let blade = self.phase_a.sin() * 0.82 + (2.0 * self.phase_a).sin() * 0.22;
-        let tail = self.phase_b.sin() * 0.22;
-        let turbine = self.phase_c.sin() * 0.16;
-        let slap_drive = self.phase_a.sin().abs();
+        let a = self.phase_a.radians();
+        let blade = M::sin(a) * 0.82 + M::sin(2.0 * a) * 0.22;
+        let tail = M::sin(self.phase_b.radians()) * 0.22;
+        let turbine = M::sin(self.phase_c.radians()) * 0.16;
+        let slap_drive = M::sin(a).abs();
         if mode == BioType::HelicopterRotor && bio_rate < 0.4 && slap_drive > 0.94 {
             self.burst_env = 1.0;
         }
-        self.burst_env *= 0.96;
+        self.burst_env = flush_denormal(self.burst_env * slap_decay);
 
         match mode {
             BioType::JetAircraft => (hp * (0.22 + 0.45 * bio_rate) + turbine + blade * 0.18) * 0.55,
@@ -1257,7 +1852,7 @@ impl RotorState {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct NoiseFieldState {
     lp_a: f32,
     lp_b: f32,
@@ -1276,28 +1871,32 @@ impl NoiseFieldState {
     }
 
     #[inline]
-    fn tick(&mut self, mode: BioType, sample_rate: f32, bio_rate: f32, rng: &mut u32) -> f32 {
+    // Takes the drift's smoothing coefficient, the bursts' decay factor and the chance of a
+    // burst starting on any one sample, then the low-passes splitting the noise into bands.
+    fn tick<M: Math>(
+        &mut self,
+        mode: BioType,
+        (drift, burst_decay, burst_chance): (f32, f32, f32),
+        (low_lp, mid_lp): (f32, f32),
+        bio_rate: f32,
+        rng: &mut u32,
+    ) -> f32 {
         let white = rand_signed(rng);
-        self.lp_a += 0.02 * (white - self.lp_a);
-        self.lp_b += 0.12 * (white - self.lp_b);
-        self.drift += 0.001 * (rand_signed(rng) - self.drift);
+        self.lp_a += low_lp * (white - self.lp_a);
+        self.lp_b += mid_lp * (white - self.lp_b);
+        self.drift += drift * (rand_signed(rng) - self.drift);
         let low = self.lp_a;
         let mid = self.lp_b - self.lp_a * 0.6;
         let high = white - self.lp_b;
 
-        let trigger = ((xorshift32(rng) as f32) / u32::MAX as f32) < (0.0004 + bio_rate * 0.0025);
+        let trigger = ((xorshift32(rng) as f32) / u32::MAX as f32) < burst_chance;
         if trigger {
             self.burst_env = 1.0;
         }
-        self.burst_env *= match mode {
-            BioType::GeologicalNoise => 0.997,
-            BioType::IceNoise => 0.985,
-            BioType::Precipitation => 0.94,
-            _ => 0.965,
-        };
+        self.burst_env = flush_denormal(self.burst_env * burst_decay);
 
         let shaped = match mode {
-            BioType::AmbientOcean => low * 0.65 + mid * 0.22 + high * 0.04 + (18.0 * self.drift).sin() * 0.03,
+            BioType::AmbientOcean => low * 0.65 + mid * 0.22 + high * 0.04 + M::sin(18.0 * self.drift) * 0.03,
             BioType::Precipitation => high * (0.22 + 0.46 * bio_rate) + mid * 0.12 + self.burst_env * high * 0.5,
             BioType::IceNoise => low * 0.32 + mid * 0.28 + self.burst_env * (low * 0.8 + high * 0.25),
             BioType::GeologicalNoise => low * 0.82 + mid * 0.14 + self.burst_env * (low * 1.2 + mid * 0.4),
@@ -1310,12 +1909,341 @@ impl NoiseFieldState {
             BioType::GeologicalNoise => 0.16 + 0.34 * bio_rate,
             _ => 0.2,
         };
-        let _ = sample_rate;
         shaped * level
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct VentState {
+    hiss_lp: f32,
+    rumble_lp_a: f32,
+    rumble_lp_b: f32,
+    surge_phase: Phase,
+    samples_to_next: u32,
+    carry: f32,
+    bubble_phase: Phase,
+    bubble_hz: f32,
+    bubble_env: f32,
+}
+
+impl VentState {
+    fn new() -> Self {
+        Self {
+            hiss_lp: 0.0,
+            rumble_lp_a: 0.0,
+            rumble_lp_b: 0.0,
+            surge_phase: Phase::default(),
+            samples_to_next: 0,
+            carry: 0.0,
+            bubble_phase: Phase::default(),
+            bubble_hz: 600.0,
+            bubble_env: 0.0,
+        }
+    }
+
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+    }
+
+    #[inline]
+    fn schedule_bubble(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        let jitter = 0.3 + 1.4 * ((xorshift32(rng) as f32) / u32::MAX as f32);
+        let base_ms = 90.0 - 78.0 * bio_rate;
+        let ms = (base_ms * jitter).max(4.0);
+        self.samples_to_next = event_countdown(ms * 0.001, sample_rate, &mut self.carry);
+    }
+
+    #[inline]
+    fn trigger_bubble(&mut self, rng: &mut u32) {
+        // Minnaert-style resonance: small bubbles ring higher and quieter.
+        let r = (xorshift32(rng) as f32) / u32::MAX as f32;
+        self.bubble_hz = 180.0 + 1400.0 * r * r;
+        self.bubble_phase = Phase::default();
+        self.bubble_env = 0.35 + 0.65 * (1.0 - r);
+    }
+
+    // Stationary plume: ignores RPM so the source never picks up engine coupling.
+    #[inline]
+    // Takes the bubble ring's decay factor and the hiss's and rumble's low-pass coefficients
+    // after the rate.
+    fn tick<M: Math>(
+        &mut self,
+        sample_rate: f32,
+        (ring, hiss_lp, rumble_lp): (f32, f32, f32),
+        bio_rate: f32,
+        rng: &mut u32,
+    ) -> f32 {
+        if self.samples_to_next == 0 {
+            self.trigger_bubble(rng);
+            self.schedule_bubble(sample_rate, bio_rate, rng);
+        } else {
+            self.samples_to_next -= 1;
+        }
+
+        let white = rand_signed(rng);
+        self.hiss_lp += hiss_lp * (white - self.hiss_lp);
+        let hiss = white - self.hiss_lp * 0.7;
+        self.rumble_lp_a += rumble_lp * (white - self.rumble_lp_a);
+        self.rumble_lp_b += rumble_lp * (self.rumble_lp_a - self.rumble_lp_b);
+
+        self.surge_phase.advance(0.05 + 0.15 * bio_rate, sample_rate);
+        let surge = 0.75 + 0.25 * M::sin(self.surge_phase.radians());
+        let rumble = self.rumble_lp_b * 14.0 * surge;
+
+        let mut bubble = 0.0;
+        if self.bubble_env > 0.0001 {
+            // Detaching bubbles shrink slightly, so the ring glides upward.
+            self.bubble_hz *= 1.0 + 0.8 / sample_rate;
+            self.bubble_phase.advance(self.bubble_hz, sample_rate);
+            self.bubble_env *= ring;
+            bubble = M::sin(self.bubble_phase.radians()) * self.bubble_env;
+        }
+
+        hiss * (0.05 + 0.07 * bio_rate) * surge + rumble * (0.22 + 0.20 * bio_rate) + bubble * 0.30
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct TPhaseState {
+    armed: bool,
+    samples_to_next: u32,
+    carry: f32,
+    event_pos: u32,
+    rise_samples: u32,
+    event_samples: u32,
+    env: f32,
+    peak: f32,
+    decay: f32,
+    lp_a: f32,
+    lp_b: f32,
+    roll_phase: Phase,
+    roll_hz: f32,
+}
+
+impl TPhaseState {
+    fn new() -> Self {
+        Self {
+            armed: false,
+            samples_to_next: 0,
+            carry: 0.0,
+            event_pos: 0,
+            rise_samples: 0,
+            event_samples: 0,
+            env: 0.0,
+            peak: 0.0,
+            decay: 1.0,
+            lp_a: 0.0,
+            lp_b: 0.0,
+            roll_phase: Phase::default(),
+            roll_hz: 0.4,
+        }
+    }
+
+    fn rescale(&mut self, ratio: f32) {
+        self.samples_to_next = scale_samples(self.samples_to_next, ratio);
+        self.event_pos = scale_samples(self.event_pos, ratio);
+        self.rise_samples = scale_samples(self.rise_samples, ratio);
+        self.event_samples = scale_samples(self.event_samples, ratio);
+        self.decay = self.decay.powf(1.0 / ratio);
+    }
+
+    // bio_rate maps to a mean spacing of 600 s (0.0+) down to 30 s (1.0); exactly 0 disables
+    // random events so the rumble only fires through trigger().
+    #[inline]
+    fn schedule_next(&mut self, sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        let u = ((xorshift32(rng) as f32) / u32::MAX as f32).max(1.0e-4);
+        let mean_s = 600.0 - 570.0 * bio_rate;
+        let s = clamp(-u.ln() * mean_s, 8.0, 3600.0);
+        self.samples_to_next = event_countdown(s, sample_rate, &mut self.carry);
+        self.armed = true;
+    }
+
+    #[inline]
+    fn trigger(&mut self, sample_rate: f32, rng: &mut u32) {
+        let r0 = (xorshift32(rng) as f32) / u32::MAX as f32;
+        let r1 = (xorshift32(rng) as f32) / u32::MAX as f32;
+        let rise_s = 4.0 + 8.0 * r0;
+        let decay_s = 12.0 + 18.0 * r1;
+        self.rise_samples = (sample_rate * rise_s) as u32;
+        self.event_samples = self.rise_samples + (sample_rate * decay_s * 3.0) as u32;
+        self.event_pos = 0;
+        self.peak = 0.5 + 0.5 * r1;
+        self.decay = (-1.0 / (decay_s * sample_rate)).exp();
+        self.roll_hz = 0.25 + 0.5 * r0;
+    }
+
+    fn render<M: Math>(&mut self, out: &mut [f32], sample_rate: f32, bio_rate: f32, rng: &mut u32) {
+        let mut i = 0;
+        while i < out.len() {
+            let left = out.len() - i;
+            let len = if bio_rate > 0.0 {
+                let fired = self.samples_to_next == 0;
+                if fired {
+                    if self.armed {
+                        self.trigger(sample_rate, rng);
+                    }
+                    self.schedule_next(sample_rate, bio_rate, rng);
+                }
+                control_stretch(&mut self.samples_to_next, fired, left)
+            } else {
+                left.min(CONTROL_FRAMES)
+            };
+            let stretch = &mut out[i..i + len];
+            if self.event_pos >= self.event_samples {
+                self.env = 0.0;
+                stretch.fill(0.0);
+            } else {
+                stretch.iter_mut().for_each(|s| *s = self.tick::<M>(sample_rate, rng));
+            }
+            i += len;
+        }
+    }
+
+    #[inline]
+    fn tick<M: Math>(&mut self, sample_rate: f32, rng: &mut u32) -> f32 {
+        if self.event_pos >= self.event_samples {
+            self.env = 0.0;
+            return 0.0;
+        }
+
+        self.event_pos += 1;
+        if self.event_pos < self.rise_samples {
+            let t = self.event_pos as f32 / self.rise_samples as f32;
+            self.env = self.peak * t * t * (3.0 - 2.0 * t);
+        } else {
+            self.env = flush_denormal(self.env * self.decay);
+        }
+
+        let white = rand_signed(rng);
+        let rc = 1.0 / (TWO_PI * 38.0);
+        let dt = 1.0 / sample_rate.max(1.0);
+        let alpha = dt / (rc + dt);
+        self.lp_a += alpha * (white - self.lp_a);
+        self.lp_b += alpha * (self.lp_a - self.lp_b);
+
+        self.roll_phase.advance(self.roll_hz, sample_rate);
+        let roll = 0.8 + 0.2 * M::sin(self.roll_phase.radians());
+
+        self.lp_b * 18.0 * roll * self.env
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct SurfState {
+    wave_phase: Phase,
+    wave_hz: f32,
+    slap_env: f32,
+    slap_hp: f32,
+    thump_phase: Phase,
+    thump_hz: f32,
+    sweep_env: f32,
+    sweep_hz: f32,
+    sweep_low: f32,
+    sweep_band: f32,
+    wash_lp: f32,
+}
+
+impl SurfState {
+    fn new() -> Self {
+        Self {
+            wave_phase: Phase::default(),
+            wave_hz: 0.15,
+            slap_env: 0.0,
+            slap_hp: 0.0,
+            thump_phase: Phase::default(),
+            thump_hz: 70.0,
+            sweep_env: 0.0,
+            sweep_hz: 3000.0,
+            sweep_low: 0.0,
+            sweep_band: 0.0,
+            wash_lp: 0.0,
+        }
+    }
+
+    #[inline]
+    fn trigger_slap(&mut self, strength: f32, rng: &mut u32) {
+        let r = (xorshift32(rng) as f32) / u32::MAX as f32;
+        self.slap_env = (self.slap_env + strength * (0.6 + 0.4 * r)).min(1.0);
+        self.thump_hz = 55.0 + 40.0 * r;
+        self.thump_phase = Phase::default();
+    }
+
+    #[inline]
+    fn trigger_sweep(&mut self, strength: f32, rng: &mut u32) {
+        let r = (xorshift32(rng) as f32) / u32::MAX as f32;
+        self.sweep_env = (self.sweep_env + strength * (0.5 + 0.5 * r)).min(1.0);
+        self.sweep_hz = 2400.0 + 1600.0 * r;
+    }
+
+    // Intensity follows sea state and falls off quickly below the surface, so the layer
+    // dominates at periscope depth and vanishes once the boat goes deep.
+    #[inline]
+    // Takes the slap's and the bubble sweep's per-sample decay factors, then the wash's
+    // low-pass and the slap's high-pass corner coefficients, after the rate.
+    fn tick<M: Math>(
+        &mut self,
+        sample_rate: f32,
+        (slap_decay, sweep_decay): (f32, f32),
+        (wash_lp, slap_lp): (f32, f32),
+        bio_rate: f32,
+        env: &Environment,
+        rng: &mut u32,
+    ) -> f32 {
+        let sea = clamp(env.sea_state / 6.0, 0.0, 1.5);
+        let depth_factor = (-env.listener_depth.max(0.0) / 25.0).exp();
+        let agitation = sea * sea.sqrt() * depth_factor;
+        if agitation < 0.0001 {
+            self.slap_env = 0.0;
+            self.sweep_env = 0.0;
+            return 0.0;
+        }
+
+        // Swell period stretches from ~5 s in light seas to ~10 s in heavy weather.
+        self.wave_hz = 0.2 - 0.1 * clamp(sea, 0.0, 1.0);
+        if self.wave_phase.advance(self.wave_hz, sample_rate) {
+            self.trigger_slap(agitation, rng);
+            let r = (xorshift32(rng) as f32) / u32::MAX as f32;
+            if r < 0.3 + 0.6 * sea.min(1.0) {
+                self.trigger_sweep(agitation, rng);
+            }
+        }
+        // Chop slaps land about once a second at full sea and rate, whatever the sample rate.
+        let chop = (xorshift32(rng) as f32) / u32::MAX as f32;
+        if chop < (0.96 + 9.6 * bio_rate) * sea / sample_rate {
+            self.trigger_slap(agitation * 0.6, rng);
+        }
+
+        let white = rand_signed(rng);
+        self.wash_lp += wash_lp * (white - self.wash_lp);
+        let swell = 0.5 + 0.5 * M::sin(self.wave_phase.radians());
+        let wash = self.wash_lp * 4.0 * (0.4 + 0.6 * swell);
+
+        let mut slap = 0.0;
+        if self.slap_env > 0.0001 {
+            self.slap_hp += slap_lp * (white - self.slap_hp);
+            self.thump_phase.advance(self.thump_hz, sample_rate);
+            let thump = M::sin(self.thump_phase.radians());
+            slap = ((white - self.slap_hp) * 0.7 + thump * 0.5) * self.slap_env;
+            self.slap_env *= slap_decay;
+        }
+
+        let mut sweep = 0.0;
+        if self.sweep_env > 0.0001 {
+            // Bubble cloud driven down by a breaking crest: resonance glides downward.
+            self.sweep_hz = (self.sweep_hz * (1.0 - 2.2 / sample_rate)).max(300.0);
+            let f = 2.0 * M::sin(PI * self.sweep_hz.min(sample_rate / 6.0) / sample_rate);
+            self.sweep_low += f * self.sweep_band;
+            let high = white - self.sweep_low - 0.35 * self.sweep_band;
+            self.sweep_band += f * high;
+            sweep = self.sweep_band * self.sweep_env * 0.6;
+            self.sweep_env *= sweep_decay;
+        }
+
+        (wash * 0.12 + slap * 0.55 + sweep) * agitation
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
 struct BioState {
     bio_type: BioType,
     prev_type: BioType,
@@ -1332,10 +2260,15 @@ struct BioState {
     social_call: SocialCallState,
     rotor: RotorState,
     noise_field: NoiseFieldState,
+    vent: VentState,
+    t_phase: TPhaseState,
+    surf: SurfState,
+    low_rate: LowRate,
+    rng: u32,
 }
 
 impl BioState {
-    fn new() -> Self {
+    fn new(seed: u32) -> Self {
         Self {
             bio_type: BioType::Chirp,
             prev_type: BioType::Chirp,
@@ -1352,140 +2285,1384 @@ impl BioState {
             social_call: SocialCallState::new(),
             rotor: RotorState::new(),
             noise_field: NoiseFieldState::new(),
+            vent: VentState::new(),
+            t_phase: TPhaseState::new(),
+            surf: SurfState::new(),
+            low_rate: LowRate::default(),
+            rng: seed,
         }
     }
 
+    fn rescale(&mut self, ratio: f32) {
+        self.chirp.rescale(ratio);
+        self.snapping_shrimp.rescale(ratio);
+        self.dolphin_whistle.rescale(ratio);
+        self.echolocation_click.rescale(ratio);
+        self.humpback_song.rescale(ratio);
+        self.low_call.rescale(ratio);
+        self.click_train.rescale(ratio);
+        self.social_call.rescale(ratio);
+        self.vent.rescale(ratio);
+        self.t_phase.rescale(ratio);
+    }
+
+    // A switch that interrupts a crossfade cuts the type it was fading out, so that one is
+    // finished there and then.
     #[inline]
     fn set_type(&mut self, next: BioType) {
         if next == self.bio_type {
             return;
         }
+        if self.xfade < 1.0 && self.prev_type != next {
+            self.finish(self.prev_type);
+        }
         self.prev_type = self.bio_type;
         self.bio_type = next;
         self.xfade = 0.0;
     }
 
+    // Index of the generator state a type renders through; several types share one.
+    fn generator(mode: BioType) -> usize {
+        match mode {
+            BioType::Chirp => 0,
+            BioType::SnappingShrimp => 1,
+            BioType::WhaleMoan => 2,
+            BioType::DolphinWhistle => 3,
+            BioType::EcholocationClick => 4,
+            BioType::HumpbackSong => 5,
+            BioType::BlueWhale | BioType::FinWhale | BioType::MinkePulse | BioType::FishChorus => 6,
+            BioType::SpermWhaleClick => 7,
+            BioType::OrcaCall | BioType::BelugaCall | BioType::HerringSchool | BioType::DolphinSchool => {
+                8
+            }
+            BioType::HelicopterRotor | BioType::FixedWingAircraft | BioType::JetAircraft => 9,
+            BioType::AmbientOcean | BioType::Precipitation | BioType::IceNoise | BioType::GeologicalNoise => {
+                10
+            }
+            BioType::HydrothermalVent => 11,
+            BioType::SeismicTPhase => 12,
+            BioType::SurfAgitation => 13,
+        }
+    }
+
+    // Drops whatever a faded-out type had under way (a half-sung unit, a scheduled snap), so
+    // switching back to it starts fresh rather than resuming mid-event. A generator the current
+    // type also renders through is left alone.
+    fn finish(&mut self, mode: BioType) {
+        let generator = Self::generator(mode);
+        if generator == Self::generator(self.bio_type) {
+            return;
+        }
+        match generator {
+            0 => self.chirp = ChirpState::new(),
+            1 => self.snapping_shrimp = SnappingShrimpState::new(),
+            2 => self.whale_moan = WhaleMoanState::new(),
+            3 => self.dolphin_whistle = DolphinWhistleState::new(),
+            4 => self.echolocation_click = EcholocationClickState::new(),
+            5 => self.humpback_song = HumpbackSongState::new(),
+            6 => self.low_call = LowCallState::new(),
+            7 => self.click_train = ClickTrainState::new(),
+            8 => self.social_call = SocialCallState::new(),
+            9 => self.rotor = RotorState::new(),
+            10 => self.noise_field = NoiseFieldState::new(),
+            11 => self.vent = VentState::new(),
+            12 => self.t_phase = TPhaseState::new(),
+            _ => self.surf = SurfState::new(),
+        }
+    }
+
     #[inline]
     fn set_rate(&mut self, value: f32) {
         self.bio_rate = clamp(value, 0.0, 1.0);
     }
 
-    #[inline]
-    fn tick_mode(&mut self, mode: BioType, sample_rate: f32, rpm: f32, rng: &mut u32) -> f32 {
+    // Runs one generator across `out`, skipping the samples whose gate is closed. The mode is
+    // matched once for the whole run rather than per sample. The event-driven generators run
+    // their schedules at control rate and render a stretch at a time; the low-frequency ones
+    // compute their waveforms every `factor` samples.
+    #[allow(clippy::too_many_arguments)]
+    fn render_mode<M: Math>(
+        &mut self,
+        mode: BioType,
+        out: &mut [f32],
+        sample_rate: f32,
+        rpm: &[f32],
+        gate: &[f32],
+        env: &Environment,
+        factor: u32,
+    ) {
+        let rate = self.bio_rate;
+        let rng = &mut self.rng;
         match mode {
-            BioType::Chirp => self.chirp.tick(sample_rate, rpm, self.bio_rate, rng),
-            BioType::SnappingShrimp => self.snapping_shrimp.tick(sample_rate, self.bio_rate, rng),
-            BioType::WhaleMoan => self.whale_moan.tick(sample_rate, self.bio_rate, rng),
-            BioType::DolphinWhistle => self.dolphin_whistle.tick(sample_rate, self.bio_rate, rng),
-            BioType::EcholocationClick => self.echolocation_click.tick(sample_rate, self.bio_rate, rng),
-            BioType::HumpbackSong => self.humpback_song.tick(sample_rate, self.bio_rate, rng),
+            BioType::Chirp => gated_runs(out, gate, |run, i| {
+                self.chirp.render::<M>(run, sample_rate, &rpm[i..], rate, rng)
+            }),
+            BioType::SnappingShrimp => gated_runs(out, gate, |run, _| {
+                self.snapping_shrimp.render::<M>(run, sample_rate, rate, rng)
+            }),
+            BioType::WhaleMoan => {
+                let drift = smoothing(26.0, sample_rate);
+                gated(out, gate, |_| {
+                    let due = self.low_rate.is_due();
+                    let value = self.whale_moan.tick::<M>(sample_rate, drift, rate, due, rng);
+                    self.low_rate.next(value, factor)
+                })
+            }
+            BioType::DolphinWhistle => gated_runs(out, gate, |run, _| {
+                self.dolphin_whistle.render::<M>(run, sample_rate, rate, rng)
+            }),
+            BioType::EcholocationClick => gated_runs(out, gate, |run, _| {
+                self.echolocation_click.render::<M>(run, sample_rate, rate, rng)
+            }),
+            BioType::HumpbackSong => gated_runs(out, gate, |run, _| {
+                self.humpback_song.render::<M>(run, sample_rate, rate, rng)
+            }),
             BioType::BlueWhale | BioType::FinWhale | BioType::MinkePulse | BioType::FishChorus => {
-                self.low_call.tick(mode, sample_rate, self.bio_rate, rng)
+                // Minke pulses carry noise, and fish choruses reach too high for the low rate.
+                let factor = match mode {
+                    BioType::BlueWhale | BioType::FinWhale => factor,
+                    _ => 1,
+                };
+                let low_rate = &mut self.low_rate;
+                gated_runs(out, gate, |run, _| {
+                    self.low_call.render::<M>(mode, run, sample_rate, rate, low_rate, factor, rng)
+                })
             }
-            BioType::SpermWhaleClick => self.click_train.tick(mode, sample_rate, self.bio_rate, rng),
+            BioType::SpermWhaleClick => gated_runs(out, gate, |run, _| {
+                self.click_train.render::<M>(mode, run, sample_rate, rate, rng)
+            }),
             BioType::OrcaCall | BioType::BelugaCall | BioType::HerringSchool | BioType::DolphinSchool => {
-                self.social_call.tick(mode, sample_rate, self.bio_rate, rng)
+                gated_runs(out, gate, |run, _| {
+                    self.social_call.render::<M>(mode, run, sample_rate, rate, rng)
+                })
             }
             BioType::HelicopterRotor | BioType::FixedWingAircraft | BioType::JetAircraft => {
-                self.rotor.tick(mode, sample_rate, self.bio_rate, rpm, rng)
+                // Blade slap decay, and the corner under the airframe's broadband hiss.
+                let shape = (decay(0.51, sample_rate), lowpass(490.0, sample_rate));
+                gated(out, gate, |i| {
+                    self.rotor.tick::<M>(mode, sample_rate, shape, rate, rpm[i], rng)
+                })
             }
             BioType::AmbientOcean | BioType::Precipitation | BioType::IceNoise | BioType::GeologicalNoise => {
-                self.noise_field.tick(mode, sample_rate, self.bio_rate, rng)
+                // Geological rumbles ring longest and rain spatters shortest.
+                let burst_ms = match mode {
+                    BioType::GeologicalNoise => 6.9,
+                    BioType::IceNoise => 1.4,
+                    BioType::Precipitation => 0.34,
+                    _ => 0.58,
+                };
+                // Bursts arrive at 19 to 139 a second, as a chance per sample at this rate.
+                let chance = (19.2 + 120.0 * rate) / sample_rate;
+                let timing = (smoothing(21.0, sample_rate), decay(burst_ms, sample_rate), chance);
+                // Bands split at 155 Hz and 1 kHz.
+                let bands = (lowpass(155.0, sample_rate), lowpass(1040.0, sample_rate));
+                gated(out, gate, |_| self.noise_field.tick::<M>(mode, timing, bands, rate, rng))
+            }
+            BioType::HydrothermalVent => {
+                // Bubble ring, hiss corner and the rumble's two-pole corner.
+                let shape = (
+                    decay(8.3, sample_rate),
+                    lowpass(4100.0, sample_rate),
+                    lowpass(30.0, sample_rate),
+                );
+                gated(out, gate, |_| self.vent.tick::<M>(sample_rate, shape, rate, rng))
+            }
+            BioType::SeismicTPhase => gated_runs(out, gate, |run, _| {
+                self.t_phase.render::<M>(run, sample_rate, rate, rng)
+            }),
+            BioType::SurfAgitation => {
+                let decays = (decay(35.0, sample_rate), decay(139.0, sample_rate));
+                let filters = (lowpass(77.0, sample_rate), lowpass(2550.0, sample_rate));
+                gated(out, gate, |_| {
+                    self.surf.tick::<M>(sample_rate, decays, filters, rate, env, rng)
+                })
             }
         }
     }
 
+    // Fires the active generator's next event immediately. Only sources with discrete events
+    // can be scheduled this way; continuous fields return false.
     #[inline]
-    fn tick(&mut self, sample_rate: f32, rpm: f32, rng: &mut u32) -> f32 {
-        if self.xfade < 1.0 {
-            let a = self.tick_mode(self.prev_type, sample_rate, rpm, rng);
-            let b = self.tick_mode(self.bio_type, sample_rate, rpm, rng);
-            let out = a * (1.0 - self.xfade) + b * self.xfade;
+    fn trigger_event(&mut self, sample_rate: f32, rpm: f32) -> bool {
+        let rng = &mut self.rng;
+        match self.bio_type {
+            BioType::Chirp => self.chirp.trigger_click(rpm, rng),
+            BioType::SnappingShrimp => self.snapping_shrimp.trigger_snap(sample_rate, rng),
+            BioType::EcholocationClick => {
+                self.echolocation_click.trigger_click(sample_rate, self.bio_rate, rng)
+            }
+            BioType::DolphinWhistle => self.dolphin_whistle.trigger_whistle(rng, self.bio_rate),
+            BioType::HumpbackSong => self.humpback_song.trigger_unit(sample_rate, self.bio_rate, rng),
+            BioType::HydrothermalVent => self.vent.trigger_bubble(rng),
+            BioType::SeismicTPhase => self.t_phase.trigger(sample_rate, rng),
+            BioType::SurfAgitation => self.surf.trigger_slap(1.0, rng),
+            _ => return false,
+        }
+        true
+    }
+
+    // Fills `out` with the biologic layer, where `gate` is open. A crossfade between types
+    // runs sample by sample, at the full rate since both generators are sounding; whatever of
+    // the run is left after it goes through one generator. The two sources are unrelated, so
+    // the crossfade follows a quarter cosine and sine to hold their summed power steady.
+    fn render<M: Math>(
+        &mut self,
+        out: &mut [f32],
+        sample_rate: f32,
+        rpm: &[f32],
+        gate: &[f32],
+        env: &Environment,
+        factor: u32,
+    ) {
+        let mut start = 0;
+        while self.xfade < 1.0 && start < out.len() {
+            let i = start;
+            start += 1;
+            if gate[i] <= 0.0 {
+                out[i] = 0.0;
+                continue;
+            }
+            let (mut a, mut b) = ([0.0], [0.0]);
+            let rpm = &rpm[i..=i];
+            self.render_mode::<M>(self.prev_type, &mut a, sample_rate, rpm, &[1.0], env, 1);
+            self.render_mode::<M>(self.bio_type, &mut b, sample_rate, rpm, &[1.0], env, 1);
+            let angle = self.xfade * core::f32::consts::FRAC_PI_2;
+            out[i] = a[0] * M::cos(angle) + b[0] * M::sin(angle);
             let step = 1.0 / (sample_rate * 0.015);
             self.xfade = (self.xfade + step).min(1.0);
-            out
+            if self.xfade >= 1.0 {
+                self.finish(self.prev_type);
+            }
+        }
+        let (out, rpm, gate) = (&mut out[start..], &rpm[start..], &gate[start..]);
+        self.render_mode::<M>(self.bio_type, out, sample_rate, rpm, gate, env, factor);
+    }
+}
+
+// Fills `out` from `tick` wherever `gate` is open and with silence elsewhere; a generator
+// whose gate is closed doesn't move.
+#[inline]
+fn gated(out: &mut [f32], gate: &[f32], mut tick: impl FnMut(usize) -> f32) {
+    for (i, (sample, open)) in out.iter_mut().zip(gate).enumerate() {
+        *sample = if *open > 0.0 { tick(i) } else { 0.0 };
+    }
+}
+
+// As gated, for generators that render a stretch at a time: `render` gets each run of samples
+// whose gate is open, with the index of its first sample.
+#[inline]
+fn gated_runs(out: &mut [f32], gate: &[f32], mut render: impl FnMut(&mut [f32], usize)) {
+    let mut start = 0;
+    while start < out.len() {
+        let open = gate[start] > 0.0;
+        let len = gate[start..out.len()]
+            .iter()
+            .position(|g| (*g > 0.0) != open)
+            .unwrap_or(out.len() - start);
+        let run = &mut out[start..start + len];
+        if open {
+            render(run, start);
         } else {
-            self.tick_mode(self.bio_type, sample_rate, rpm, rng)
+            run.fill(0.0);
         }
+        start += len;
     }
 }
 
-#[derive(Clone, Copy)]
+// Longest stretch an event generator runs between looks at its schedule, so one that falls
+// silent part way through a stretch is skipped from the next.
+const CONTROL_FRAMES: usize = 32;
+
+// Takes the next control stretch off an event countdown: at most CONTROL_FRAMES and `left`
+// samples, ending where the countdown runs out so the event fires at the start of the next
+// stretch. When it `fired` at the start of this one, that sample doesn't count down.
+#[inline]
+fn control_stretch(countdown: &mut u32, fired: bool, left: usize) -> usize {
+    let due = *countdown as usize + fired as usize;
+    let len = left.min(CONTROL_FRAMES).min(due);
+    *countdown -= (len - fired as usize) as u32;
+    len
+}
+
+// One instance's departure from its preset under Param::Variation. Three fixed draws per
+// voice, taken from its seed, set clock factors for the tonal (engine, cavitation) and event
+// (biologic) generators and a level factor. The first draw feeds all three, so an instance
+// that runs low also tends to run a little louder and slower, like a bigger hull would.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct Humanize {
+    amount: f32,
+    draws: [f32; 3],
+    tonal: f32,
+    timing: f32,
+    level: f32,
+}
+
+impl Humanize {
+    fn new(seed: u32) -> Self {
+        let mut humanize = Self {
+            amount: 0.0,
+            draws: [0.0; 3],
+            tonal: 1.0,
+            timing: 1.0,
+            level: 1.0,
+        };
+        humanize.reseed(seed);
+        humanize
+    }
+
+    fn reseed(&mut self, seed: u32) {
+        let mut rng = (seed ^ 0x3c6e_f372) | 1;
+        self.draws = [(); 3].map(|_| rand_signed(&mut rng));
+        self.set(self.amount);
+    }
+
+    // At full variation: tonal lines within 4%, event timing within 15%, level within 1.5 dB.
+    fn set(&mut self, amount: f32) {
+        let [shared, level, timing] = self.draws;
+        self.amount = amount;
+        self.tonal = 1.0 + 0.04 * amount * shared;
+        self.timing = 1.0 + 0.15 * amount * 0.5 * (shared + timing);
+        self.level = 10.0f32.powf(1.5 * amount * (0.6 * level - 0.4 * shared) / 20.0);
+    }
+}
+
+// Per-sample controls and generator outputs for a run of one voice, reused by each voice in
+// turn. Every lane holds a full block.
+#[derive(Default)]
+struct VoiceLanes {
+    rpm: Vec<f32>,
+    shaft_rate: Vec<f32>,
+    load: Vec<f32>,
+    cavitation: Vec<f32>,
+    gates: [Vec<f32>; COMPONENT_COUNT],
+    shaft_phase: Vec<f32>,
+    blade_phase: Vec<f32>,
+    engine: Vec<f32>,
+    cav: Vec<f32>,
+    bio: Vec<f32>,
+}
+
+impl VoiceLanes {
+    fn new(max_frames: usize) -> Self {
+        let lane = || vec![0.0; max_frames];
+        Self {
+            rpm: lane(),
+            shaft_rate: lane(),
+            load: lane(),
+            cavitation: lane(),
+            gates: [lane(), lane(), lane()],
+            shaft_phase: lane(),
+            blade_phase: lane(),
+            engine: lane(),
+            cav: lane(),
+            bio: lane(),
+        }
+    }
+
+    fn heap_bytes(&self) -> usize {
+        let lanes = [&self.rpm, &self.shaft_rate, &self.load, &self.cavitation];
+        let outputs = [&self.shaft_phase, &self.blade_phase, &self.engine, &self.cav, &self.bio];
+        lanes.into_iter().chain(&self.gates).chain(outputs).map(vec_bytes).sum()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 struct Voice {
     active: bool,
-    gain: f32,
-    engine_mix: f32,
-    cav_mix: f32,
-    bio_mix: f32,
-    cavitation_level: f32,
+    gain: Ramp,
+    engine_mix: Ramp,
+    cav_mix: Ramp,
+    bio_mix: Ramp,
+    cavitation_level: Ramp,
+    // Send level into each return bus; the reverb send is Param::ReverbSend.
+    sends: [Ramp; BUS_COUNT],
+    rpm: Ramp,
+    load: Ramp,
+    // Only drives the engine while an explicit shaft rate is set (or while gliding back).
+    shaft_rate: Ramp,
+    // True when the shaft rate follows RPM / 60 rather than shaft_rate.
+    shaft_derived: bool,
+    // Fastest the RPM may climb and fall, in RPM per sample; 0 leaves that direction to the
+    // parameter ramp alone.
+    rpm_slew: [f32; 2],
     rng: u32,
     engine: EngineState,
     cav: CavState,
     bio: BioState,
+    source_depth: f32,
+    propagation: VoicePropagation,
+    multipath: Multipath,
+    panner: Panner,
+    ring: RingPanner,
+    binaural: Binaural,
+    // Smoothed hull-receiver directivity gain for this voice's bearing.
+    receive_gain: f32,
+    group: usize,
+    // Smoothed level of this voice's group.
+    group_gain: f32,
+    bearing_noise: BearingNoise,
+    array_delay: ArrayDelay,
+    track: Track,
+    echo: Echo,
+    // Per-component on/off envelopes. A component whose gate has closed is not rendered at
+    // all, and picks up from its frozen state when reopened.
+    gates: [Ramp; COMPONENT_COUNT],
+    ducker: Ducker,
+    humanize: Humanize,
+    // Output envelope for add/remove. A removed voice is inactive to the API at once but keeps
+    // rendering until this reaches zero.
+    fade: Ramp,
+    // Set while the voice is culled as inaudible, with the fade that takes it out and brings it
+    // back. Once that fade reaches zero the voice isn't rendered at all.
+    culled: bool,
+    cull: Ramp,
+    // Active-sonar target strength (dB) and hull length (m) shaping this voice's echoes.
+    target_strength: f32,
+    target_length: f32,
+    // Received-to-emitted frequency ratio; 1.0 when the voice isn't moving relative to us.
+    doppler: f32,
+    modulation: Modulation,
+    // Isolated copy of this voice's last block as the receiver hears it; empty when untapped.
+    tap: Vec<f32>,
+    meter: MeterState,
+    // External audio written by the host for the next block, mixed in ahead of the voice's gain
+    // and consumed as it renders; empty when the input port is off.
+    input: Vec<f32>,
+    // Higher outranks lower when add_voice has to steal a voice.
+    priority: u32,
+    // Graph clock when the voice starts (or started) rendering.
+    started: u64,
+    // Samples left before a scheduled voice starts; its fade-in begins when this runs out.
+    start_delay: u32,
+    // Scenario cues within the current block: frames until a bio event fires, and until the
+    // fade-out of a removed voice begins (with the fade length).
+    bio_cue: Option<u32>,
+    stop_cue: Option<(u32, u32)>,
+    // Host's own handles for the voice; 0 and empty until set.
+    tag: u64,
+    label: String,
+    // Last preset applied, kept for the host to read back.
+    preset: Option<u32>,
+    // Set when the voice's last block came out non-finite and its state was rebuilt; the graph
+    // reports it once the block is mixed.
+    #[serde(skip)]
+    quarantined: bool,
 }
 
 impl Voice {
     fn new(seed: u32) -> Self {
+        let [echo_seed, engine_seed, cav_seed, bio_seed, bearing_seed, mod_seed, variation_seed] =
+            voice_seeds(seed);
         Self {
             active: true,
-            gain: 1.0,
-            engine_mix: 1.0,
-            cav_mix: 0.55,
-            bio_mix: 0.25,
-            cavitation_level: 0.35,
-            rng: seed,
-            engine: EngineState::new(),
-            cav: CavState::new(),
-            bio: BioState::new(),
+            gain: Ramp::new(1.0),
+            engine_mix: Ramp::new(1.0),
+            cav_mix: Ramp::new(0.55),
+            bio_mix: Ramp::new(0.25),
+            cavitation_level: Ramp::new(0.35),
+            sends: [Ramp::new(0.0); BUS_COUNT],
+            rpm: Ramp::new(0.0),
+            load: Ramp::new(0.45),
+            shaft_rate: Ramp::new(0.0),
+            shaft_derived: true,
+            rpm_slew: [0.0; 2],
+            rng: echo_seed,
+            engine: EngineState::new(engine_seed),
+            cav: CavState::new(cav_seed),
+            bio: BioState::new(bio_seed),
+            source_depth: 50.0,
+            propagation: VoicePropagation::new(),
+            multipath: Multipath::new(),
+            panner: Panner::new(),
+            ring: RingPanner::new(),
+            binaural: Binaural::new(),
+            receive_gain: 1.0,
+            group: 0,
+            group_gain: 1.0,
+            bearing_noise: BearingNoise::new(bearing_seed),
+            array_delay: ArrayDelay::new(),
+            track: Track::new(),
+            echo: Echo::new(),
+            gates: [Ramp::new(1.0); COMPONENT_COUNT],
+            ducker: Ducker::new(),
+            humanize: Humanize::new(variation_seed),
+            fade: Ramp::new(0.0),
+            culled: false,
+            cull: Ramp::new(1.0),
+            target_strength: 10.0,
+            target_length: 80.0,
+            doppler: 1.0,
+            modulation: Modulation::new(mod_seed),
+            tap: Vec::new(),
+            meter: MeterState::new(),
+            input: Vec::new(),
+            priority: 0,
+            started: 0,
+            start_delay: 0,
+            bio_cue: None,
+            stop_cue: None,
+            tag: 0,
+            label: String::new(),
+            preset: None,
+            quarantined: false,
         }
     }
 
+    // Rebuilds the generator and filter state after a block came out non-finite, keeping every
+    // setting, so one bad value costs a block of silence rather than the voice.
+    fn quarantine(&mut self) {
+        let engine = &self.engine;
+        self.engine = EngineState {
+            blades: engine.blades,
+            load: engine.load,
+            rpm_jitter: engine.rpm_jitter,
+            class_profile: engine.class_profile,
+            ..EngineState::new(engine.rng)
+        };
+        self.cav = CavState::new(self.cav.rng);
+        let bio = &self.bio;
+        self.bio = BioState {
+            bio_type: bio.bio_type,
+            prev_type: bio.bio_type,
+            bio_rate: bio.bio_rate,
+            ..BioState::new(bio.rng)
+        };
+        self.propagation.clear();
+        self.multipath.clear();
+        self.ducker.clear();
+        self.quarantined = true;
+    }
+
+    fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.tap)
+            + vec_bytes(&self.input)
+            + self.label.capacity()
+            + self.multipath.heap_bytes()
+            + self.array_delay.heap_bytes()
+    }
+
+    // Rough level as heard, for picking a voice to steal: gain, propagation loss, receiver
+    // directivity and group gain.
+    fn audibility(&self) -> f32 {
+        self.gain.value * self.propagation.gain() * self.receive_gain * self.group_gain
+    }
+
+    // Still audible: either active or fading out after removal.
     #[inline]
-    fn sample(&mut self, sample_rate: f32) -> f32 {
-        if !self.active {
-            return 0.0;
-        }
+    fn is_sounding(&self) -> bool {
+        self.active || self.fade.value > 0.0
+    }
 
-        let e = self.engine.tick(sample_rate, &mut self.rng);
-        let c = self
-            .cav
-            .tick(
-                sample_rate,
-                self.engine.current_rpm,
-                self.engine.shaft_phase,
-                self.engine.blade_phase,
-                self.engine.blades,
-                self.engine.load,
-                self.cavitation_level,
-                self.engine.class_profile,
-                &mut self.rng,
-            );
-        let b = self
-            .bio
-            .tick(sample_rate, self.engine.current_rpm, &mut self.rng);
+    // Most this voice can put into the mix judging by its gains alone: output gain, component
+    // mixes and gates, propagation loss with any bounces, receiver directivity and group level.
+    // Each counts at the larger of its current value and target, so a voice on its way back up
+    // is caught as its ramps start. The generators themselves peak around full scale.
+    fn level_bound(&self, receive: f32, group: f32) -> f32 {
+        let peak = |ramp: &Ramp| ramp.value.max(ramp.target);
+        let mix = [&self.engine_mix, &self.cav_mix, &self.bio_mix]
+            .into_iter()
+            .zip(&self.gates)
+            .map(|(mix, gate)| peak(mix) * peak(gate))
+            .fold(0.0, f32::max);
+        let propagation = self.propagation.gain().max(self.propagation.target_gain());
+        peak(&self.gain) * peak(&self.fade) * mix * self.humanize.level
+            * propagation
+            * self.multipath.peak_gain()
+            * self.receive_gain.max(receive)
+            * self.group_gain.max(group)
+    }
 
-        (e * self.engine_mix + c * self.cav_mix + b * self.bio_mix) * self.gain
+    // Culls or restores the voice ahead of a block: it goes once its bound drops below
+    // `threshold` (0 when culling is off) and comes back once the bound is CULL_HYSTERESIS over
+    // it, fading over `fade` samples either way. Host input or an echo on its way keep it in.
+    fn update_cull(&mut self, bound: f32, threshold: f32, fade: u32) {
+        let limit = if self.culled { threshold * CULL_HYSTERESIS } else { threshold };
+        let culled = bound < limit && self.input.is_empty() && !self.echo.is_armed();
+        if culled != self.culled {
+            self.culled = culled;
+            self.cull.set(if culled { 0.0 } else { 1.0 }, fade);
+        }
     }
-}
 
-#[wasm_bindgen]
-pub struct DspGraph {
-    sample_rate: f32,
-    max_frames: usize,
-    last_frames: usize,
-    voices: Vec<Voice>,
+    // Culled and faded all the way out, so render() can be skipped.
+    fn is_silenced(&self) -> bool {
+        self.culled && self.cull.value == 0.0
+    }
+
+    // Stands in for render() while the voice is silenced: the block's automation still lands
+    // and its ramps, cues and start delay move on by `n` frames, but nothing is generated. A
+    // bio cue falling in the block is dropped, and a removed voice ends at once.
+    fn skip<'a>(&mut self, n: usize, events: impl Iterator<Item = (usize, &'a ParamEvent)>) {
+        for (_, event) in events {
+            self.set_value(event.param, event.value, event.ramp);
+        }
+        let n = n as u32;
+        let delay = self.start_delay.min(n);
+        self.start_delay -= delay;
+        let live = n - delay;
+        let ramps = [
+            &mut self.gain,
+            &mut self.engine_mix,
+            &mut self.cav_mix,
+            &mut self.bio_mix,
+            &mut self.cavitation_level,
+            &mut self.rpm,
+            &mut self.load,
+            &mut self.shaft_rate,
+            &mut self.fade,
+        ];
+        for ramp in ramps.into_iter().chain(&mut self.gates).chain(&mut self.sends) {
+            ramp.advance(live);
+        }
+        self.engine.current_rpm = self.rpm.value;
+        self.engine.current_shaft_rate = if self.shaft_derived && self.shaft_rate.is_settled() {
+            self.rpm.value / 60.0
+        } else {
+            self.shaft_rate.value
+        };
+        self.bio_cue = self.bio_cue.and_then(|left| left.checked_sub(n));
+        if !self.active {
+            self.stop_cue = None;
+            self.fade.hold(0.0);
+        }
+    }
+
+    // Carries every sample-counted timer, ramp and delay over to a new sample rate. Phases and
+    // per-block coefficients are rate-independent or recomputed on the next block.
+    fn rescale(&mut self, ratio: f32) {
+        for ramp in [
+            &mut self.gain,
+            &mut self.engine_mix,
+            &mut self.cav_mix,
+            &mut self.bio_mix,
+            &mut self.cavitation_level,
+            &mut self.rpm,
+            &mut self.load,
+            &mut self.shaft_rate,
+            &mut self.fade,
+            &mut self.cull,
+        ] {
+            ramp.rescale(ratio);
+        }
+        for gate in &mut self.gates {
+            gate.rescale(ratio);
+        }
+        for send in &mut self.sends {
+            send.rescale(ratio);
+        }
+        self.rpm_slew = self.rpm_slew.map(|slew| slew / ratio);
+        self.start_delay = scale_samples(self.start_delay, ratio);
+        self.bio.rescale(ratio);
+        self.echo.rescale(ratio);
+        self.multipath.rescale(ratio);
+        self.array_delay.rescale(ratio);
+    }
+
+    fn reseed(&mut self, seed: u32) {
+        let [echo, engine, cav, bio, bearing, modulation, variation] = voice_seeds(seed);
+        [self.rng, self.engine.rng, self.cav.rng, self.bio.rng] = [echo, engine, cav, bio];
+        self.bearing_noise.reseed(bearing);
+        self.modulation.reseed(modulation);
+        self.humanize.reseed(variation);
+    }
+
+    // Stores an already clamped value; `ramp` is the ramp length in samples for the
+    // parameters that ramp.
+    fn apply_param(&mut self, param: Param, value: f32, ramp: u32) {
+        match param {
+            // A slew limit stretches the ramp until the change runs no faster than it allows.
+            Param::Rpm => {
+                let delta = value - self.rpm.value;
+                let slew = self.rpm_slew[usize::from(delta < 0.0)];
+                let ramp = if slew > 0.0 {
+                    ramp.max((delta.abs() / slew).min(u32::MAX as f32) as u32)
+                } else {
+                    ramp
+                };
+                self.rpm.set(value, ramp);
+                if self.shaft_derived && !self.shaft_rate.is_settled() {
+                    self.shaft_rate.set(value / 60.0, ramp);
+                }
+            }
+            Param::Blades => self.engine.blades = value,
+            Param::Gain => self.gain.set(value, ramp),
+            Param::EngineMix => self.engine_mix.set(value, ramp),
+            Param::CavMix => self.cav_mix.set(value, ramp),
+            Param::BioMix => self.bio_mix.set(value, ramp),
+            Param::BioType => self.bio.set_type(BioType::from_param(value)),
+            Param::BioRate => self.bio.set_rate(value),
+            // Anything at or below 0.01 Hz hands the shaft back to RPM, gliding there first.
+            Param::ShaftRate => {
+                self.shaft_rate.hold(self.engine.current_shaft_rate);
+                self.shaft_derived = value <= 0.01;
+                let target = if self.shaft_derived { self.rpm.target / 60.0 } else { value };
+                self.shaft_rate.set(target, ramp);
+            }
+            Param::Load => self.load.set(value, ramp),
+            Param::RpmJitter => self.engine.rpm_jitter = value,
+            Param::ClassProfile => self.engine.class_profile = value.round() as u32,
+            Param::CavitationLevel => self.cavitation_level.set(value, ramp),
+            Param::Range => self.propagation.range = value,
+            Param::SourceDepth => self.source_depth = value,
+            Param::ReverbSend => self.sends[BUS_REVERB as usize].set(value, ramp),
+            // Degrees clockwise from own-ship's bow.
+            Param::Bearing => self.panner.bearing = value.to_radians(),
+            Param::TargetStrength => self.target_strength = value,
+            Param::TargetLength => self.target_length = value,
+            // RMS bearing error in degrees: slow wander and fast jitter.
+            Param::BearingWander => self.bearing_noise.wander = value.to_radians(),
+            Param::BearingJitter => self.bearing_noise.jitter = value.to_radians(),
+            // Degrees above the horizontal; only the binaural renderer uses it.
+            Param::Elevation => self.binaural.elevation = value.to_radians(),
+            Param::Variation => self.humanize.set(value),
+            Param::GainDb => self.gain.set(db_to_linear(value), ramp),
+            Param::EngineMixDb => self.engine_mix.set(db_to_linear(value), ramp),
+            Param::CavMixDb => self.cav_mix.set(db_to_linear(value), ramp),
+            Param::BioMixDb => self.bio_mix.set(db_to_linear(value), ramp),
+            Param::ReverbSendDb => self.sends[BUS_REVERB as usize].set(db_to_linear(value), ramp),
+        }
+    }
+
+    // Routes a set_param through the modulation base when the parameter is being modulated.
+    // dB variants land on their linear parameter, and so on its base.
+    fn set_value(&mut self, param: Param, value: f32, ramp: u32) {
+        if let Some(linear) = param.as_linear() {
+            let value = linear.spec().apply(db_to_linear(value));
+            return self.set_value(linear, value, ramp);
+        }
+        match &mut self.modulation.base[param as usize] {
+            Some(base) => *base = value,
+            None => self.apply_param(param, value, ramp),
+        }
+    }
+
+    // Advances the modulation sources by one block and ramps every modulated parameter to its
+    // new value across the `frames` about to be rendered.
+    fn modulate(&mut self, dt: f32, frames: u32) {
+        if !self.modulation.is_active() {
+            return;
+        }
+        self.modulation.advance(dt);
+        for spec in &PARAM_SPECS {
+            let Some(base) = self.modulation.base[spec.param as usize] else {
+                continue;
+            };
+            let value = spec.apply(base + self.modulation.offset(spec.param));
+            self.apply_param(spec.param, value, frames);
+        }
+    }
+
+    // 0 while the shaft rate follows RPM, matching what set_param accepts.
+    fn target_shaft_rate(&self) -> f32 {
+        if self.shaft_derived {
+            0.0
+        } else {
+            self.shaft_rate.target
+        }
+    }
+
+    // Fills `out` with this block's output, applying the voice's automation at the frames it
+    // lands on.
+    fn render<'a>(
+        &mut self,
+        out: &mut [f32],
+        events: impl Iterator<Item = (usize, &'a ParamEvent)>,
+        lanes: &mut VoiceLanes,
+        mixing: &VoiceMixing,
+    ) {
+        let mut events = events.peekable();
+        let mut start = 0;
+        while start < out.len() {
+            while let Some((_, event)) = events.next_if(|(at, _)| *at <= start) {
+                self.set_value(event.param, event.value, event.ramp);
+            }
+            let end = events.peek().map_or(out.len(), |(at, _)| (*at).min(out.len()));
+            self.render_span(&mut out[start..end], start, lanes, mixing);
+            start = end;
+        }
+    }
+
+    // Renders frames `index..index + out.len()` of the block, firing scenario cues ahead of
+    // the frame they land on and holding silent through any start delay.
+    fn render_span(
+        &mut self,
+        out: &mut [f32],
+        index: usize,
+        lanes: &mut VoiceLanes,
+        mixing: &VoiceMixing,
+    ) {
+        let sample_rate = mixing.sample_rate;
+        let mut start = 0;
+        while start < out.len() {
+            if !self.is_sounding() {
+                out[start..].fill(0.0);
+                return;
+            }
+            if self.bio_cue == Some(0) {
+                self.bio_cue = None;
+                self.bio.trigger_event(sample_rate, self.engine.current_rpm);
+            }
+            if let Some((0, fade)) = self.stop_cue {
+                self.stop_cue = None;
+                self.fade.set(0.0, fade);
+            }
+            let cues = [self.bio_cue, self.stop_cue.map(|(left, _)| left)];
+            let mut len = (out.len() - start) as u32;
+            for left in cues.into_iter().flatten() {
+                len = len.min(left);
+            }
+            if self.start_delay > 0 {
+                len = len.min(self.start_delay);
+            }
+            self.bio_cue = self.bio_cue.map(|left| left - len);
+            self.stop_cue = self.stop_cue.map(|(left, fade)| (left - len, fade));
+
+            let run = start..start + len as usize;
+            let at = index + start;
+            if self.start_delay > 0 {
+                // Input written for frames the voice sits out is dropped.
+                self.start_delay -= len;
+                out[run].fill(0.0);
+                if let Some(input) = self.input.get_mut(at..at + len as usize) {
+                    input.fill(0.0);
+                }
+            } else {
+                self.render_run(&mut out[run], at, lanes, mixing);
+            }
+            start += len as usize;
+        }
+    }
+
+    // Runs each generator across the first `n` frames of the lanes.
+    fn generate<M: Math>(
+        &mut self,
+        n: usize,
+        sample_rate: f32,
+        lanes: &mut VoiceLanes,
+        mixing: &VoiceMixing,
+    ) {
+        let (env, profile) = (mixing.environment, mixing.profile);
+        let tonal_rate = sample_rate / self.humanize.tonal;
+        // The engine also turns the shaft cavitation locks to, so its phases keep advancing
+        // while cavitation is on, even with the engine itself off.
+        let start = profile.start();
+        self.engine
+            .render::<M>(tonal_rate, lanes, n, mixing.low_rate, mixing.band_limited_engines);
+        profile.record(ProfileStage::Engine, start, n);
+        let start = profile.start();
+        self.cav.render::<M>(tonal_rate, &self.engine, lanes, n);
+        profile.record(ProfileStage::Cavitation, start, n);
+        let start = profile.start();
+        self.bio.render::<M>(
+            &mut lanes.bio[..n],
+            sample_rate / self.humanize.timing,
+            &lanes.rpm[..n],
+            &lanes.gates[Component::Bio as usize][..n],
+            env,
+            mixing.low_rate,
+        );
+        profile.record(ProfileStage::Bio, start, n);
+    }
+
+    // Renders a stretch with no cue or start delay inside it: the control lanes first, then
+    // each generator across the whole stretch, then the per-sample mix through propagation.
+    fn render_run(
+        &mut self,
+        out: &mut [f32],
+        index: usize,
+        lanes: &mut VoiceLanes,
+        mixing: &VoiceMixing,
+    ) {
+        let n = out.len();
+        for i in 0..n {
+            let rpm = self.rpm.next();
+            lanes.rpm[i] = rpm;
+            lanes.shaft_rate[i] = if self.shaft_derived && self.shaft_rate.is_settled() {
+                rpm / 60.0
+            } else {
+                self.shaft_rate.next()
+            };
+        }
+        self.load.fill(&mut lanes.load[..n]);
+        self.cavitation_level.fill(&mut lanes.cavitation[..n]);
+        for (gate, lane) in self.gates.iter_mut().zip(&mut lanes.gates) {
+            gate.fill(&mut lane[..n]);
+        }
+
+        // Echoes and recorded input already carry their own Doppler, so they run on the true
+        // clock. Rendering the generators against a scaled clock shifts every frequency and
+        // event rate together.
+        let true_rate = mixing.sample_rate;
+        let sample_rate = true_rate / self.doppler;
+        match mixing.precision {
+            Precision::Full => self.generate::<FullMath>(n, sample_rate, lanes, mixing),
+            Precision::Reduced => self.generate::<ReducedMath>(n, sample_rate, lanes, mixing),
+        }
+
+        let [engine_gate, cav_gate, bio_gate] = &lanes.gates;
+        for (i, sample) in out.iter_mut().enumerate() {
+            // A removed voice goes quiet for good once its fade-out ends.
+            if !self.is_sounding() {
+                out[i..].fill(0.0);
+                return;
+            }
+            // Input written straight through voice_input_ptr skips write_voice_input's check.
+            let external = self.input.get_mut(index + i).map_or(0.0, core::mem::take);
+            let external = if external.is_finite() { external } else { 0.0 };
+            let echo = self.echo.tick(true_rate);
+            let machinery = lanes.engine[i] * engine_gate[i] * self.engine_mix.next()
+                + lanes.cav[i] * cav_gate[i] * self.cav_mix.next();
+            let bio = lanes.bio[i] * bio_gate[i] * self.bio_mix.next();
+            let (machinery_duck, bio_duck) = self.ducker.process(machinery, bio);
+            let generated = (machinery * machinery_duck + bio * bio_duck) * self.humanize.level;
+            let dry = (generated + external) * self.gain.next();
+            let fade = self.fade.next() * self.cull.next();
+            let bounced = self.multipath.process(dry, mixing.glide);
+            *sample = (self.propagation.process(bounced, mixing.glide) + echo) * fade;
+        }
+    }
+}
+
+// How a block's voices reach the output: panned to mono, stereo or binaural, around a speaker
+// ring, or as plane waves across the towed array.
+#[derive(Clone, Copy)]
+enum VoiceLayout {
+    Mono,
+    Stereo,
+    Binaural,
+    Ring,
+    Array,
+}
+
+// Everything a voice reads from the graph while it renders and mixes into a block. Voices only
+// read it, so every worker can share one.
+struct VoiceMixing<'a> {
+    n: usize,
+    stride: usize,
+    channels: usize,
+    sample_rate: f32,
+    layout: VoiceLayout,
+    itd_enabled: bool,
+    listener_yaw: f32,
+    precision: Precision,
+    // Culling threshold (0 when off) and fade length in samples.
+    cull_threshold: f32,
+    cull_fade: u32,
+    // Per-sample coefficient of the DEZIPPER_MS glides.
+    glide: f32,
+    // Samples apart the low-frequency generators compute their waveforms; 1 while low-rate
+    // rendering is off.
+    low_rate: u32,
+    band_limited_engines: bool,
+    directivity: &'a Directivity,
+    groups: &'a [VoiceGroup; MAX_GROUPS],
+    automation: &'a ParamQueue,
+    environment: &'a Environment,
+    towed_array: &'a TowedArray,
+    profile: &'a Profile,
+}
+
+impl VoiceMixing<'_> {
+    // Renders the sounding voices among `voices`, the first of which has id `first`, and adds
+    // them into `out` (planar, channels `stride` apart) and the return bus sends. `dry` and
+    // `lanes` are scratch.
+    fn mix_all(
+        &self,
+        voices: &mut [Voice],
+        first: usize,
+        dry: &mut [f32],
+        lanes: &mut VoiceLanes,
+        out: &mut [f32],
+        bus_send: &mut [f32],
+    ) {
+        for (k, voice) in voices.iter_mut().enumerate() {
+            if voice.is_sounding() {
+                self.mix(voice, first + k, &mut dry[..self.n], lanes, out, bus_send);
+            }
+        }
+    }
+
+    fn mix(
+        &self,
+        voice: &mut Voice,
+        idx: usize,
+        dry: &mut [f32],
+        lanes: &mut VoiceLanes,
+        out: &mut [f32],
+        bus_send: &mut [f32],
+    ) {
+        let (stride, sr) = (self.stride, self.sample_rate);
+        // The array hears every bearing alike, so the receiver pattern only shapes the others.
+        let receive = match self.layout {
+            VoiceLayout::Array => 1.0,
+            _ => self.directivity.gain(voice.panner.bearing),
+        };
+        match self.layout {
+            VoiceLayout::Binaural => {
+                let azimuth = voice.panner.apparent_bearing() - self.listener_yaw;
+                voice.binaural.steer(azimuth, sr);
+            }
+            VoiceLayout::Ring => voice.ring.steer(voice.panner.apparent_bearing(), self.channels),
+            VoiceLayout::Array => {
+                voice
+                    .array_delay
+                    .steer(self.towed_array, voice.panner.apparent_bearing(), sr);
+            }
+            VoiceLayout::Mono | VoiceLayout::Stereo => {}
+        }
+        let level = self.groups[voice.group].level();
+        let events = self.automation.pending(idx);
+        voice.update_cull(voice.level_bound(receive, level), self.cull_threshold, self.cull_fade);
+        if voice.is_silenced() {
+            voice.skip(dry.len(), events);
+            voice.receive_gain = receive;
+            voice.group_gain = level;
+            dry.iter().for_each(|_| voice.meter.add(0.0));
+            if let Some(tap) = voice.tap.get_mut(..dry.len()) {
+                tap.fill(0.0);
+            }
+            return;
+        }
+        voice.render(dry, events, lanes, self);
+        if !dry.iter().all(|s| s.is_finite()) {
+            voice.quarantine();
+            dry.fill(0.0);
+        }
+        for (i, &s) in dry.iter().enumerate() {
+            let s = match self.layout {
+                VoiceLayout::Array => s,
+                _ => {
+                    let step = self.glide * (receive - voice.receive_gain);
+                    voice.receive_gain = flush_denormal(voice.receive_gain + step);
+                    s * voice.receive_gain
+                }
+            };
+            if let Some(tap) = voice.tap.get_mut(i) {
+                *tap = s;
+            }
+            voice.group_gain =
+                flush_denormal(voice.group_gain + self.glide * (level - voice.group_gain));
+            let s = s * voice.group_gain;
+            voice.meter.add(s);
+            match self.layout {
+                VoiceLayout::Mono => out[i] += s,
+                VoiceLayout::Stereo => {
+                    let (l, r) = voice.panner.process(s, self.itd_enabled, sr, self.glide);
+                    out[i] += l;
+                    out[stride + i] += r;
+                }
+                VoiceLayout::Binaural => {
+                    let (l, r) = voice.binaural.process(s, self.glide);
+                    out[i] += l;
+                    out[stride + i] += r;
+                }
+                VoiceLayout::Ring => {
+                    voice.ring.process(s, self.glide, out, stride, i, self.channels)
+                }
+                VoiceLayout::Array => voice.array_delay.process(s, self.glide, out, stride, i),
+            }
+            for (bus, send) in voice.sends.iter_mut().enumerate() {
+                bus_send[bus * stride + i] += s * send.next();
+            }
+        }
+    }
+}
+
+// What add_voice does when the voice limit is reached.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum StealPolicy {
+    // Fail with PoolExhausted.
+    Never = 0,
+    // Fade out the lowest-priority voice, the quietest among equals.
+    Quietest = 1,
+    // Fade out the lowest-priority voice, the longest-running among equals.
+    Oldest = 2,
+}
+
+impl StealPolicy {
+    fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Never),
+            1 => Some(Self::Quietest),
+            2 => Some(Self::Oldest),
+            _ => None,
+        }
+    }
+}
+
+// A voice's sound generators, as selected in set_component_enabled.
+#[derive(Clone, Copy)]
+enum Component {
+    Engine = 0,
+    Cavitation = 1,
+    Bio = 2,
+}
+
+const COMPONENT_COUNT: usize = 3;
+
+impl Component {
+    fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Engine),
+            1 => Some(Self::Cavitation),
+            2 => Some(Self::Bio),
+            _ => None,
+        }
+    }
+}
+
+// A zeroed block of `len` floats in this module's memory that belongs to the host, e.g. as
+// the target of process_into(). Returns its address; release it with free_f32_buffer.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn alloc_f32_buffer(len: usize) -> usize {
+    Box::into_raw(vec![0.0f32; len.max(1)].into_boxed_slice()) as *mut f32 as usize
+}
+
+/// Releases a buffer from alloc_f32_buffer, given the same length it was allocated with. A
+/// null address is ignored.
+///
+/// # Safety
+///
+/// `ptr` and `len` must come from a single alloc_f32_buffer call, the buffer must not have
+/// been freed already, and nothing may use it afterwards.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub unsafe fn free_f32_buffer(ptr: usize, len: usize) {
+    if ptr == 0 {
+        return;
+    }
+    let slice = core::ptr::slice_from_raw_parts_mut(ptr as *mut f32, len.max(1));
+    // SAFETY: upheld by the caller, as above.
+    drop(unsafe { Box::from_raw(slice) });
+}
+
+// OutOfRange for an address no host buffer can have: null, or not aligned for f32.
+fn check_host_ptr(ptr: usize) -> DspResult {
+    ensure(ptr != 0 && ptr.is_multiple_of(core::mem::align_of::<f32>()), DspError::OutOfRange)
+}
+
+// The `len` floats at `ptr`, a host-owned block such as alloc_f32_buffer returns, for results
+// written straight into it. The one place a host address becomes a slice; every `_into` entry
+// point goes through it. OutOfRange for a null or misaligned address or no room at all.
+//
+// SAFETY: the caller must know that `ptr` addresses `len` floats nothing else uses while the
+// slice lives; the public entry points pass that contract on as their own `# Safety`.
+unsafe fn host_floats<'a>(ptr: usize, len: usize) -> DspResult<&'a mut [f32]> {
+    check_host_ptr(ptr)?;
+    ensure(len > 0, DspError::OutOfRange)?;
+    Ok(unsafe { core::slice::from_raw_parts_mut(ptr as *mut f32, len) })
+}
+
+// Size this module's memory has grown to, in bytes: whatever graphs, analysers and host buffers
+// hold, plus the allocator's free space. Wasm memory never shrinks, so this is also the peak.
+#[cfg(target_arch = "wasm32")]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn wasm_memory_bytes() -> usize {
+    core::arch::wasm32::memory_size(0) * 65_536
+}
+
+// Outside wasm there is no module memory to measure.
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn wasm_memory_bytes() -> usize {
+    0
+}
+
+// Peak and RMS level (linear) over every sample since the meter was last read.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy)]
+pub struct Meter {
+    pub peak: f32,
+    pub rms: f32,
+}
+
+// Master-stage saturation since the report was last read: blocks rendered, blocks where the
+// limiter took off more than 1 dB, blocks and samples that left the graph beyond full scale,
+// the highest peak into the limiter in dBFS (above 0 is overshoot, below is headroom left),
+// and the deepest limiter gain reduction in dB.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy)]
+pub struct HeadroomReport {
+    pub blocks: u32,
+    pub limited_blocks: u32,
+    pub clipped_blocks: u32,
+    pub clipped_samples: u32,
+    pub peak_db: f32,
+    pub max_reduction_db: f32,
+}
+
+// Bytes of wasm memory a graph holds, by what holds them. `voices` is the voice pool, which
+// keeps every slot it has grown to, with each voice's delay lines, tap and input buffers.
+// `buffers` is the per-block scratch and output, which scales with the block size and channel
+// count. `history` is the event log and any recording or replay. `scene` is the rest: the graph
+// itself, reverb and limiter delay lines, array delays, the bathymetry grid, and the queued
+// automation, scenario and control bindings.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy)]
+pub struct MemoryUsage {
+    pub voices: usize,
+    pub buffers: usize,
+    pub history: usize,
+    pub scene: usize,
+    pub total: usize,
+}
+
+// Snapshot of a voice's live, smoothed state as opposed to the targets set_param wrote.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy)]
+pub struct VoiceState {
+    pub current_rpm: f32,
+    pub target_rpm: f32,
+    pub current_shaft_rate: f32,
+    pub target_shaft_rate: f32,
+    pub bio_type: BioType,
+    pub prev_bio_type: BioType,
+    // 0 right after a bio type change, 1 once the crossfade has finished.
+    pub bio_xfade: f32,
+    pub range: f32,
+    // Apparent bearing in degrees clockwise from the bow, including any wander/jitter.
+    pub bearing: f32,
+    pub doppler: f32,
+    pub propagation_gain: f32,
+    pub receive_gain: f32,
+    // Culled as inaudible; see DspGraph::set_voice_culling.
+    pub culled: bool,
+}
+
+// The second set of the buffers the host reads each block from. process() swaps every one with
+// its spare before rendering, so the block the host was last handed stays where it is, intact,
+// until the call after.
+#[derive(Default)]
+struct SpareOutputs {
+    output: Vec<f32>,
+    interleaved: Vec<f32>,
+    pcm16: Vec<i16>,
+    hydrophone: Vec<f32>,
+}
+
+impl SpareOutputs {
+    fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.output)
+            + vec_bytes(&self.interleaved)
+            + vec_bytes(&self.pcm16)
+            + vec_bytes(&self.hydrophone)
+    }
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Serialize, Deserialize)]
+pub struct DspGraph {
+    sample_rate: f32,
+    max_frames: usize,
+    last_frames: usize,
+    voices: Vec<Voice>,
+    // Most voices active at once. The slot pool grows as needed and never shrinks; it may run
+    // a little past the limit while stolen voices fade out.
+    voice_limit: usize,
+    steal_policy: StealPolicy,
+    // Level below which a voice is culled as inaudible; 0 while culling is off.
+    cull_threshold: f32,
+    low_rate_sources: bool,
+    band_limited_engines: bool,
+    channels: usize,
+    itd_enabled: bool,
+    binaural: bool,
+    // Listener's head yaw relative to own-ship's bow, radians clockwise.
+    listener_yaw: f32,
+    directivity: Directivity,
+    // Per-parameter ramp length used by set_param; only Ramped parameters are ever non-zero.
+    ramp_ms: [f32; PARAM_COUNT],
+    automation: ParamQueue,
+    scenario: Scenario,
+    controls: ControlMap,
+    groups: [VoiceGroup; MAX_GROUPS],
+    events: EventLog,
+    // Per-block buffers and profiling counters stay out of snapshots.
+    #[serde(skip)]
     output: Vec<f32>,
+    // Planar, one block per return bus.
+    #[serde(skip)]
+    bus_send: Vec<f32>,
+    reverb: Reverb,
+    hull_filter: HullFilter,
+    // The master chain: DC blocker, operator band, loudness normaliser, limiter. The hydrophone
+    // mix is taken off ahead of it.
+    dc_blocker: DcBlocker,
+    operator_band: OperatorBand,
+    leveler: Leveler,
+    limiter: Limiter,
+    master_meter: MeterState,
+    hydrophone: bool,
+    // Planar like `output`, and empty while the hydrophone mix is off.
+    #[serde(skip)]
+    hydrophone_output: Vec<f32>,
+    #[serde(skip)]
+    headroom: Headroom,
+    environment: Environment,
+    // Water depth the column glides toward when no bathymetry grid is loaded.
+    depth_target: f32,
+    depth_glide_s: f32,
+    bathymetry: Bathymetry,
+    noise_field: DirectionalNoiseField,
+    weather: Weather,
+    own_ship: OwnShip,
+    self_noise: SelfNoise,
+    towed_array: TowedArray,
+    self_noise_delay: ArrayDelay,
+    sector_delays: Vec<ArrayDelay>,
+    ping_reverb: PingReverb,
+    #[serde(skip)]
+    scratch: Vec<f32>,
+    #[serde(skip)]
+    lanes: VoiceLanes,
+    #[cfg(feature = "threads")]
+    #[serde(skip)]
+    partial_mixes: Vec<PartialMix>,
+    // A property of the host rather than the scene, so snapshots neither carry nor change it.
+    #[serde(skip)]
+    precision: Precision,
+    interleaved: bool,
+    // While set, process() returns silence and nothing in the graph moves on.
+    paused: bool,
+    // Sized on first use after the layout, channel count or block size changes.
+    #[serde(skip)]
+    interleaved_output: Vec<f32>,
+    // Optional 16-bit copy of each block, in the output layout with planar channels packed.
+    pcm16: bool,
+    dither_rng: u32,
+    #[serde(skip)]
+    pcm16_output: Vec<i16>,
+    #[serde(skip)]
+    spare_outputs: SpareOutputs,
+    // Base of every random stream in the graph; 0 keeps the long-standing default sequences.
+    seed: u32,
     next_seed: u32,
+    #[serde(skip)]
     process_call_count: u32,
+    #[serde(skip)]
     process_total_ms: f64,
+    #[serde(skip)]
     process_max_ms: f64,
+    #[serde(skip)]
+    profile: Profile,
+    // Session artifacts rather than graph state; restore() ends both.
+    #[serde(skip)]
+    recorder: Recorder,
+    #[serde(skip)]
+    replay: Replay,
+    // A spectrum the host asked for, worked on a slice per block. Host-side like `precision`.
+    #[serde(skip)]
+    analysis: DemonAnalyzer,
+    #[serde(skip)]
+    analysis_budget: u32,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 impl DspGraph {
-    #[wasm_bindgen(constructor)]
+    // `max_voices` sizes the voice pool and the starting voice limit, clamped to 1 through
+    // max_voice_limit() so every graph can restore its own snapshots.
+    #[cfg_attr(feature = "std", wasm_bindgen(constructor))]
     pub fn new(sample_rate: f32, max_frames: usize, max_voices: usize) -> Self {
-        let capped_voices = max_voices.max(1);
+        let capped_voices = max_voices.clamp(1, MAX_VOICE_LIMIT);
         let mut voices = Vec::with_capacity(capped_voices);
         for _ in 0..capped_voices {
             voices.push(Voice::new(0));
@@ -1498,85 +3675,1766 @@ impl DspGraph {
             max_frames: max_frames.max(1),
             last_frames: 0,
             voices,
+            voice_limit: capped_voices,
+            steal_policy: StealPolicy::Never,
+            cull_threshold: 0.0,
+            low_rate_sources: false,
+            band_limited_engines: false,
+            channels: 1,
+            itd_enabled: false,
+            binaural: false,
+            listener_yaw: 0.0,
+            directivity: Directivity::new(),
+            ramp_ms: PARAM_SPECS.map(|spec| spec.ramp_ms),
+            automation: ParamQueue::new(),
+            scenario: Scenario::new(),
+            controls: ControlMap::new(),
+            groups: [VoiceGroup::new(); MAX_GROUPS],
+            events: EventLog::new(),
             output: vec![0.0; max_frames.max(1)],
+            bus_send: vec![0.0; max_frames.max(1) * BUS_COUNT],
+            reverb: Reverb::new(sample_rate),
+            hull_filter: HullFilter::new(sample_rate),
+            dc_blocker: DcBlocker::new(sample_rate),
+            operator_band: OperatorBand::new(sample_rate),
+            leveler: Leveler::new(sample_rate),
+            limiter: Limiter::new(sample_rate),
+            master_meter: MeterState::new(),
+            hydrophone: false,
+            hydrophone_output: Vec::new(),
+            headroom: Headroom::default(),
+            environment: Environment::new(),
+            depth_target: Environment::new().water_depth,
+            depth_glide_s: 0.0,
+            bathymetry: Bathymetry::new(),
+            noise_field: DirectionalNoiseField::new(0x5eed_f1e1),
+            weather: Weather::new(Environment::new().sea_state, 0x7ea7_4e12),
+            own_ship: OwnShip::new(),
+            self_noise: SelfNoise::new(0x0b0a_7001),
+            towed_array: TowedArray::new(0x70e0_a77a),
+            self_noise_delay: ArrayDelay::new(),
+            sector_delays: vec![ArrayDelay::new(); NOISE_SECTORS],
+            ping_reverb: PingReverb::new(0x9196_0ec0),
+            scratch: vec![0.0; max_frames.max(1)],
+            lanes: VoiceLanes::new(max_frames.max(1)),
+            #[cfg(feature = "threads")]
+            partial_mixes: Vec::new(),
+            precision: Precision::Full,
+            interleaved: false,
+            paused: false,
+            interleaved_output: Vec::new(),
+            pcm16: false,
+            dither_rng: 0xd17e_4001,
+            pcm16_output: Vec::new(),
+            spare_outputs: SpareOutputs::default(),
+            seed: 0,
             next_seed: 0x1234_abcd,
             process_call_count: 0,
             process_total_ms: 0.0,
             process_max_ms: 0.0,
+            profile: Profile::default(),
+            recorder: Recorder::default(),
+            replay: Replay::default(),
+            analysis: DemonAnalyzer::new(),
+            analysis_budget: DEFAULT_ANALYSIS_BUDGET,
+        }
+    }
+
+    // Voices fade in over a few milliseconds. A slot still fading out from remove_voice is
+    // not reused until it is silent; with every slot taken the pool grows by one. Once the
+    // voice limit is reached this fails with PoolExhausted, unless a steal policy is set and
+    // some voice ranks no higher than priority 0 (see add_voice_with_priority).
+    pub fn add_voice(&mut self) -> DspResult<u32> {
+        self.add_voice_with_priority(0)
+    }
+
+    // Adds a voice ranked `priority` (higher is more important). At the voice limit, the
+    // steal policy may fade out a voice of equal or lower priority to make room; the stolen
+    // voice raises EVENT_VOICE_DEACTIVATED as if removed.
+    pub fn add_voice_with_priority(&mut self, priority: u32) -> DspResult<u32> {
+        self.schedule_voice(priority, 0)
+    }
+
+    // Adds a voice that stays silent until `offset_samples` frames after the start of the next
+    // process() call, then fades in. It is active to the API at once, so parameters, tracks and
+    // scheduled changes can be set up ahead of its start; EVENT_VOICE_ACTIVATED carries the
+    // start sample. Pausing holds the countdown.
+    pub fn schedule_voice(&mut self, priority: u32, offset_samples: u32) -> DspResult<u32> {
+        let id = self.start_voice(priority, offset_samples, 0)?;
+        let change = Change::AddVoice {
+            priority,
+            delay: offset_samples,
+        };
+        self.recorder.record(self.automation.clock(), id, change);
+        Ok(id)
+    }
+
+    // Same as schedule_voice with the offset given in seconds.
+    pub fn schedule_voice_in(&mut self, priority: u32, delay_s: f64) -> DspResult<u32> {
+        ensure(delay_s.is_finite() && delay_s >= 0.0, DspError::OutOfRange)?;
+        let offset = (delay_s * self.sample_rate as f64).round().min(u32::MAX as f64) as u32;
+        self.schedule_voice(priority, offset)
+    }
+
+    // Samples until a scheduled voice starts; 0 once it is rendering.
+    pub fn voice_start_delay(&self, voice_id: u32) -> Option<u32> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.start_delay)
+    }
+
+    fn start_voice(&mut self, priority: u32, offset_samples: u32, tag: u64) -> DspResult<u32> {
+        if self.active_voice_count() >= self.voice_limit {
+            let victim = self.steal_victim(priority).ok_or(DspError::PoolExhausted)?;
+            self.stop_voice(victim, 0);
         }
+        let i = match self.voices.iter().position(|v| !v.is_sounding()) {
+            Some(i) => i,
+            None if self.voices.len() < MAX_VOICE_LIMIT => {
+                self.voices.push(Voice::new(0));
+                self.voices.len() - 1
+            }
+            None => return Err(DspError::PoolExhausted),
+        };
+        self.next_seed = self.next_seed.wrapping_add(0x9e37_79b9);
+        let clock = self.automation.clock();
+        let fade = self.fade_samples();
+        let voice = &mut self.voices[i];
+        *voice = Voice::new(self.next_seed);
+        voice.fade.set(1.0, fade);
+        voice.priority = priority;
+        voice.started = clock + offset_samples as u64;
+        voice.start_delay = offset_samples;
+        voice.tag = tag;
+        self.events
+            .push(EVENT_VOICE_ACTIVATED, Some(i), tag, voice.started, 0, 0.0);
+        Ok(i as u32)
+    }
+
+    // 0 = never steal (the default), 1 = steal the quietest of the lowest-priority voices,
+    // 2 = steal the longest-running of them.
+    pub fn set_steal_policy(&mut self, policy: u32) -> DspResult {
+        self.steal_policy = StealPolicy::from_id(policy).ok_or(DspError::OutOfRange)?;
+        Ok(())
+    }
+
+    pub fn steal_policy(&self) -> u32 {
+        self.steal_policy as u32
+    }
+
+    pub fn set_voice_priority(&mut self, voice_id: u32, priority: u32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        self.voices[idx].priority = priority;
+        Ok(())
+    }
+
+    pub fn voice_priority(&self, voice_id: u32) -> Option<u32> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.priority)
+    }
+
+    // Ties a voice to a host entity. The tag comes back with every event the voice raises (see
+    // drain_events) and from find_voice_by_tag; 0 means untagged. Events carry it as a double,
+    // so tags above 2^53 lose their low bits there.
+    pub fn set_voice_tag(&mut self, voice_id: u32, tag: u64) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        self.voices[idx].tag = tag;
+        Ok(())
+    }
+
+    pub fn voice_tag(&self, voice_id: u32) -> Option<u64> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.tag)
+    }
+
+    // First active voice carrying `tag`, if any.
+    pub fn find_voice_by_tag(&self, tag: u64) -> Option<u32> {
+        self.voices
+            .iter()
+            .position(|v| v.active && v.tag == tag)
+            .map(|i| i as u32)
+    }
+
+    // Free-form name for debugging views and logs; never read by the DSP.
+    pub fn set_voice_label(&mut self, voice_id: u32, label: String) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        self.voices[idx].label = label;
+        Ok(())
+    }
+
+    pub fn voice_label(&self, voice_id: u32) -> Option<String> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.label.clone())
+    }
+
+    pub fn active_voice_count(&self) -> usize {
+        self.voices.iter().filter(|v| v.active).count()
+    }
+
+    // Slots still rendering: the active voices plus removed ones not yet faded out. New voices
+    // can't take these.
+    pub fn sounding_voice_count(&self) -> usize {
+        self.voices.iter().filter(|v| v.is_sounding()).count()
+    }
+
+    // Sounding voices culled as inaudible, including any still fading out.
+    pub fn culled_voice_count(&self) -> usize {
+        self.voices.iter().filter(|v| v.is_sounding() && v.culled).count()
+    }
+
+    // Ids of every active voice, lowest first. With voice_tag, voice_label and voice_preset,
+    // enough for the host to rebuild its contact list from the graph, say after a restore.
+    pub fn active_voice_ids(&self) -> Vec<u32> {
+        (0..self.voices.len() as u32)
+            .filter(|&id| self.voices[id as usize].active)
+            .collect()
+    }
+
+    // Preset last applied to a voice, by apply_preset, a scenario step, clone_voice or
+    // DspGraphBuilder; -1 if none has been.
+    pub fn voice_preset(&self, voice_id: u32) -> Option<i32> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.preset.map_or(-1, |id| id as i32))
+    }
+
+    // Most voices active at once, starting at the constructor's max_voices. Clamped between
+    // the voices active now and max_voice_limit().
+    pub fn set_voice_limit(&mut self, limit: usize) {
+        self.voice_limit = limit.min(MAX_VOICE_LIMIT).max(self.active_voice_count()).max(1);
+    }
+
+    pub fn voice_limit(&self) -> usize {
+        self.voice_limit
+    }
+
+    pub fn voice_pool_size(&self) -> usize {
+        self.voices.len()
+    }
+
+    pub fn max_voice_limit(&self) -> usize {
+        MAX_VOICE_LIMIT
+    }
+
+    pub fn remove_voice(&mut self, voice_id: u32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        self.stop_voice(idx, 0);
+        self.recorder
+            .record(self.automation.clock(), voice_id, Change::RemoveVoice);
+        Ok(())
     }
 
-    pub fn add_voice(&mut self) -> i32 {
-        for i in 0..self.voices.len() {
-            if !self.voices[i].active {
-                self.next_seed = self.next_seed.wrapping_add(0x9e37_79b9);
-                self.voices[i] = Voice::new(self.next_seed);
-                return i as i32;
+    // Removes every active voice as remove_voice would, each fading out and raising
+    // EVENT_VOICE_DEACTIVATED. Returns how many were removed.
+    pub fn clear_voices(&mut self) -> u32 {
+        let clock = self.automation.clock();
+        let mut removed = 0;
+        for idx in 0..self.voices.len() {
+            if self.voices[idx].active {
+                self.stop_voice(idx, 0);
+                self.recorder
+                    .record(clock, idx as u32, Change::RemoveVoice);
+                removed += 1;
             }
         }
-        -1
+        removed
     }
 
-    pub fn remove_voice(&mut self, voice_id: u32) -> bool {
-        let idx = voice_id as usize;
-        if idx >= self.voices.len() {
-            return false;
+    // Clears the scene for the next one: clear_voices() without the fade-outs, so nothing
+    // from before sounds past this call, then drops the scenario and any replay and empties
+    // the reverb, hull filter, ping reverberation and master chain (DC blocker, operator band,
+    // loudness normaliser, limiter, master meter and headroom report). Settings, the ambient
+    // bed and own-ship state carry on, and random streams restart from the seed.
+    pub fn reset(&mut self) {
+        self.clear_voices();
+        for voice in &mut self.voices {
+            voice.fade = Ramp::new(0.0);
+            voice.stop_cue = None;
         }
-        self.voices[idx].active = false;
-        true
+        self.scenario.clear();
+        self.replay.clear();
+        self.bus_send.fill(0.0);
+        self.reverb.clear();
+        self.hull_filter.clear();
+        self.ping_reverb.clear();
+        self.dc_blocker.clear();
+        self.operator_band.clear();
+        self.leveler.clear();
+        self.limiter.clear();
+        self.master_meter = MeterState::new();
+        self.headroom = Headroom::default();
+        self.reseed();
     }
 
-    pub fn set_param(&mut self, voice_id: u32, param_id: u32, value: f32) -> bool {
-        let idx = voice_id as usize;
-        if idx >= self.voices.len() || !self.voices[idx].active {
-            return false;
+    pub fn set_param(&mut self, voice_id: u32, param: Param, value: f32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        ensure(param.accepts(value), DspError::OutOfRange)?;
+        self.recorder
+            .record(self.automation.clock(), voice_id, Change::SetParam { param, value });
+        let value = param.spec().apply(value);
+        let ramp = (self.ramp_ms[param as usize] * 0.001 * self.sample_rate) as u32;
+
+        self.voices[idx].set_value(param, value, ramp);
+        Ok(())
+    }
+
+    // Applies a packed run of changes in one call, param_update_stride() floats each: voice
+    // id, Param id, value. Each lands exactly as the matching set_param would, in order; a bad
+    // record is skipped without stopping the rest. Returns how many were applied.
+    pub fn set_params(&mut self, updates: &[f32]) -> DspResult<u32> {
+        ensure(updates.len().is_multiple_of(PARAM_UPDATE_STRIDE), DspError::OutOfRange)?;
+        let mut applied = 0;
+        for record in updates.chunks_exact(PARAM_UPDATE_STRIDE) {
+            let voice_id = whole_id(record[0]);
+            let param = whole_id(record[1]).and_then(Param::from_id);
+            let (Some(voice_id), Some(param)) = (voice_id, param) else {
+                continue;
+            };
+            if self.set_param(voice_id, param, record[2]).is_ok() {
+                applied += 1;
+            }
         }
+        Ok(applied)
+    }
 
-        let v = &mut self.voices[idx];
-        match param_id {
-            PARAM_RPM => v.engine.target_rpm = value.max(0.0),
-            PARAM_BLADES => v.engine.blades = clamp(value, 1.0, 12.0),
-            PARAM_GAIN => v.gain = clamp(value, 0.0, 2.0),
-            PARAM_ENGINE_MIX => v.engine_mix = clamp(value, 0.0, 1.5),
-            PARAM_CAV_MIX => v.cav_mix = clamp(value, 0.0, 1.5),
-            PARAM_BIO_MIX => v.bio_mix = clamp(value, 0.0, 1.5),
-            PARAM_BIO_TYPE => v.bio.set_type(BioType::from_param(value)),
-            PARAM_BIO_RATE => v.bio.set_rate(value),
-            PARAM_SHAFT_RATE => v.engine.target_shaft_rate = clamp(value, 0.0, 120.0),
-            PARAM_LOAD => v.engine.load = clamp(value, 0.0, 1.0),
-            PARAM_RPM_JITTER => v.engine.rpm_jitter = clamp(value, 0.0, 1.0),
-            PARAM_CLASS_PROFILE => v.engine.class_profile = clamp(value.round(), 0.0, 4.0) as u32,
-            PARAM_CAVITATION_LEVEL => v.cavitation_level = clamp(value, 0.0, 1.0),
-            _ => return false,
+    // Binds control `control` (0 to max_controls() - 1; a CC number, a pad axis, whatever the
+    // host likes) to a voice parameter: set_control() positions 0..1 map onto min..max along
+    // `curve`. A control can drive any number of parameters; binding it to a voice parameter
+    // it already drives replaces that binding. Bindings end when their voice is removed. Fails
+    // with OutOfRange once max_control_bindings() are in place.
+    pub fn bind_control(&mut self, control: u32, voice_id: u32, param: Param, curve: ControlCurve, min: f32, max: f32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        let binding = Binding {
+            control,
+            voice: idx,
+            param,
+            curve,
+            min,
+            max,
+        };
+        let valid = control < MAX_CONTROLS && binding.is_valid();
+        ensure(valid && self.controls.bind(binding), DspError::OutOfRange)?;
+        Ok(())
+    }
+
+    // Drops every binding of a control. Returns how many there were.
+    pub fn unbind_control(&mut self, control: u32) -> u32 {
+        self.controls.unbind(control) as u32
+    }
+
+    // Moves a control to `position` (clamped to 0..1) and sets each parameter bound to it
+    // through set_param, so clamping, ramps and recording apply as usual. Returns the number
+    // of parameters set.
+    pub fn set_control(&mut self, control: u32, position: f32) -> DspResult<u32> {
+        ensure(control < MAX_CONTROLS && position.is_finite(), DspError::OutOfRange)?;
+        let mut applied = 0;
+        for i in 0..self.controls.bindings.len() {
+            let binding = self.controls.bindings[i];
+            if binding.control != control {
+                continue;
+            }
+            let value = binding.value(position);
+            if self.set_param(binding.voice as u32, binding.param, value).is_ok() {
+                applied += 1;
+            }
         }
+        Ok(applied)
+    }
 
-        true
+    pub fn control_binding_count(&self) -> u32 {
+        self.controls.len() as u32
     }
 
-    // Returns a pointer into WASM memory to the graph output buffer.
-    // Read `output_len()` samples from this address.
-    pub fn process(&mut self, frames: usize) -> usize {
-        let n = frames.min(self.max_frames);
-        self.last_frames = n;
+    pub fn max_controls(&self) -> u32 {
+        MAX_CONTROLS
+    }
+
+    pub fn max_control_bindings(&self) -> u32 {
+        MAX_CONTROL_BINDINGS as u32
+    }
+
+    pub fn param_update_stride(&self) -> u32 {
+        PARAM_UPDATE_STRIDE as u32
+    }
+
+    // Queues a set_param to land `offset_samples` frames after the start of the next process()
+    // call. A negative ramp_ms uses the parameter's configured ramp; otherwise it overrides it
+    // for this change only (ignored by parameters that don't ramp).
+    pub fn schedule_param(&mut self, voice_id: u32, param: Param, value: f32, offset_samples: u32, ramp_ms: f32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        let spec = param.spec();
+        ensure(param.accepts(value) && !ramp_ms.is_nan(), DspError::OutOfRange)?;
+        let ramp_ms = if ramp_ms < 0.0 || spec.smoothing != Smoothing::Ramped {
+            self.ramp_ms[param as usize]
+        } else {
+            ramp_ms.min(10_000.0)
+        };
+        let ramp = (ramp_ms * 0.001 * self.sample_rate) as u32;
+        let queued = self
+            .automation
+            .push(idx, param, spec.apply(value), ramp, offset_samples as u64);
+        ensure(queued, DspError::QueueFull)
+    }
+
+    // Same as schedule_param with the offset given in seconds.
+    pub fn schedule_param_in(&mut self, voice_id: u32, param: Param, value: f32, delay_s: f64, ramp_ms: f32) -> DspResult {
+        ensure(delay_s.is_finite() && delay_s >= 0.0, DspError::OutOfRange)?;
+        let offset = (delay_s * self.sample_rate as f64).round().min(u32::MAX as f64) as u32;
+        self.schedule_param(voice_id, param, value, offset, ramp_ms)
+    }
+
+    // Drops every queued change for a voice; returns how many were dropped.
+    pub fn cancel_scheduled(&mut self, voice_id: u32) -> u32 {
+        self.automation.cancel(voice_id as usize) as u32
+    }
+
+    pub fn scheduled_count(&self) -> u32 {
+        self.automation.len() as u32
+    }
+
+    pub fn max_scheduled_events(&self) -> u32 {
+        MAX_SCHEDULED_EVENTS as u32
+    }
+
+    // Loads a mission script that process() runs sample-accurately, replacing any script still
+    // running. Each step is scenario_stride() values: time in seconds from the start of the
+    // next process() call, a ScenarioAction, the voice tag it addresses, and up to three
+    // arguments (see ScenarioAction). AddVoice gives the new voice that tag, so later steps
+    // can address it; the others act on whichever active voice carries it, and are skipped
+    // if none does. A malformed step rejects the whole script. Returns the step count.
+    pub fn load_scenario(&mut self, steps: &[f64]) -> DspResult<u32> {
+        let clock = self.automation.clock();
+        let count = self.scenario.load(steps, clock, self.sample_rate)?;
+        Ok(count as u32)
+    }
+
+    pub fn clear_scenario(&mut self) {
+        self.scenario.clear();
+    }
+
+    // Steps not yet run; 0 once the script has finished.
+    pub fn scenario_pending(&self) -> u32 {
+        self.scenario.remaining() as u32
+    }
+
+    pub fn scenario_stride(&self) -> u32 {
+        SCENARIO_STRIDE as u32
+    }
+
+    pub fn max_scenario_steps(&self) -> u32 {
+        MAX_SCENARIO_STEPS as u32
+    }
+
+    // Starts logging every add_voice, remove_voice and set_param (including those made through
+    // set_params and apply_preset) against the graph clock, discarding any earlier recording.
+    // Changes the scenario or a replay makes are not logged. Past max_recorded_changes()
+    // untaken changes, further ones are dropped.
+    pub fn start_recording(&mut self) {
+        self.recorder.start(self.automation.clock());
+    }
+
+    pub fn stop_recording(&mut self) {
+        self.recorder.stop();
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_armed()
+    }
+
+    // Hands over and empties the recording as flat records of record_stride() values: samples
+    // since start_recording, a RecordedChange, the voice id, a detail and a value (see
+    // RecordedChange). Successive takes concatenate into one log.
+    pub fn take_recording(&mut self) -> Vec<f64> {
+        self.recorder.take()
+    }
+
+    pub fn recorded_count(&self) -> u32 {
+        self.recorder.len() as u32
+    }
+
+    pub fn record_stride(&self) -> u32 {
+        RECORD_STRIDE as u32
+    }
+
+    pub fn max_recorded_changes(&self) -> u32 {
+        MAX_RECORDED_CHANGES as u32
+    }
+
+    // Plays a recording back from the start of the next process() call, every change landing
+    // on the same sample relative to the start as it was recorded. For an exact reproduction,
+    // replay into a graph in the state recording began from (e.g. restored from a snapshot
+    // taken then), so voices that already existed keep their ids; voices the recording adds
+    // are followed to whatever ids they get. A malformed record rejects the whole log.
+    // Returns the change count.
+    pub fn start_replay(&mut self, recording: &[f64]) -> DspResult<u32> {
+        let count = self.replay.load(recording, self.automation.clock())?;
+        Ok(count as u32)
+    }
+
+    pub fn stop_replay(&mut self) {
+        self.replay.clear();
+    }
+
+    // Changes not yet replayed; 0 once playback has finished.
+    pub fn replay_pending(&self) -> u32 {
+        self.replay.remaining() as u32
+    }
+
+    // Hands over everything the graph raised since the last call, oldest first, as flat
+    // records of event_stride() values: kind (EVENT_*), voice (-1 for graph-wide events),
+    // sample on the graph clock, a detail id, a value, and the voice's tag (0 when untagged,
+    // and always 0 on activation, which comes before any set_voice_tag). Automation carries
+    // the parameter id and value, bio events the bio type, pings their level; transients are
+    // ping echoes reaching a voice, and a quarantine is a voice whose block went non-finite and
+    // was silenced while its state was rebuilt. Calls between blocks are stamped with the next
+    // block's first sample. Meant to be drained once per block; past max_graph_events() the
+    // oldest drop.
+    pub fn drain_events(&mut self) -> Vec<f64> {
+        self.events.drain()
+    }
+
+    pub fn pending_event_count(&self) -> u32 {
+        self.events.len() as u32
+    }
+
+    pub fn event_stride(&self) -> u32 {
+        EVENT_STRIDE as u32
+    }
+
+    pub fn max_graph_events(&self) -> u32 {
+        MAX_GRAPH_EVENTS as u32
+    }
+
+    // Serializes the whole graph, down to oscillator phases, delay lines and RNG states, as
+    // JSON. Restoring it into a graph at the same sample rate resumes the identical output.
+    pub fn snapshot(&self) -> Option<String> {
+        serde_json::to_string(&SnapshotRef {
+            version: SNAPSHOT_VERSION,
+            graph: self,
+        })
+        .ok()
+    }
+
+    // Layout version written into new snapshots; restore() accepts this and every older one.
+    pub fn snapshot_version(&self) -> u32 {
+        SNAPSHOT_VERSION
+    }
+
+    // Replaces this graph's state with a snapshot. Snapshots from older builds are migrated,
+    // with anything they predate at its default. Rejects malformed snapshots, ones from a
+    // newer build and ones taken at another sample rate, leaving the graph untouched. Output
+    // pointers change.
+    pub fn restore(&mut self, snapshot: &str) -> DspResult {
+        let Ok(Snapshot { version, mut graph }) = serde_json::from_str::<Snapshot<Value>>(snapshot) else {
+            return Err(DspError::InvalidSnapshot);
+        };
+        let template = serde_json::to_value(Self::new(self.sample_rate, self.max_frames, 1))
+            .map_err(|_| DspError::InvalidSnapshot)?;
+        ensure(upgrade(&mut graph, version, &template), DspError::InvalidSnapshot)?;
+        let Ok(graph) = serde_json::from_value::<DspGraph>(graph) else {
+            return Err(DspError::InvalidSnapshot);
+        };
+        if graph.sample_rate != self.sample_rate {
+            return Err(DspError::InvalidSnapshot);
+        }
+        let consistent = graph.max_frames > 0
+            && !graph.voices.is_empty()
+            && graph.voices.len() <= MAX_VOICE_LIMIT
+            && (1..=MAX_VOICE_LIMIT).contains(&graph.voice_limit)
+            && graph.voices.iter().all(|v| v.group < MAX_GROUPS)
+            && (1..=MAX_ARRAY_ELEMENTS).contains(&graph.channels)
+            && graph.sector_delays.len() == NOISE_SECTORS;
+        if !consistent {
+            return Err(DspError::InvalidSnapshot);
+        }
+        let max_frames = graph.max_frames;
+        *self = Self {
+            last_frames: 0,
+            output: vec![0.0; max_frames * graph.channels.max(1)],
+            bus_send: vec![0.0; max_frames * BUS_COUNT],
+            scratch: vec![0.0; max_frames],
+            lanes: VoiceLanes::new(max_frames),
+            precision: self.precision,
+            analysis: core::mem::take(&mut self.analysis),
+            analysis_budget: self.analysis_budget,
+            ..graph
+        };
+        self.size_hydrophone_output();
+        Ok(())
+    }
+
+    // Ramp time in milliseconds for later set_param calls on a ramped parameter; 0 jumps.
+    // Ramps already in flight keep their original length.
+    pub fn set_param_ramp(&mut self, param: Param, ramp_ms: f32) -> DspResult {
+        ensure(param.spec().smoothing == Smoothing::Ramped, DspError::InvalidParam)?;
+        ensure(ramp_ms.is_finite(), DspError::OutOfRange)?;
+        self.ramp_ms[param as usize] = clamp(ramp_ms, 0.0, 10_000.0);
+        Ok(())
+    }
+
+    pub fn param_ramp_ms(&self, param: Param) -> f32 {
+        self.ramp_ms[param as usize]
+    }
+
+    // Sets every class-defining parameter of a voice from the built-in vessel library, through
+    // set_param so the usual ramps apply. Bearing, range and gain are left as they were.
+    pub fn apply_preset(&mut self, voice_id: u32, preset_id: u32) -> DspResult {
+        let preset = preset(preset_id).ok_or(DspError::OutOfRange)?;
+        self.voice_index(voice_id)?;
+        for &(param, value) in preset.values {
+            self.set_param(voice_id, param, value)?;
+        }
+        self.voices[voice_id as usize].preset = Some(preset_id);
+        Ok(())
+    }
+
+    // Adds a voice set up like `source_id`: every parameter as set_param last left it, plus its
+    // sends, group, priority, preset, component switches, RPM slew, ducking and modulation. The
+    // copy starts at those values rather than ramping to them, and draws from its own random
+    // streams, so its jitter, variation and random walks go their own way. Tag, label,
+    // kinematics, tap and input port stay at a new voice's defaults. Steals as
+    // add_voice_with_priority does at the source's priority.
+    pub fn clone_voice(&mut self, source_id: u32) -> DspResult<u32> {
+        let idx = self.voice_index(source_id)?;
+        let values: Vec<(Param, f32)> = PARAM_SPECS
+            .iter()
+            .map(|spec| spec.param)
+            .filter(|param| param.as_linear().is_none())
+            .filter_map(|param| Some((param, self.get_param(source_id, param)?)))
+            .collect();
+        let source = &self.voices[idx];
+        let (sends, gates) = (source.sends.map(|s| s.target), source.gates.map(|g| g.target));
+        let (group, ducker, modulation) = (source.group, source.ducker, source.modulation.clone());
+        let (preset, rpm_slew) = (source.preset, source.rpm_slew);
+        let id = self.add_voice_with_priority(source.priority)?;
+        let clock = self.automation.clock();
+        let voice = &mut self.voices[id as usize];
+        for &(param, value) in &values {
+            voice.set_value(param, value, 0);
+            self.recorder
+                .record(clock, id, Change::SetParam { param, value });
+        }
+        voice.sends = sends.map(Ramp::new);
+        voice.gates = gates.map(Ramp::new);
+        voice.group = group;
+        voice.preset = preset;
+        voice.rpm_slew = rpm_slew;
+        voice.ducker.copy_settings(&ducker);
+        voice.modulation.copy_settings(&modulation);
+        Ok(id)
+    }
+
+    // Switches one of a voice's generators (0 = engine, 1 = cavitation, 2 = biologic) on or
+    // off. Unlike a zero mix, a component that is off is skipped entirely once its short fade
+    // has run, and resumes click-free from where it stopped. An engine that is off still turns
+    // its shaft while cavitation is on, since cavitation follows it, but renders no waveform.
+    pub fn set_component_enabled(&mut self, voice_id: u32, component: u32, enabled: bool) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        let component = Component::from_id(component).ok_or(DspError::OutOfRange)?;
+        let fade = self.fade_samples();
+        self.voices[idx].gates[component as usize].set(if enabled { 1.0 } else { 0.0 }, fade);
+        Ok(())
+    }
+
+    pub fn component_enabled(&self, voice_id: u32, component: u32) -> Option<bool> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        let component = Component::from_id(component)?;
+        Some(voice.gates[component as usize].target > 0.0)
+    }
+
+    // Lets a voice's loud machinery (engine plus cavitation) push its biologic component down,
+    // and/or the reverse, as masking would. Whichever side's level rises above threshold_db
+    // (-60..0 dBFS) ducks the other by its ratio (1..20; 1 leaves that side alone), with a
+    // 5 ms attack and release_ms (5..5000) to recover. Off by default.
+    pub fn set_voice_ducking(&mut self, voice_id: u32, threshold_db: f32, bio_ratio: f32, machinery_ratio: f32, release_ms: f32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        let finite = [threshold_db, bio_ratio, machinery_ratio, release_ms]
+            .iter()
+            .all(|v| v.is_finite());
+        ensure(finite, DspError::OutOfRange)?;
+        self.voices[idx].ducker.configure(
+            self.sample_rate,
+            threshold_db,
+            bio_ratio,
+            machinery_ratio,
+            release_ms,
+        );
+        Ok(())
+    }
+
+    // Caps how fast a voice's RPM answers a change, in RPM per second, separately for speeding
+    // up and slowing down, so a ship takes its own time to come up to a new bell. 0 lifts that
+    // direction's limit. A change whose ramp is already slower keeps its ramp; one in flight
+    // keeps its pace until the next change. Off by default.
+    pub fn set_rpm_slew(&mut self, voice_id: u32, accel_rpm_s: f32, decel_rpm_s: f32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        let valid = [accel_rpm_s, decel_rpm_s].iter().all(|v| v.is_finite() && *v >= 0.0);
+        ensure(valid, DspError::OutOfRange)?;
+        self.voices[idx].rpm_slew = [accel_rpm_s, decel_rpm_s].map(|slew| slew / self.sample_rate);
+        Ok(())
+    }
+
+    pub fn rpm_accel(&self, voice_id: u32) -> Option<f32> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.rpm_slew[0] * self.sample_rate)
+    }
+
+    pub fn rpm_decel(&self, voice_id: u32) -> Option<f32> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.rpm_slew[1] * self.sample_rate)
+    }
+
+    // Current ducking of a voice's machinery and biologic sides, in dB.
+    pub fn machinery_ducking_db(&self, voice_id: u32) -> Option<f32> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.ducker.reduction_db().0)
+    }
+
+    pub fn bio_ducking_db(&self, voice_id: u32) -> Option<f32> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.ducker.reduction_db().1)
+    }
+
+    // Reads back what set_param last stored (after clamping), in the same units.
+    pub fn get_param(&self, voice_id: u32, param: Param) -> Option<f32> {
+        let v = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        if let Some(base) = v.modulation.base[param as usize] {
+            return Some(base);
+        }
+        let value = match param {
+            Param::Rpm => v.rpm.target,
+            Param::Blades => v.engine.blades,
+            Param::Gain => v.gain.target,
+            Param::EngineMix => v.engine_mix.target,
+            Param::CavMix => v.cav_mix.target,
+            Param::BioMix => v.bio_mix.target,
+            Param::BioType => v.bio.bio_type as u32 as f32,
+            Param::BioRate => v.bio.bio_rate,
+            Param::ShaftRate => v.target_shaft_rate(),
+            Param::Load => v.load.target,
+            Param::RpmJitter => v.engine.rpm_jitter,
+            Param::ClassProfile => v.engine.class_profile as f32,
+            Param::CavitationLevel => v.cavitation_level.target,
+            Param::Range => v.propagation.range,
+            Param::SourceDepth => v.source_depth,
+            Param::ReverbSend => v.sends[BUS_REVERB as usize].target,
+            Param::Bearing => v.panner.bearing.to_degrees(),
+            Param::TargetStrength => v.target_strength,
+            Param::TargetLength => v.target_length,
+            Param::BearingWander => v.bearing_noise.wander.to_degrees(),
+            Param::BearingJitter => v.bearing_noise.jitter.to_degrees(),
+            Param::Elevation => v.binaural.elevation.to_degrees(),
+            Param::Variation => v.humanize.amount,
+            // Read through the linear parameter, which carries any modulation base.
+            Param::GainDb
+            | Param::EngineMixDb
+            | Param::CavMixDb
+            | Param::BioMixDb
+            | Param::ReverbSendDb => {
+                return self.get_param(voice_id, param.as_linear()?).map(linear_to_db);
+            }
+        };
+        Some(value)
+    }
+
+    pub fn voice_state(&self, voice_id: u32) -> Option<VoiceState> {
+        let v = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(VoiceState {
+            current_rpm: v.engine.current_rpm,
+            target_rpm: v.rpm.target,
+            current_shaft_rate: v.engine.current_shaft_rate,
+            target_shaft_rate: v.target_shaft_rate(),
+            bio_type: v.bio.bio_type,
+            prev_bio_type: v.bio.prev_type,
+            bio_xfade: v.bio.xfade,
+            range: v.propagation.range,
+            bearing: v.panner.apparent_bearing().rem_euclid(TWO_PI).to_degrees(),
+            doppler: v.doppler,
+            propagation_gain: v.propagation.gain(),
+            receive_gain: v.receive_gain,
+            culled: v.culled,
+        })
+    }
+
+    // Fires one call or transient now. Continuous generators have no discrete event to fire
+    // and fail with Unsupported.
+    pub fn trigger_bio_event(&mut self, voice_id: u32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        let v = &mut self.voices[idx];
+        if !v.bio.trigger_event(self.sample_rate, v.engine.current_rpm) {
+            return Err(DspError::Unsupported);
+        }
+        let bio_type = v.bio.bio_type as u32;
+        let tag = v.tag;
+        self.events
+            .push(EVENT_BIO, Some(idx), tag, self.automation.clock(), bio_type, 0.0);
+        Ok(())
+    }
+
+    // Transmits an active ping: every ranged voice returns an echo after its two-way travel
+    // time, and `reverb_level` > 0 adds the volume/surface/bottom reverberation that follows.
+    // Equal start and end frequencies give a CW pulse, different ones an LFM sweep.
+    pub fn emit_ping(&mut self, start_hz: f32, end_hz: f32, duration_s: f32, level: f32, reverb_level: f32) -> DspResult {
+        if !(start_hz.is_finite()
+            && end_hz.is_finite()
+            && duration_s.is_finite()
+            && level.is_finite()
+            && reverb_level.is_finite())
+        {
+            return Err(DspError::OutOfRange);
+        }
+        let ping = Ping {
+            start_hz: clamp(start_hz, 20.0, 40_000.0),
+            end_hz: clamp(end_hz, 20.0, 40_000.0),
+            duration_s: clamp(duration_s, 0.005, 4.0),
+            level: clamp(level, 0.0, 4.0),
+        };
+
+        for voice in &mut self.voices {
+            if !voice.active || voice.propagation.range <= 0.0 {
+                continue;
+            }
+            let track = &voice.track;
+            // Untracked voices are assumed to be presenting their beam.
+            let aspect = if track.enabled && track.vx.hypot(track.vz) > 0.1 {
+                let course = track.vx.atan2(track.vz);
+                let line_of_sight = (self.own_ship.x - track.x).atan2(self.own_ship.z - track.z);
+                course - line_of_sight
+            } else {
+                core::f32::consts::FRAC_PI_2
+            };
+            let one_way = voice.propagation.target_gain();
+            let geo = EchoGeometry {
+                range: voice.propagation.range,
+                doppler: voice.doppler,
+                aspect,
+                path_gain: one_way * one_way,
+                target_strength_db: voice.target_strength,
+                length: voice.target_length,
+            };
+            voice.echo.schedule(&ping, &geo, self.sample_rate, &mut voice.rng);
+        }
+
+        self.ping_reverb.start(
+            &ping,
+            clamp(reverb_level, 0.0, 2.0),
+            self.environment.listener_depth,
+            self.environment.water_depth,
+            self.sample_rate,
+        );
+        self.events
+            .push(EVENT_PING, None, 0, self.automation.clock(), 0, ping.level);
+        Ok(())
+    }
+
+    // Snaps the sea state immediately, cancelling any weather ramp or storm in progress.
+    pub fn set_sea_state(&mut self, sea_state: f32) {
+        if sea_state.is_finite() {
+            let mut levels = self.weather.levels();
+            levels.sea_state = sea_state;
+            self.weather.snap(levels);
+            self.environment.sea_state = self.weather.levels().sea_state;
+        }
+    }
+
+    // Glides sea state, rain (0-1) and wind (0-1) to new values over `transition_s` seconds.
+    pub fn set_weather_target(&mut self, sea_state: f32, rain: f32, wind: f32, transition_s: f32) -> DspResult {
+        ensure([sea_state, rain, wind, transition_s].iter().all(|v| v.is_finite()), DspError::OutOfRange)?;
+        let target = WeatherLevels { sea_state, rain, wind };
+        self.weather.retarget(target, transition_s, self.sample_rate);
+        Ok(())
+    }
+
+    // Builds to the given peak, holds, then abates back to the conditions at call time.
+    pub fn start_storm(
+        &mut self,
+        peak_sea_state: f32,
+        peak_rain: f32,
+        peak_wind: f32,
+        build_s: f32,
+        hold_s: f32,
+        abate_s: f32,
+    ) -> DspResult {
+        let values = [peak_sea_state, peak_rain, peak_wind, build_s, hold_s, abate_s];
+        ensure(values.iter().all(|v| v.is_finite()), DspError::OutOfRange)?;
+        let peak = WeatherLevels {
+            sea_state: peak_sea_state,
+            rain: peak_rain,
+            wind: peak_wind,
+        };
+        self.weather
+            .start_storm(peak, build_s, hold_s, abate_s, self.sample_rate);
+        Ok(())
+    }
+
+    pub fn sea_state(&self) -> f32 {
+        self.weather.levels().sea_state
+    }
+
+    pub fn rain_level(&self) -> f32 {
+        self.weather.levels().rain
+    }
+
+    pub fn wind_level(&self) -> f32 {
+        self.weather.levels().wind
+    }
+
+    pub fn set_listener_depth(&mut self, depth_m: f32) {
+        if depth_m.is_finite() {
+            self.environment.listener_depth = depth_m.max(0.0);
+        }
+    }
+
+    // Spherical spreading applies out to `transition_m`, cylindrical beyond; losses are
+    // relative to `reference_m`, where voices play at their nominal gain.
+    pub fn set_propagation(&mut self, reference_m: f32, transition_m: f32, absorption_db_per_km: f32) -> DspResult {
+        ensure([reference_m, transition_m, absorption_db_per_km].iter().all(|v| v.is_finite()), DspError::OutOfRange)?;
+        self.environment.reference_range = reference_m.max(1.0);
+        self.environment.spreading_transition = transition_m.max(1.0);
+        self.environment.absorption_db_per_km = absorption_db_per_km.max(0.0);
+        Ok(())
+    }
+
+    // Paths with source and receiver on opposite sides of the layer lose `loss_db` extra.
+    // A depth of 0 removes the layer.
+    pub fn set_layer(&mut self, depth_m: f32, loss_db: f32) -> DspResult {
+        ensure(depth_m.is_finite() && loss_db.is_finite(), DspError::OutOfRange)?;
+        self.environment.layer_depth = depth_m.max(0.0);
+        self.environment.layer_loss_db = clamp(loss_db, 0.0, 60.0);
+        Ok(())
+    }
+
+    // 0 = mud, 1 = sand, 2 = rock. Sets bottom-bounce loss and the reverb bus character.
+    pub fn set_bottom_type(&mut self, bottom_type: u32) -> DspResult {
+        let bottom = BottomType::from_id(bottom_type).ok_or(DspError::OutOfRange)?;
+        self.environment.bottom_type = bottom;
+        let (decay_scale, damping) = bottom.reverb_character();
+        self.reverb.set_bottom(decay_scale, damping);
+        Ok(())
+    }
+
+    // 0 = spreading model, 1 = eigenray model using the two-layer sound-speed profile.
+    // The ray model traces as many bounce paths as set_multipath asks for.
+    pub fn set_channel_model(&mut self, model: u32) -> DspResult {
+        let model = ChannelModel::from_id(model).ok_or(DspError::OutOfRange)?;
+        self.environment.channel_model = model;
+        Ok(())
+    }
+
+    // Sound speed (m/s) above and below the layer depth given to set_layer.
+    pub fn set_sound_speed_profile(&mut self, upper_ms: f32, lower_ms: f32) -> DspResult {
+        ensure(upper_ms.is_finite() && lower_ms.is_finite(), DspError::OutOfRange)?;
+        self.environment.sound_speed_upper = clamp(upper_ms, 1400.0, 1600.0);
+        self.environment.sound_speed_lower = clamp(lower_ms, 1400.0, 1600.0);
+        Ok(())
+    }
+
+    // Surface duct: sources and a listener both shallower than `depth_m` hear each other far
+    // beyond normal spreading, minus the leakage rate. 0 depth turns the duct off.
+    pub fn set_surface_duct(&mut self, depth_m: f32, leak_db_per_km: f32) -> DspResult {
+        ensure(depth_m.is_finite() && leak_db_per_km.is_finite(), DspError::OutOfRange)?;
+        self.environment.duct_depth = clamp(depth_m, 0.0, 500.0);
+        self.environment.duct_leak_db_per_km = clamp(leak_db_per_km, 0.0, 5.0);
+        Ok(())
+    }
+
+    // Deep-water convergence zones every `interval_m` (typically 30-60 km), each `width_m`
+    // wide at the first zone and up to `gain_db` above the shadow. 0 interval disables them.
+    pub fn set_convergence_zones(&mut self, interval_m: f32, width_m: f32, gain_db: f32) -> DspResult {
+        ensure([interval_m, width_m, gain_db].iter().all(|v| v.is_finite()), DspError::OutOfRange)?;
+        self.environment.cz_interval = clamp(interval_m, 0.0, 100000.0);
+        self.environment.cz_width = clamp(width_m, 100.0, 20000.0);
+        self.environment.cz_gain_db = clamp(gain_db, 0.0, 40.0);
+        Ok(())
+    }
+
+    // Transmission loss (dB re 1 m) at `points` evenly spaced ranges out to `max_range_m`
+    // (the first point is one step out, not zero), computed by the same model the voices are
+    // rendered with. Lets the UI draw detection-range predictions that match what is heard.
+    pub fn transmission_loss_curve(
+        &self,
+        source_depth_m: f32,
+        receiver_depth_m: f32,
+        frequency_hz: f32,
+        max_range_m: f32,
+        points: u32,
+    ) -> Vec<f32> {
+        if !(source_depth_m.is_finite()
+            && receiver_depth_m.is_finite()
+            && frequency_hz.is_finite()
+            && max_range_m.is_finite())
+            || points == 0
+        {
+            return Vec::new();
+        }
+        let mut env = self.environment;
+        env.listener_depth = clamp(receiver_depth_m, 0.0, 11_000.0);
+        let source_depth = clamp(source_depth_m, 0.0, 11_000.0);
+        let frequency = clamp(frequency_hz, 1.0, self.sample_rate * 0.5);
+        let points = points.min(4096) as usize;
+        let step = clamp(max_range_m, 1.0, 200_000.0) / points as f32;
+        (1..=points)
+            .map(|i| predicted_loss_db(step * i as f32, source_depth, frequency, &env))
+            .collect()
+    }
+
+    // Distant voices lose their top end to seawater absorption; `strength` exaggerates (>1) or
+    // softens (<1) the effect, and 0 turns it off.
+    pub fn set_absorption_filter(&mut self, strength: f32) -> DspResult {
+        ensure(strength.is_finite(), DspError::OutOfRange)?;
+        self.environment.absorption_filter = clamp(strength, 0.0, 10.0);
+        Ok(())
+    }
+
+    // Renders up to four bounce paths per ranged voice (surface, bottom, then the two
+    // double bounces); 0 turns multipath off.
+    pub fn set_multipath(&mut self, taps: u32, water_depth_m: f32) -> DspResult {
+        ensure(water_depth_m.is_finite(), DspError::OutOfRange)?;
+        self.environment.multipath_taps = (taps as usize).min(MAX_MULTIPATH_TAPS);
+        self.environment.water_depth = water_depth_m.max(1.0);
+        self.depth_target = self.environment.water_depth;
+        Ok(())
+    }
+
+    // Glides the water depth to `depth_m` over roughly `transition_s` seconds, moving the
+    // bottom bounces and reverb tail with it. A loaded bathymetry grid takes precedence.
+    pub fn set_water_depth(&mut self, depth_m: f32, transition_s: f32) -> DspResult {
+        ensure(depth_m.is_finite() && transition_s.is_finite(), DspError::OutOfRange)?;
+        self.depth_target = clamp(depth_m, 1.0, 11_000.0);
+        self.depth_glide_s = clamp(transition_s, 0.0, 600.0);
+        Ok(())
+    }
+
+    // Uploads a row-major depth grid (metres, positive down) with `cols` columns of
+    // `cell_m`-sized cells starting at (`origin_x`, `origin_z`). The water depth then follows
+    // the grid under own-ship, gliding over `transition_s`.
+    pub fn set_bathymetry(
+        &mut self,
+        origin_x: f32,
+        origin_z: f32,
+        cell_m: f32,
+        cols: u32,
+        depths: &[f32],
+        transition_s: f32,
+    ) -> DspResult {
+        let cols = cols as usize;
+        if !(origin_x.is_finite() && origin_z.is_finite() && cell_m.is_finite() && transition_s.is_finite())
+            || cols == 0
+            || depths.len() < cols
+        {
+            return Err(DspError::OutOfRange);
+        }
+        self.bathymetry.load(origin_x, origin_z, cell_m, cols, depths);
+        self.depth_glide_s = clamp(transition_s, 0.0, 600.0);
+        Ok(())
+    }
+
+    pub fn clear_bathymetry(&mut self) {
+        self.bathymetry.clear();
+    }
+
+    pub fn water_depth(&self) -> f32 {
+        self.environment.water_depth
+    }
+
+    // `decay_s` is the low-frequency T60 of the shared reverb bus; `damping` (0-1) makes the
+    // high end die faster; `return_level` scales the wet signal added to the master.
+    pub fn set_reverb(&mut self, decay_s: f32, damping: f32, return_level: f32) -> DspResult {
+        ensure([decay_s, damping, return_level].iter().all(|v| v.is_finite()), DspError::OutOfRange)?;
+        self.reverb.configure(decay_s, damping, return_level);
+        Ok(())
+    }
+
+    // Own-ship position (m), velocity (m/s) and heading (degrees clockwise from north) in
+    // model space (+X east, +Z north). Position is dead-reckoned from velocity every block.
+    pub fn set_own_ship(&mut self, x: f32, z: f32, vx: f32, vz: f32, heading_deg: f32) -> DspResult {
+        let values = [x, z, vx, vz, heading_deg];
+        ensure(values.iter().all(|v| v.is_finite()), DspError::OutOfRange)?;
+        self.own_ship = OwnShip {
+            x,
+            z,
+            vx,
+            vz,
+            heading: heading_deg.rem_euclid(360.0).to_radians(),
+        };
+        Ok(())
+    }
+
+    // Puts a voice under kinematic control: range, bearing and Doppler are then derived every
+    // block from its track and own-ship's, overriding Param::Range and Param::Bearing.
+    pub fn set_voice_kinematics(&mut self, voice_id: u32, x: f32, z: f32, vx: f32, vz: f32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        ensure([x, z, vx, vz].iter().all(|v| v.is_finite()), DspError::OutOfRange)?;
+        self.voices[idx].track = Track {
+            enabled: true,
+            x,
+            z,
+            vx,
+            vz,
+        };
+        Ok(())
+    }
+
+    // Returns the voice to manual range/bearing control with no Doppler.
+    pub fn clear_voice_kinematics(&mut self, voice_id: u32) -> DspResult {
+        let idx = self.voice_index(voice_id)?;
+        let v = &mut self.voices[idx];
+        v.track.enabled = false;
+        v.doppler = 1.0;
+        Ok(())
+    }
+
+    // Own-ship self-noise on our hydrophones. Speed comes from set_own_ship's velocity;
+    // `machinery` (0-1) is plant load. A `level` of 0 disables it.
+    pub fn set_self_noise(&mut self, level: f32, machinery: f32) -> DspResult {
+        ensure(level.is_finite() && machinery.is_finite(), DspError::OutOfRange)?;
+        self.self_noise.configure(level, machinery);
+        Ok(())
+    }
+
+    // Broadband self-noise over the last block in dB re full scale, for the host's SNR model.
+    pub fn self_noise_db(&self) -> f32 {
+        self.self_noise.broadband_db()
+    }
+
+    // Sector 0 is centred on north, numbering runs clockwise in 45-degree steps.
+    pub fn set_noise_sector(&mut self, sector: u32, level: f32, tilt: f32) -> DspResult {
+        ensure(self.noise_field.set_sector(sector as usize, level, tilt), DspError::OutOfRange)
+    }
+
+    pub fn noise_sector_count(&self) -> u32 {
+        NOISE_SECTORS as u32
+    }
+
+    // Width of 360 degrees (the default) listens omnidirectionally.
+    pub fn set_look_direction(&mut self, bearing_deg: f32, width_deg: f32) {
+        if bearing_deg.is_finite() && width_deg.is_finite() {
+            self.noise_field
+                .set_look(bearing_deg.to_radians(), width_deg.to_radians());
+        }
+    }
+
+    // Returns a pointer into WASM memory to the graph output buffer.
+    // Read `output_len()` samples from this address. In stereo mode this is the left
+    // channel; the right channel follows at `channel_ptr(1)`.
+    // Every output buffer is double-buffered: a block stays readable at the pointers handed
+    // out for it while the next process() call runs, and only the call after that reuses its
+    // memory. Pointers alternate between the two, so fetch them again after each call.
+    pub fn process(&mut self, frames: usize) -> usize {
+        let n = frames.min(self.max_frames);
+        self.last_frames = n;
+        self.flip_outputs();
+        if self.paused {
+            return self.silent_block();
+        }
+        let control = self.profile.start();
+        // Ahead of everything else, so voices these add render from their start frame. Neither
+        // is host input, so neither is recorded.
+        let recording = self.recorder.suspend();
+        self.run_replay(n);
+        self.run_scenario(n);
+        self.recorder.resume(recording);
+        let stereo = self.channels == 2;
+
+        for sample in self.output.iter_mut() {
+            *sample = 0.0;
+        }
+        for bus in 0..BUS_COUNT {
+            let start = bus * self.max_frames;
+            self.bus_send[start..start + n].iter_mut().for_each(|s| *s = 0.0);
+        }
+
+        // Sea state only moves over minutes, so block-rate hand-off to the voices is smooth.
+        self.environment.sea_state = self.weather.levels().sea_state;
+
+        let dt = n as f32 / self.sample_rate;
+        self.own_ship.advance(dt);
+
+        let depth_target = self
+            .bathymetry
+            .depth_at(self.own_ship.x, self.own_ship.z)
+            .unwrap_or(self.depth_target);
+        let glide = if self.depth_glide_s > 0.0 {
+            1.0 - (-dt / self.depth_glide_s).exp()
+        } else {
+            1.0
+        };
+        self.environment.water_depth += glide * (depth_target - self.environment.water_depth);
+        self.reverb
+            .set_depth_scale(depth_reverb_scale(self.environment.water_depth));
+
+        let clock = self.automation.clock();
+        for (idx, voice) in self.voices.iter_mut().enumerate() {
+            if !voice.is_sounding() {
+                continue;
+            }
+            if let Some(offset) = voice.echo.arrival_within(n) {
+                self.events
+                    .push(EVENT_TRANSIENT, Some(idx), voice.tag, clock + offset as u64, 0, 0.0);
+            }
+            voice.modulate(dt, n as u32);
+            if let Some(derived) = voice.track.advance(&self.own_ship, dt) {
+                voice.propagation.range = derived.range.max(1.0);
+                voice.panner.bearing = derived.bearing;
+                voice.doppler = derived.doppler;
+            }
+            voice.panner.error = voice.bearing_noise.advance(dt);
+            voice
+                .propagation
+                .update(voice.source_depth, &self.environment, self.sample_rate);
+            voice.multipath.update(
+                &voice.propagation,
+                voice.source_depth,
+                &self.environment,
+                self.sample_rate,
+            );
+        }
+
+        self.automation.begin_block(n);
+        for (at, event) in self.automation.due() {
+            let tag = self.voices[event.voice].tag;
+            self.events
+                .push(EVENT_AUTOMATION, Some(event.voice), tag, at, event.param as u32, event.value);
+        }
+        self.profile.record(ProfileStage::Control, control, n);
+        if self.towed_array.elements > 0 {
+            self.process_array(n);
+            return self.finish_block(n);
+        }
+        if self.channels > 2 {
+            self.process_ring(n);
+            return self.finish_block(n);
+        }
+
+        let layout = if self.binaural {
+            VoiceLayout::Binaural
+        } else if stereo {
+            VoiceLayout::Stereo
+        } else {
+            VoiceLayout::Mono
+        };
+        self.mix_voices(n, layout);
+        let (left, right) = self.output.split_at_mut(self.max_frames);
+        let (left, right) = (&mut left[..n], &mut right[..if stereo { n } else { 0 }]);
+
+        let buses = self.profile.start();
+        let (reverb_send, hull_send) = bus_sends(&self.bus_send, self.max_frames, n);
+        self.reverb.process(reverb_send, left, right);
+        if self.hull_filter.is_active() {
+            let buf = &mut self.scratch[..n];
+            buf.iter_mut().for_each(|s| *s = 0.0);
+            self.hull_filter.process(hull_send, buf);
+            for (i, s) in buf.iter().enumerate() {
+                left[i] += s;
+                if stereo {
+                    right[i] += s;
+                }
+            }
+        }
+        self.profile.record(ProfileStage::Buses, buses, n);
+
+        let ambience = self.profile.start();
+        if self.self_noise.is_active() {
+            let speed = self.own_ship.vx.hypot(self.own_ship.vz);
+            let buf = &mut self.scratch[..n];
+            buf.iter_mut().for_each(|s| *s = 0.0);
+            self.self_noise
+                .render(buf, speed, self.environment.listener_depth, self.sample_rate);
+            for (i, s) in buf.iter().enumerate() {
+                left[i] += s;
+                if stereo {
+                    right[i] += s;
+                }
+            }
+        }
+
+        if self.noise_field.is_active() {
+            let filters = DirectionalNoiseField::filters(self.sample_rate);
+            if stereo {
+                let pan = DirectionalNoiseField::stereo_pan(self.own_ship.heading);
+                for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+                    let (nl, nr) = self.noise_field.tick_stereo(&pan, filters);
+                    *l += nl;
+                    *r += nr;
+                }
+            } else {
+                for sample in left.iter_mut() {
+                    *sample += self.noise_field.tick(filters);
+                }
+            }
+        }
+
+        if self.weather.is_audible() {
+            for i in 0..n {
+                let bed = self.weather.tick(self.sample_rate);
+                left[i] += bed;
+                if stereo {
+                    right[i] += bed;
+                }
+            }
+        } else {
+            self.weather.skip(n, self.sample_rate);
+        }
+
+        if self.ping_reverb.is_active() {
+            for i in 0..n {
+                let rev = self.ping_reverb.tick(self.sample_rate);
+                left[i] += rev;
+                if stereo {
+                    right[i] += rev;
+                }
+            }
+        }
+        self.profile.record(ProfileStage::Ambience, ambience, n);
+
+        self.master_chain(n);
+        self.finish_block(n)
+    }
+
+    /// Renders like process() but writes the block to `ptr`, a host-owned region of this
+    /// module's memory (see alloc_f32_buffer) with room for output_channels() * frames floats.
+    /// Planar channels are packed back to back, so a single view covers them all however the
+    /// memory has grown; in interleaved mode the frames follow one another as usual. Returns
+    /// the frames written: `frames`, capped at max_frames().
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must address output_channels() * frames writable floats, such as a live
+    /// alloc_f32_buffer block, that nothing else reads or writes during the call.
+    pub unsafe fn process_into(&mut self, ptr: usize, frames: usize) -> DspResult<usize> {
+        check_host_ptr(ptr)?;
+        self.process(frames);
+        let n = self.last_frames;
+        if n == 0 {
+            return Ok(0);
+        }
+        // SAFETY: n never exceeds frames, so this stays within what the caller vouched for.
+        let out = unsafe { host_floats(ptr, self.channels * n)? };
+        if self.interleaved {
+            out.copy_from_slice(&self.interleaved_output[..self.channels * n]);
+        } else {
+            for (k, channel) in out.chunks_exact_mut(n).enumerate() {
+                channel.copy_from_slice(&self.output[k * self.max_frames..][..n]);
+            }
+        }
+        Ok(n)
+    }
+
+    // Freezes the graph: process() keeps returning silent blocks, but oscillator phases,
+    // generator timers, weather, own-ship's dead reckoning, reverb tails and the sample clock
+    // all stand still, so scheduled automation and bio events resume exactly where they left
+    // off. Audio written to voice inputs while paused is dropped. Output stops and restarts
+    // without a fade.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    // Interleaved output hands process(), output_ptr() and output_copy() one run of
+    // output_len() * output_channels() samples, frame by frame, for WAV export and native
+    // backends. Planar (the default) is what Web Audio wants. channel_ptr() and
+    // channel_copy() stay planar either way.
+    pub fn set_interleaved_output(&mut self, enabled: bool) {
+        self.interleaved = enabled;
+        self.last_frames = 0;
+    }
+
+    pub fn interleaved_output(&self) -> bool {
+        self.interleaved
+    }
+
+    // Also renders each block as TPDF-dithered 16-bit PCM, for recording, streaming or
+    // lighter transfers. It follows the output layout, except that planar channels are packed
+    // back to back (output_len() frames each), so pcm16_copy() always holds every channel.
+    pub fn set_pcm16_output(&mut self, enabled: bool) {
+        self.pcm16 = enabled;
+        self.last_frames = 0;
+    }
+
+    pub fn pcm16_output(&self) -> bool {
+        self.pcm16
+    }
+
+    // output_len() * output_channels() samples of the last block; empty while 16-bit output
+    // is off.
+    pub fn pcm16_ptr(&self) -> usize {
+        self.pcm16_output.as_ptr() as usize
+    }
+
+    pub fn pcm16_copy(&self) -> Vec<i16> {
+        if !self.pcm16 {
+            return Vec::new();
+        }
+        self.pcm16_output[..self.last_frames * self.channels].to_vec()
+    }
+
+    pub fn set_stereo_output(&mut self, enabled: bool) {
+        self.towed_array.configure(0, 0.0, 0.0);
+        if !enabled {
+            self.binaural = false;
+        }
+        self.set_channel_count(if enabled { 2 } else { 1 });
+    }
+
+    // Master limiter on every output channel: threshold in dBFS (-24..0) with a soft knee (6 dB
+    // unless set_limiter_shape changes it) and 1.5 ms lookahead, release in ms. Disabling it
+    // passes the mix through unlimited, and undriven.
+    pub fn set_limiter(&mut self, enabled: bool, threshold_db: f32, release_ms: f32) -> DspResult {
+        ensure(threshold_db.is_finite() && release_ms.is_finite(), DspError::OutOfRange)?;
+        self.limiter.enabled = enabled;
+        self.limiter
+            .configure(self.sample_rate, threshold_db, release_ms);
+        Ok(())
+    }
+
+    // How the limiter bends the mix: drive_db (-24..24) scales the mix on its way in, so a
+    // dense scene can be backed off rather than squeezed, and knee_db (0..24) widens the soft
+    // knee around the threshold, with 0 limiting hard at it. Defaults are 0 dB drive and a
+    // 6 dB knee.
+    pub fn set_limiter_shape(&mut self, drive_db: f32, knee_db: f32) -> DspResult {
+        ensure(drive_db.is_finite() && knee_db.is_finite(), DspError::OutOfRange)?;
+        self.limiter.shape(drive_db, knee_db);
+        Ok(())
+    }
+
+    pub fn limiter_drive_db(&self) -> f32 {
+        self.limiter.drive_db()
+    }
+
+    pub fn limiter_knee_db(&self) -> f32 {
+        self.limiter.knee_db()
+    }
+
+    // Stops rendering voices too quiet to hear. A voice whose gains, propagation loss, hull
+    // directivity and group level keep it below threshold_db (dBFS, -160..-40) fades out over
+    // 10 ms and is skipped until it is 6 dB back over the threshold, when it fades back in. A
+    // skipped voice still follows its automation and cues, but any bio event due meanwhile is
+    // lost. Off by default.
+    pub fn set_voice_culling(&mut self, enabled: bool, threshold_db: f32) -> DspResult {
+        ensure(threshold_db.is_finite(), DspError::OutOfRange)?;
+        self.cull_threshold = if enabled {
+            10.0f32.powf(clamp(threshold_db, -160.0, -40.0) / 20.0)
+        } else {
+            0.0
+        };
+        Ok(())
+    }
+
+    // Computes the waveforms of the inherently low-frequency generators (the engine, whale
+    // moans and blue and fin whale calls) at about 12 kHz and interpolates them up, which
+    // cuts the engine stage to about a third of its cost. The result stays 45 dB or more from
+    // full rate, the difference being mostly in the hard onset of a call. Their phases,
+    // envelopes and events still run at the full rate, so cavitation stays locked to the
+    // shaft. The vent's rumble and the ambient noise field's low band are left out: each is
+    // filtered from the same white noise as a full-band hiss in the same generator, so the
+    // filters have to see every sample anyway, and the rumble is just two one-pole stages, no
+    // dearer than the interpolation would be. Off by default.
+    pub fn set_low_rate_sources(&mut self, enabled: bool) {
+        self.low_rate_sources = enabled;
+    }
+
+    pub fn low_rate_sources(&self) -> bool {
+        self.low_rate_sources
+    }
+
+    // Keeps the engines' spectra below the Nyquist frequency, so high RPM at a low sample rate
+    // doesn't fold inharmonic lines across the LOFAR display. Shaft, blade and machinery
+    // partials fade out as they near it, and the saturating waveshapers are anti-aliased. The
+    // anti-aliasing rolls the engine off toward Nyquist, 3 dB down at half of it, which at
+    // 44.1 kHz and up leaves every engine line within a fraction of a dB. With low-rate
+    // rendering on, the limit is that of the rate the waveform is computed at. Off by default.
+    pub fn set_band_limited_engines(&mut self, enabled: bool) {
+        self.band_limited_engines = enabled;
+    }
+
+    pub fn band_limited_engines(&self) -> bool {
+        self.band_limited_engines
+    }
+
+    // A voice's level as it enters the mix, after propagation, hull directivity and its group
+    // gain. Reading resets the window, so each caller sees the levels since its last read.
+    pub fn meter(&mut self, voice_id: u32) -> Option<Meter> {
+        let voice = self.voices.get_mut(voice_id as usize).filter(|v| v.active)?;
+        let (peak, rms) = voice.meter.take();
+        Some(Meter { peak, rms })
+    }
+
+    // Level of the final output across every channel, after the limiter.
+    pub fn master_meter(&mut self) -> Meter {
+        let (peak, rms) = self.master_meter.take();
+        Meter { peak, rms }
+    }
+
+    pub fn limiter_reduction_db(&self) -> f32 {
+        self.limiter.reduction_db()
+    }
+
+    // Reading resets the counts, so a host polling once a second sees each second's mix.
+    pub fn headroom_report(&mut self) -> HeadroomReport {
+        let headroom = self.headroom.take();
+        HeadroomReport {
+            blocks: headroom.blocks,
+            limited_blocks: headroom.limited_blocks,
+            clipped_blocks: headroom.clipped_blocks,
+            clipped_samples: headroom.clipped_samples,
+            peak_db: 20.0 * headroom.peak.max(1.0e-6).log10(),
+            max_reduction_db: (-20.0 * headroom.min_gain.max(1.0e-6).log10()).max(0.0),
+        }
+    }
+
+    // What the graph has allocated, for budgeting memory growth. Analysers the host keeps
+    // alongside report their own with memory_bytes(), and wasm_memory_bytes() gives the size
+    // the module's memory has grown to in all.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let voices =
+            vec_bytes(&self.voices) + self.voices.iter().map(Voice::heap_bytes).sum::<usize>();
+        #[cfg(feature = "threads")]
+        let partial_mixes = vec_bytes(&self.partial_mixes)
+            + self.partial_mixes.iter().map(PartialMix::heap_bytes).sum::<usize>();
+        #[cfg(not(feature = "threads"))]
+        let partial_mixes = 0;
+        let buffers = vec_bytes(&self.output)
+            + vec_bytes(&self.bus_send)
+            + vec_bytes(&self.hydrophone_output)
+            + vec_bytes(&self.scratch)
+            + self.lanes.heap_bytes()
+            + partial_mixes
+            + vec_bytes(&self.interleaved_output)
+            + vec_bytes(&self.pcm16_output)
+            + self.spare_outputs.heap_bytes()
+            + self.analysis.heap_bytes();
+        let history =
+            self.events.heap_bytes() + self.recorder.heap_bytes() + self.replay.heap_bytes();
+        let scene = core::mem::size_of::<Self>()
+            + self.automation.heap_bytes()
+            + self.scenario.heap_bytes()
+            + self.controls.heap_bytes()
+            + self.reverb.heap_bytes()
+            + self.dc_blocker.heap_bytes()
+            + self.operator_band.heap_bytes()
+            + self.limiter.heap_bytes()
+            + self.bathymetry.heap_bytes()
+            + self.self_noise_delay.heap_bytes()
+            + vec_bytes(&self.sector_delays)
+            + self.sector_delays.iter().map(ArrayDelay::heap_bytes).sum::<usize>();
+        MemoryUsage {
+            voices,
+            buffers,
+            history,
+            scene,
+            total: voices + buffers + history + scene,
+        }
+    }
+
+    // Asks for the DEMON spectrum of `input` at the graph's sample rate (see DemonAnalyzer),
+    // worked out a slice per process() call within the analysis budget, so a long recording
+    // never costs one callback more than that. Poll demon_spectrum_complete(); until it
+    // reports true the spectrum reads zero. A new request replaces one still under way.
+    pub fn request_demon_spectrum(
+        &mut self,
+        input: &[f32],
+        max_freq_hz: u32,
+        input_band_low_hz: f32,
+        input_band_high_hz: f32,
+        envelope_hp_hz: f32,
+        decimated_rate_target_hz: f32,
+    ) {
+        self.analysis.begin(
+            input,
+            self.sample_rate,
+            max_freq_hz,
+            input_band_low_hz,
+            input_band_high_hz,
+            envelope_hp_hz,
+            decimated_rate_target_hz,
+        );
+    }
+
+    pub fn demon_spectrum_complete(&self) -> bool {
+        self.analysis.is_complete()
+    }
+
+    // Why the requested spectrum reads zero, if it does; Pending while it is being worked out.
+    pub fn demon_spectrum_status(&self) -> DemonStatus {
+        self.analysis.status()
+    }
+
+    // Least input, in seconds at the graph's rate, the request's settings can analyse.
+    pub fn demon_min_input_seconds(&self) -> f32 {
+        self.analysis.min_input_seconds()
+    }
+
+    // The requested spectrum: demon_spectrum_len() floats, bin f at f Hz. Valid until the
+    // next request.
+    pub fn demon_spectrum_ptr(&self) -> usize {
+        self.analysis.spectrum_ptr()
+    }
+
+    pub fn demon_spectrum_len(&self) -> usize {
+        self.analysis.spectrum_len()
+    }
+
+    // Work a requested spectrum may take per process() call: about one unit per input sample
+    // for each of its two passes over the recording, then the decimated envelope's length
+    // (about 1% of the input at the default rates) per bin. Fails with OutOfRange for 0.
+    pub fn set_analysis_budget(&mut self, units: u32) -> DspResult {
+        ensure(units > 0, DspError::OutOfRange)?;
+        self.analysis_budget = units;
+        Ok(())
+    }
+
+    pub fn analysis_budget(&self) -> u32 {
+        self.analysis_budget
+    }
+
+    // Slow loudness normalisation ahead of the limiter, so quiet biologic scenes and loud
+    // convoys land at comparable levels: a 3 s mean-square level (dBFS, unweighted) is steered
+    // toward target_db (-40..-6) at no more than 2 dB/s, boosting by at most max_boost_db and
+    // cutting by at most max_cut_db (each 0..24). Off by default.
+    pub fn set_loudness_normalization(&mut self, enabled: bool, target_db: f32, max_boost_db: f32, max_cut_db: f32) -> DspResult {
+        let finite = [target_db, max_boost_db, max_cut_db]
+            .iter()
+            .all(|v| v.is_finite());
+        ensure(finite, DspError::OutOfRange)?;
+        self.leveler.enabled = enabled;
+        self.leveler.configure(target_db, max_boost_db, max_cut_db);
+        Ok(())
+    }
+
+    pub fn loudness_gain_db(&self) -> f32 {
+        self.leveler.gain_db()
+    }
+
+    // Band-limits the operator output (what process() returns) to low_hz..high_hz with
+    // 12 dB/oct slopes, ahead of loudness normalisation and the limiter. The high corner is
+    // held to 20 Hz..0.45x the sample rate and the low corner to 10 Hz..half the high one.
+    // Off by default; 100 Hz to 8 kHz when first enabled.
+    pub fn set_operator_band(&mut self, enabled: bool, low_hz: f32, high_hz: f32) -> DspResult {
+        ensure(low_hz.is_finite() && high_hz.is_finite(), DspError::OutOfRange)?;
+        self.operator_band.enabled = enabled;
+        self.operator_band.configure(low_hz, high_hz);
+        Ok(())
+    }
+
+    pub fn operator_band_low_hz(&self) -> f32 {
+        self.operator_band.corners().0
+    }
+
+    pub fn operator_band_high_hz(&self) -> f32 {
+        self.operator_band.corners().1
+    }
+
+    // A second master output rendered in the same pass: the full hydrophone mix, wideband and
+    // unprocessed, taken just before the DC blocker, operator band, loudness normaliser and
+    // limiter. For analysis displays, alongside the operator audio process() returns. Planar in
+    // the output channel layout whatever set_interleaved_output says, and silent while paused.
+    pub fn set_hydrophone_output(&mut self, enabled: bool) {
+        self.hydrophone = enabled;
+        self.size_hydrophone_output();
+    }
+
+    pub fn hydrophone_output(&self) -> bool {
+        self.hydrophone
+    }
+
+    // Channel `channel` of the last block's hydrophone mix, output_len() samples; 0 while the
+    // mix is off. Channels are max_frames() apart. Invalid once the channel count or block
+    // size changes, or the mix is switched.
+    pub fn hydrophone_ptr(&self, channel: usize) -> usize {
+        if self.hydrophone_output.is_empty() {
+            return 0;
+        }
+        let ch = channel.min(self.channels - 1);
+        self.hydrophone_output[ch * self.max_frames..].as_ptr() as usize
+    }
+
+    pub fn hydrophone_copy(&self, channel: usize) -> Vec<f32> {
+        if channel >= self.channels || self.hydrophone_output.is_empty() {
+            return Vec::new();
+        }
+        let start = channel * self.max_frames;
+        self.hydrophone_output[start..start + self.last_frames].to_vec()
+    }
+
+    // Output channel count: 1 is mono, 2 the stereo pair, and 3 up to max_ring_channels() a
+    // ring of equally spaced speakers starting dead ahead and running clockwise. Leaves
+    // towed-array and binaural rendering.
+    pub fn set_output_channels(&mut self, channels: u32) -> DspResult {
+        let channels = channels as usize;
+        ensure((1..=MAX_RING_CHANNELS).contains(&channels), DspError::OutOfRange)?;
+        self.towed_array.configure(0, 0.0, 0.0);
+        self.binaural = false;
+        self.set_channel_count(channels);
+        Ok(())
+    }
+
+    pub fn max_ring_channels(&self) -> u32 {
+        MAX_RING_CHANNELS as u32
+    }
+
+    // Renders one planar channel per hydrophone of a line array towed astern, with per-element
+    // delays from each voice's bearing and independent flow noise. 0 elements returns to mono.
+    pub fn set_towed_array(&mut self, elements: u32, spacing_m: f32, flow_level: f32) -> DspResult {
+        let valid = elements as usize <= MAX_ARRAY_ELEMENTS && spacing_m.is_finite() && flow_level.is_finite();
+        ensure(valid, DspError::OutOfRange)?;
+        self.towed_array.configure(elements as usize, spacing_m, flow_level);
+        self.set_channel_count((elements as usize).max(1));
+        Ok(())
+    }
+
+    pub fn max_array_elements(&self) -> u32 {
+        MAX_ARRAY_ELEMENTS as u32
+    }
+
+    // Hull receiver pattern: 0 = omni, 1 = stern baffles `width_deg` wide, 2 = cardioid.
+    // `loss_db` is the attenuation inside the baffles, or at the cardioid's null. The towed
+    // array has no baffles and ignores this.
+    pub fn set_receiver_directivity(&mut self, pattern: u32, width_deg: f32, loss_db: f32) -> DspResult {
+        let pattern = ReceiverPattern::from_id(pattern).ok_or(DspError::OutOfRange)?;
+        ensure(width_deg.is_finite() && loss_db.is_finite(), DspError::OutOfRange)?;
+        self.directivity.configure(pattern, width_deg.to_radians(), loss_db);
+        Ok(())
+    }
+
+    // Headphone rendering with head shadow, ITD and pinna cues from each voice's bearing and
+    // elevation. Turns stereo output on; turning stereo off also ends binaural mode.
+    pub fn set_binaural(&mut self, enabled: bool) {
+        if enabled {
+            self.set_stereo_output(true);
+        }
+        self.binaural = enabled;
+    }
+
+    // Which way the listener faces relative to the bow (degrees clockwise), so a sonar
+    // operator can turn their head. Own-ship heading itself comes from set_own_ship.
+    pub fn set_listener_yaw(&mut self, yaw_deg: f32) -> DspResult {
+        ensure(yaw_deg.is_finite(), DspError::OutOfRange)?;
+        self.listener_yaw = yaw_deg.rem_euclid(360.0).to_radians();
+        Ok(())
+    }
 
-        for sample in &mut self.output[..n] {
-            *sample = 0.0;
-        }
+    // Adds a sub-millisecond interaural delay on the far ear in stereo mode.
+    pub fn set_itd(&mut self, enabled: bool) {
+        self.itd_enabled = enabled;
+    }
 
-        for voice in &mut self.voices {
-            if !voice.active {
-                continue;
-            }
-            for i in 0..n {
-                self.output[i] += voice.sample(self.sample_rate);
-            }
-        }
+    pub fn output_channels(&self) -> usize {
+        self.channels
+    }
 
-        for i in 0..n {
-            self.output[i] = self.output[i].tanh();
-        }
+    // Channels are planar: each holds `output_len()` samples, `max_frames()` apart.
+    pub fn channel_ptr(&self, channel: usize) -> usize {
+        let ch = channel.min(self.channels - 1);
+        self.output[ch * self.max_frames..].as_ptr() as usize
+    }
 
-        self.output.as_ptr() as usize
+    pub fn channel_copy(&self, channel: usize) -> Vec<f32> {
+        if channel >= self.channels {
+            return Vec::new();
+        }
+        let start = channel * self.max_frames;
+        self.output[start..start + self.last_frames].to_vec()
     }
 
     pub fn record_process_ms(&mut self, elapsed_ms: f64) {
@@ -1608,90 +5466,1021 @@ impl DspGraph {
         self.process_call_count
     }
 
+    // Reseeds every random source in the graph (ambient fields, weather, self-noise, array
+    // flow noise, ping reverberation and each voice) and the seeds handed to voices added
+    // later. The same seed and the same parameter timeline from here on render identical
+    // output; set it before adding voices to make a whole session reproducible.
+    pub fn set_seed(&mut self, seed: u32) {
+        self.seed = seed;
+        self.reseed();
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
     pub fn reset_process_stats(&mut self) {
         self.process_call_count = 0;
         self.process_total_ms = 0.0;
         self.process_max_ms = 0.0;
     }
 
+    // Times each stage of process() from the inside, for stage_profile(). Off by default, and
+    // only available in builds with the profile feature: without it enabling fails with
+    // Unsupported, as it does in wasm when the scope has no performance.now().
+    pub fn set_profiling(&mut self, enabled: bool) -> DspResult {
+        ensure(self.profile.set_enabled(enabled), DspError::Unsupported)
+    }
+
+    // Time, frames and calls spent in one stage since profiling was enabled or last reset.
+    pub fn stage_profile(&self, stage: ProfileStage) -> StageProfile {
+        self.profile.stage(stage)
+    }
+
+    pub fn reset_stage_profile(&mut self) {
+        self.profile.reset();
+    }
+
+    // Send level (0..1) from a voice into a return bus. The reverb bus is the same send as
+    // Param::ReverbSend; both ramp over the reverb-send ramp time.
+    pub fn set_voice_send(&mut self, voice_id: u32, bus: u32, level: f32) -> DspResult {
+        ensure((bus as usize) < BUS_COUNT && level.is_finite(), DspError::OutOfRange)?;
+        let ramp = (self.ramp_ms[Param::ReverbSend as usize] * 0.001 * self.sample_rate) as u32;
+        let voice = self
+            .voices
+            .get_mut(voice_id as usize)
+            .filter(|v| v.active)
+            .ok_or(DspError::InvalidVoice)?;
+        voice.sends[bus as usize].set(clamp(level, 0.0, 1.0), ramp);
+        Ok(())
+    }
+
+    pub fn voice_send(&self, voice_id: u32, bus: u32) -> Option<f32> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        voice.sends.get(bus as usize).map(|send| send.target)
+    }
+
+    // Hull-filter return: a resonant low-pass (cutoff in Hz, resonance 0..0.95) heard on every
+    // output channel. A return level of 0 switches the bus off.
+    pub fn set_hull_filter(&mut self, cutoff_hz: f32, resonance: f32, return_level: f32) -> DspResult {
+        ensure([cutoff_hz, resonance, return_level].iter().all(|v| v.is_finite()), DspError::OutOfRange)?;
+        self.hull_filter.configure(cutoff_hz, resonance, return_level);
+        Ok(())
+    }
+
+    // Modulation source `source` (0 to mod_source_count() - 1) of a voice: 0 = sine LFO,
+    // 1 = triangle LFO, 2 = random walk. For the walk, the rate is how fast it decorrelates.
+    // Sources 0 and 1 start as sines and 2 and 3 as random walks, all at 0.05 Hz.
+    pub fn set_mod_source(&mut self, voice_id: u32, source: u32, shape: u32, rate_hz: f32) -> DspResult {
+        let shape = ModShape::from_id(shape).ok_or(DspError::OutOfRange)?;
+        ensure((source as usize) < MOD_SOURCES && rate_hz.is_finite(), DspError::OutOfRange)?;
+        let voice = self
+            .voices
+            .get_mut(voice_id as usize)
+            .filter(|v| v.active)
+            .ok_or(DspError::InvalidVoice)?;
+        voice
+            .modulation
+            .configure_source(source as usize, shape, rate_hz);
+        Ok(())
+    }
+
+    // Routes a source onto a parameter in one of the voice's route slots, replacing whatever
+    // the slot held. `depth` is the peak offset in the parameter's own units. Discrete
+    // parameters (blades, bio type, class profile) can't be modulated, nor can the dB
+    // variants; route onto the linear parameter instead.
+    pub fn set_mod_route(&mut self, voice_id: u32, slot: u32, source: u32, param: Param, depth: f32) -> DspResult {
+        let valid = (slot as usize) < MAX_MOD_ROUTES && (source as usize) < MOD_SOURCES && depth.is_finite();
+        ensure(valid, DspError::OutOfRange)?;
+        ensure(!param.is_discrete() && param.as_linear().is_none(), DspError::InvalidParam)?;
+        let base = self.get_param(voice_id, param).ok_or(DspError::InvalidVoice)?;
+        self.clear_mod_route(voice_id, slot)?;
+        let modulation = &mut self.voices[voice_id as usize].modulation;
+        modulation.routes[slot as usize] = Some(ModRoute {
+            source: source as usize,
+            param,
+            depth,
+        });
+        modulation.base[param as usize].get_or_insert(base);
+        Ok(())
+    }
+
+    // Empties a route slot; an empty slot is left as it is. A parameter left with no routes
+    // settles back on its base value.
+    pub fn clear_mod_route(&mut self, voice_id: u32, slot: u32) -> DspResult {
+        let voice = self
+            .voices
+            .get_mut(voice_id as usize)
+            .filter(|v| v.active)
+            .ok_or(DspError::InvalidVoice)?;
+        let route = voice
+            .modulation
+            .routes
+            .get_mut(slot as usize)
+            .ok_or(DspError::OutOfRange)?;
+        let Some(route) = route.take() else {
+            return Ok(());
+        };
+        if !voice.modulation.has_route(route.param) {
+            if let Some(base) = voice.modulation.base[route.param as usize].take() {
+                let ramp = (self.ramp_ms[route.param as usize] * 0.001 * self.sample_rate) as u32;
+                voice.apply_param(route.param, base, ramp);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn mod_source_count(&self) -> u32 {
+        MOD_SOURCES as u32
+    }
+
+    pub fn max_mod_routes(&self) -> u32 {
+        MAX_MOD_ROUTES as u32
+    }
+
+    // Moves a voice into a mix group (0 to max_groups() - 1); new voices start in group 0.
+    pub fn set_voice_group(&mut self, voice_id: u32, group: u32) -> DspResult {
+        let group = group as usize;
+        ensure(group < MAX_GROUPS, DspError::OutOfRange)?;
+        let voice = self
+            .voices
+            .get_mut(voice_id as usize)
+            .filter(|v| v.active)
+            .ok_or(DspError::InvalidVoice)?;
+        voice.group = group;
+        Ok(())
+    }
+
+    pub fn voice_group(&self, voice_id: u32) -> Option<u32> {
+        let voice = self.voices.get(voice_id as usize).filter(|v| v.active)?;
+        Some(voice.group as u32)
+    }
+
+    // Linear gain applied to every voice in the group, after taps and before panning and reverb.
+    pub fn set_group_gain(&mut self, group: u32, gain: f32) -> DspResult {
+        let group = self
+            .groups
+            .get_mut(group as usize)
+            .ok_or(DspError::OutOfRange)?;
+        ensure(gain.is_finite(), DspError::OutOfRange)?;
+        group.gain = clamp(gain, 0.0, 4.0);
+        Ok(())
+    }
+
+    // Muting keeps the gain, so unmuting fades back to where the group was.
+    pub fn set_group_mute(&mut self, group: u32, muted: bool) -> DspResult {
+        let group = self
+            .groups
+            .get_mut(group as usize)
+            .ok_or(DspError::OutOfRange)?;
+        group.muted = muted;
+        Ok(())
+    }
+
+    pub fn group_gain(&self, group: u32) -> Option<f32> {
+        self.groups.get(group as usize).map(|group| group.gain)
+    }
+
+    pub fn group_muted(&self, group: u32) -> Option<bool> {
+        self.groups.get(group as usize).map(|group| group.muted)
+    }
+
+    pub fn max_groups(&self) -> u32 {
+        MAX_GROUPS as u32
+    }
+
+    // Mirrors one voice's isolated, pre-mix signal into its own buffer on every process() call,
+    // after propagation and hull directivity but before panning and reverb.
+    pub fn set_voice_tap(&mut self, voice_id: u32, enabled: bool) -> DspResult {
+        let voice = self
+            .voices
+            .get_mut(voice_id as usize)
+            .filter(|v| v.active)
+            .ok_or(DspError::InvalidVoice)?;
+        voice.tap = if enabled { vec![0.0; self.max_frames] } else { Vec::new() };
+        Ok(())
+    }
+
+    // Holds `output_len()` samples; 0 when the voice has no tap.
+    pub fn voice_tap_ptr(&self, voice_id: u32) -> usize {
+        match self.voices.get(voice_id as usize) {
+            Some(voice) if voice.active && !voice.tap.is_empty() => voice.tap.as_ptr() as usize,
+            _ => 0,
+        }
+    }
+
+    pub fn voice_tap_copy(&self, voice_id: u32) -> Vec<f32> {
+        match self.voices.get(voice_id as usize) {
+            Some(voice) if voice.active && !voice.tap.is_empty() => voice.tap[..self.last_frames].to_vec(),
+            _ => Vec::new(),
+        }
+    }
+
+    // Opens an external input on a voice: host audio written into it before process() passes
+    // through the voice's gain, multipath, propagation filtering, panning and sends alongside
+    // its synthesized components. Each block's input is consumed as it renders, so a host that
+    // stops writing gets silence rather than a repeating block.
+    pub fn set_voice_input(&mut self, voice_id: u32, enabled: bool) -> DspResult {
+        let voice = self
+            .voices
+            .get_mut(voice_id as usize)
+            .filter(|v| v.active)
+            .ok_or(DspError::InvalidVoice)?;
+        voice.input = if enabled { vec![0.0; self.max_frames] } else { Vec::new() };
+        Ok(())
+    }
+
+    // Holds `max_frames()` samples for the next process() call; 0 when the input is off.
+    pub fn voice_input_ptr(&mut self, voice_id: u32) -> usize {
+        match self.voices.get_mut(voice_id as usize) {
+            Some(voice) if voice.active && !voice.input.is_empty() => voice.input.as_mut_ptr() as usize,
+            _ => 0,
+        }
+    }
+
+    // Copies up to `max_frames()` samples into a voice's input for the next process() call.
+    // Non-finite samples reject the whole write.
+    pub fn write_voice_input(&mut self, voice_id: u32, samples: &[f32]) -> DspResult {
+        let voice = self
+            .voices
+            .get_mut(voice_id as usize)
+            .filter(|v| v.active)
+            .ok_or(DspError::InvalidVoice)?;
+        ensure(!voice.input.is_empty(), DspError::Unsupported)?;
+        ensure(samples.iter().all(|s| s.is_finite()), DspError::OutOfRange)?;
+        let n = samples.len().min(voice.input.len());
+        voice.input[..n].copy_from_slice(&samples[..n]);
+        Ok(())
+    }
+
     pub fn output_len(&self) -> usize {
         self.last_frames
     }
 
     pub fn output_ptr(&self) -> usize {
-        self.output.as_ptr() as usize
+        if self.interleaved {
+            self.interleaved_output.as_ptr() as usize
+        } else {
+            self.output.as_ptr() as usize
+        }
     }
 
+    // In interleaved mode this is every channel; planar, it is the first.
     pub fn output_copy(&self) -> Vec<f32> {
-        self.output[0..self.last_frames].to_vec()
+        if self.interleaved {
+            self.interleaved_output[..self.last_frames * self.channels].to_vec()
+        } else {
+            self.output[0..self.last_frames].to_vec()
+        }
     }
 
     pub fn max_frames(&self) -> usize {
         self.max_frames
     }
+
+    // Set at construction through DspGraphBuilder::precision.
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    // Switches the graph to a new sample rate in place, keeping every voice and setting.
+    // Timers, ramps, scheduled automation and delays are rescaled so they still run in
+    // seconds; filter coefficients are recomputed. The reverb and limiter delay lines restart
+    // empty, so the reverb tail in flight is lost. Event timestamps stay on the one running
+    // sample clock, which counts at the new rate from here on.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) -> DspResult {
+        ensure((8000.0..=384_000.0).contains(&sample_rate), DspError::OutOfRange)?;
+        let old_rate = self.sample_rate;
+        let ratio = sample_rate / old_rate;
+        self.sample_rate = sample_rate;
+        for voice in &mut self.voices {
+            voice.rescale(ratio);
+            voice.ducker.set_sample_rate(sample_rate);
+        }
+        self.automation.rescale(ratio);
+        self.scenario.rescale(self.automation.clock(), ratio);
+        self.replay.rescale(self.automation.clock(), ratio);
+        self.weather.rescale(ratio);
+        self.self_noise_delay.rescale(ratio);
+        for delay in &mut self.sector_delays {
+            delay.rescale(ratio);
+        }
+        self.reverb.set_sample_rate(sample_rate);
+        self.hull_filter.set_sample_rate(sample_rate);
+        self.dc_blocker.set_sample_rate(sample_rate);
+        self.operator_band.set_sample_rate(sample_rate);
+        self.leveler.set_sample_rate(sample_rate);
+        self.limiter.set_sample_rate(sample_rate);
+        self.ping_reverb.set_sample_rate(old_rate, sample_rate);
+        Ok(())
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    // Reallocates every per-block buffer for a new largest block size, keeping all voice,
+    // reverb and ambient state. Pointers from output_ptr(), channel_ptr(), hydrophone_ptr(),
+    // voice_tap_ptr() and voice_input_ptr() are invalid afterwards and must be fetched again; the next
+    // process() starts clean.
+    pub fn set_max_frames(&mut self, max_frames: usize) -> DspResult {
+        ensure((1..=MAX_BLOCK_FRAMES).contains(&max_frames), DspError::OutOfRange)?;
+        self.max_frames = max_frames;
+        self.output = vec![0.0; max_frames * self.channels];
+        self.bus_send = vec![0.0; max_frames * BUS_COUNT];
+        self.scratch = vec![0.0; max_frames];
+        self.lanes = VoiceLanes::new(max_frames);
+        self.size_hydrophone_output();
+        for voice in &mut self.voices {
+            if !voice.tap.is_empty() {
+                voice.tap = vec![0.0; max_frames];
+            }
+            if !voice.input.is_empty() {
+                voice.input = vec![0.0; max_frames];
+            }
+        }
+        self.last_frames = 0;
+        Ok(())
+    }
+
+    pub fn max_block_frames(&self) -> usize {
+        MAX_BLOCK_FRAMES
+    }
+}
+
+// This block's reverb and hull sends out of the planar send buffer.
+fn bus_sends(bus_send: &[f32], stride: usize, n: usize) -> (&[f32], &[f32]) {
+    (
+        &bus_send[BUS_REVERB as usize * stride..][..n],
+        &bus_send[BUS_HULL as usize * stride..][..n],
+    )
+}
+
+impl DspGraph {
+    // The voice the steal policy gives up for a newcomer ranked `priority`, if any.
+    fn steal_victim(&self, priority: u32) -> Option<usize> {
+        let candidates = self
+            .voices
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.active && v.priority <= priority);
+        match self.steal_policy {
+            StealPolicy::Never => None,
+            StealPolicy::Quietest => candidates
+                .min_by(|(_, a), (_, b)| {
+                    a.priority
+                        .cmp(&b.priority)
+                        .then(a.audibility().total_cmp(&b.audibility()))
+                })
+                .map(|(i, _)| i),
+            StealPolicy::Oldest => candidates
+                .min_by_key(|(_, v)| (v.priority, v.started))
+                .map(|(i, _)| i),
+        }
+    }
+
+    // Applies the recorded changes landing in the coming block, each at its own frame.
+    fn run_replay(&mut self, n: usize) {
+        let clock = self.automation.clock();
+        while let Some(r) = self.replay.next_due(clock + n as u64) {
+            let offset = (r.at - clock) as u32;
+            let id = self.replay.voice(r.voice);
+            match r.change {
+                Change::AddVoice { priority, delay } => {
+                    let live = self.start_voice(priority, offset.saturating_add(delay), 0);
+                    self.replay.bind(r.voice, live.unwrap_or(u32::MAX));
+                }
+                Change::RemoveVoice => {
+                    if let Ok(idx) = self.voice_index(id) {
+                        self.stop_voice(idx, offset);
+                    }
+                }
+                // On a block boundary this is exactly the host's set_param, which also reaches
+                // the block-rate updates (propagation, panning) a queued change would miss.
+                Change::SetParam { param, value } if offset == 0 => {
+                    let _ = self.set_param(id, param, value);
+                }
+                Change::SetParam { param, value } => {
+                    let _ = self.schedule_param(id, param, value, offset, -1.0);
+                }
+            }
+        }
+    }
+
+    // Runs the scenario steps landing in the coming block, each at its own frame. A step whose
+    // voice is gone, or an AddVoice that finds the pool full, is dropped.
+    fn run_scenario(&mut self, n: usize) {
+        let clock = self.automation.clock();
+        while let Some(timed) = self.scenario.next_due(clock + n as u64) {
+            let offset = (timed.at - clock) as u32;
+            let voice = self.find_voice_by_tag(timed.tag);
+            match (timed.step, voice) {
+                (Step::AddVoice { preset, priority }, _) => {
+                    if let Ok(id) = self.start_voice(priority, offset, timed.tag) {
+                        if let Some(preset) = preset {
+                            let _ = self.apply_preset(id, preset);
+                        }
+                    }
+                }
+                (_, None) => {}
+                (Step::SetParam { param, value, ramp_ms }, Some(id)) => {
+                    let _ = self.schedule_param(id, param, value, offset, ramp_ms);
+                }
+                (Step::TriggerBio, Some(id)) => self.cue_bio_event(id as usize, offset),
+                (Step::RemoveVoice, Some(id)) => self.stop_voice(id as usize, offset),
+            }
+        }
+    }
+
+    // Deactivates a voice now and starts its fade-out `offset` frames into the next block.
+    // Changes queued from that frame on are dropped.
+    fn stop_voice(&mut self, idx: usize, offset: u32) {
+        let at = self.automation.clock() + offset as u64;
+        let fade = self.fade_samples();
+        let voice = &mut self.voices[idx];
+        self.events
+            .push(EVENT_VOICE_DEACTIVATED, Some(idx), voice.tag, at, 0, 0.0);
+        voice.active = false;
+        self.controls.unbind_voice(idx);
+        if offset == 0 {
+            voice.fade.set(0.0, fade);
+        } else {
+            voice.stop_cue = Some((offset, fade));
+        }
+        self.automation.cancel_from(idx, at);
+    }
+
+    fn cue_bio_event(&mut self, idx: usize, offset: u32) {
+        let voice = &mut self.voices[idx];
+        if !voice.bio.bio_type.fires_events() {
+            return;
+        }
+        voice.bio_cue = Some(offset);
+        let at = self.automation.clock() + offset as u64;
+        self.events
+            .push(EVENT_BIO, Some(idx), voice.tag, at, voice.bio.bio_type as u32, 0.0);
+    }
+
+    // Swaps each output buffer with its spare, sizing the spare to match first where the buffer
+    // isn't sized on use.
+    fn flip_outputs(&mut self) {
+        let spare = &mut self.spare_outputs;
+        spare.output.resize(self.output.len(), 0.0);
+        spare.hydrophone.resize(self.hydrophone_output.len(), 0.0);
+        core::mem::swap(&mut self.output, &mut spare.output);
+        core::mem::swap(&mut self.interleaved_output, &mut spare.interleaved);
+        core::mem::swap(&mut self.pcm16_output, &mut spare.pcm16);
+        core::mem::swap(&mut self.hydrophone_output, &mut spare.hydrophone);
+    }
+
+    // A paused block: silence in whichever layout the host reads, with the clock held.
+    fn silent_block(&mut self) -> usize {
+        self.output.fill(0.0);
+        for voice in &mut self.voices {
+            voice.input.fill(0.0);
+        }
+        let len = self.max_frames * self.channels;
+        if self.interleaved {
+            self.interleaved_output.resize(len, 0.0);
+            self.interleaved_output.fill(0.0);
+        }
+        if self.pcm16 {
+            self.pcm16_output.resize(len, 0);
+            self.pcm16_output.fill(0);
+        }
+        self.hydrophone_output.fill(0.0);
+        self.analysis.advance(self.analysis_budget as usize);
+        self.output_ptr()
+    }
+
+    // Takes off the hydrophone mix if asked to, then runs the operator's master chain over the
+    // output in place.
+    fn master_chain(&mut self, n: usize) {
+        let start = self.profile.start();
+        let (stride, channels) = (self.max_frames, self.channels);
+        if self.hydrophone {
+            for k in 0..channels {
+                let range = k * stride..k * stride + n;
+                self.hydrophone_output[range.clone()].copy_from_slice(&self.output[range]);
+            }
+        }
+        self.dc_blocker.process(&mut self.output, stride, channels, n);
+        self.operator_band
+            .process(&mut self.output, stride, channels, n);
+        self.leveler.process(&mut self.output, stride, channels, n);
+        self.limiter.process(&mut self.output, stride, channels, n);
+        self.profile.record(ProfileStage::Master, start, n);
+    }
+
+    // Meters the finished block, interleaves it if asked to and closes the automation block.
+    // Returns what process() hands the host.
+    fn finish_block(&mut self, n: usize) -> usize {
+        let start = self.profile.start();
+        self.master_meter
+            .add_planar(&self.output, self.max_frames, self.channels, n);
+        self.headroom.add_block(
+            self.limiter.block_peak,
+            self.limiter.block_gain,
+            &self.output,
+            self.max_frames,
+            self.channels,
+            n,
+        );
+        self.automation.end_block(n);
+        if self.interleaved {
+            let len = self.max_frames * self.channels;
+            if self.interleaved_output.len() != len {
+                self.interleaved_output = vec![0.0; len];
+            }
+            for (k, channel) in self.output.chunks_exact(self.max_frames).enumerate() {
+                for (i, &sample) in channel[..n].iter().enumerate() {
+                    self.interleaved_output[i * self.channels + k] = sample;
+                }
+            }
+        }
+        if self.pcm16 {
+            self.write_pcm16(n);
+        }
+        self.analysis.advance(self.analysis_budget as usize);
+        self.profile.record(ProfileStage::Analysis, start, n);
+        self.output_ptr()
+    }
+
+    // Quantises the block to 16 bits with TPDF dither of +/-1 LSB, which decorrelates the
+    // rounding error from the signal so quiet tails fade into noise instead of distorting.
+    fn write_pcm16(&mut self, n: usize) {
+        self.pcm16_output.resize(self.max_frames * self.channels, 0);
+        for (k, channel) in self.output.chunks_exact(self.max_frames).enumerate() {
+            for (i, &sample) in channel[..n].iter().enumerate() {
+                let dither =
+                    0.5 * (rand_signed(&mut self.dither_rng) + rand_signed(&mut self.dither_rng));
+                let value = (sample * 32767.0 + dither).round().clamp(-32768.0, 32767.0);
+                let at = if self.interleaved { i * self.channels + k } else { k * n + i };
+                self.pcm16_output[at] = value as i16;
+            }
+        }
+    }
+
+    // Index of an active voice, for calls that act on one.
+    fn voice_index(&self, voice_id: u32) -> DspResult<usize> {
+        let idx = voice_id as usize;
+        match self.voices.get(idx) {
+            Some(voice) if voice.active => Ok(idx),
+            _ => Err(DspError::InvalidVoice),
+        }
+    }
+
+    fn fade_samples(&self) -> u32 {
+        (VOICE_FADE_MS * 0.001 * self.sample_rate) as u32
+    }
+
+    // Restarts every random stream from `self.seed`; anything that resets the graph reseeds
+    // through here. The graph-wide streams keep their historical salts, so at seed 0 the noise
+    // field, weather, self-noise, towed array, ping reverb and dither start where they did
+    // before seeding existed. Voice streams don't: each voice splits its seed through
+    // voice_seeds.
+    fn reseed(&mut self) {
+        let mixed = self.seed.wrapping_mul(0x85eb_ca6b).rotate_left(16);
+        self.noise_field.reseed(0x5eed_f1e1 ^ mixed);
+        self.weather.reseed(0x7ea7_4e12 ^ mixed);
+        self.self_noise.reseed(0x0b0a_7001 ^ mixed);
+        self.towed_array.reseed(0x70e0_a77a ^ mixed);
+        self.ping_reverb.reseed(0x9196_0ec0 ^ mixed);
+        self.dither_rng = 0xd17e_4001 ^ mixed;
+        self.next_seed = 0x1234_abcd ^ mixed;
+        for voice in self.voices.iter_mut().filter(|v| v.active) {
+            self.next_seed = self.next_seed.wrapping_add(0x9e37_79b9);
+            voice.reseed(self.next_seed);
+        }
+    }
+
+    fn set_channel_count(&mut self, channels: usize) {
+        self.channels = channels;
+        self.output = vec![0.0; self.max_frames * self.channels];
+        self.size_hydrophone_output();
+        self.last_frames = 0;
+    }
+
+    fn size_hydrophone_output(&mut self) {
+        self.hydrophone_output = if self.hydrophone {
+            vec![0.0; self.max_frames * self.channels]
+        } else {
+            Vec::new()
+        };
+    }
+
+    // Renders every sounding voice into the output and the return bus sends. With the threads
+    // feature, large pools are split across rayon's workers.
+    fn mix_voices(&mut self, n: usize, layout: VoiceLayout) {
+        let start = self.profile.start();
+        let mixing = VoiceMixing {
+            n,
+            stride: self.max_frames,
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+            layout,
+            itd_enabled: self.itd_enabled,
+            listener_yaw: self.listener_yaw,
+            precision: self.precision,
+            cull_threshold: self.cull_threshold,
+            cull_fade: (CULL_FADE_MS * 0.001 * self.sample_rate) as u32,
+            glide: smoothing(DEZIPPER_MS, self.sample_rate),
+            low_rate: if self.low_rate_sources {
+                low_rate_factor(self.sample_rate)
+            } else {
+                1
+            },
+            band_limited_engines: self.band_limited_engines,
+            directivity: &self.directivity,
+            groups: &self.groups,
+            automation: &self.automation,
+            environment: &self.environment,
+            towed_array: &self.towed_array,
+            profile: &self.profile,
+        };
+        #[cfg(feature = "threads")]
+        parallel::mix_voices(
+            &mixing,
+            &mut self.voices,
+            &mut self.scratch,
+            &mut self.lanes,
+            &mut self.partial_mixes,
+            &mut self.output,
+            &mut self.bus_send,
+        );
+        #[cfg(not(feature = "threads"))]
+        mixing.mix_all(
+            &mut self.voices,
+            0,
+            &mut self.scratch,
+            &mut self.lanes,
+            &mut self.output,
+            &mut self.bus_send,
+        );
+        self.profile.record(ProfileStage::Voices, start, n);
+        let clock = self.automation.clock();
+        for (idx, voice) in self.voices.iter_mut().enumerate() {
+            if core::mem::take(&mut voice.quarantined) {
+                self.events
+                    .push(EVENT_QUARANTINE, Some(idx), voice.tag, clock, 0, 0.0);
+            }
+        }
+    }
+
+    // Speaker-ring rendering: voices and ambient sectors are panned around the ring; the diffuse
+    // returns, surface bed and own-ship noise feed every speaker alike, as both stereo
+    // channels get them.
+    fn process_ring(&mut self, n: usize) {
+        let stride = self.max_frames;
+        let sr = self.sample_rate;
+        let channels = self.channels;
+        self.mix_voices(n, VoiceLayout::Ring);
+        let out = &mut self.output[..];
+
+        let buses = self.profile.start();
+        let common = &mut self.scratch[..n];
+        common.iter_mut().for_each(|s| *s = 0.0);
+        let (reverb_send, hull_send) = bus_sends(&self.bus_send, self.max_frames, n);
+        self.reverb.process(reverb_send, common, &mut []);
+        if self.hull_filter.is_active() {
+            self.hull_filter.process(hull_send, common);
+        }
+        self.profile.record(ProfileStage::Buses, buses, n);
+        let ambience = self.profile.start();
+        if self.self_noise.is_active() {
+            let speed = self.own_ship.vx.hypot(self.own_ship.vz);
+            self.self_noise
+                .render(common, speed, self.environment.listener_depth, sr);
+        }
+        if self.weather.is_audible() {
+            for sample in common.iter_mut() {
+                *sample += self.weather.tick(sr);
+            }
+        } else {
+            self.weather.skip(n, sr);
+        }
+        if self.ping_reverb.is_active() {
+            for sample in common.iter_mut() {
+                *sample += self.ping_reverb.tick(sr);
+            }
+        }
+        for k in 0..channels {
+            simd::add(&mut out[k * stride..k * stride + n], common);
+        }
+
+        if self.noise_field.is_active() {
+            let mut sector_gains = [[0.0; MAX_RING_CHANNELS]; NOISE_SECTORS];
+            for (sector, gains) in sector_gains.iter_mut().enumerate() {
+                let bearing = DirectionalNoiseField::sector_bearing(sector) - self.own_ship.heading;
+                *gains = ring_gains(bearing, channels);
+            }
+            let filters = DirectionalNoiseField::filters(sr);
+            for i in 0..n {
+                let sectors = self.noise_field.tick_sectors(filters);
+                for (v, gains) in sectors.iter().zip(sector_gains.iter()) {
+                    for k in 0..channels {
+                        out[k * stride + i] += v * gains[k];
+                    }
+                }
+            }
+        }
+        self.profile.record(ProfileStage::Ambience, ambience, n);
+
+        self.master_chain(n);
+    }
+
+    // Line-array rendering: voices, self-noise and the ambient sectors arrive as plane waves
+    // across the elements; reverb and the surface weather bed reach every element alike.
+    fn process_array(&mut self, n: usize) {
+        let stride = self.max_frames;
+        let sr = self.sample_rate;
+        self.mix_voices(n, VoiceLayout::Array);
+        let out = &mut self.output[..];
+
+        // Diffuse returns and surface noise share one scratch bus.
+        let buses = self.profile.start();
+        let common = &mut self.scratch[..n];
+        common.iter_mut().for_each(|s| *s = 0.0);
+        let (reverb_send, hull_send) = bus_sends(&self.bus_send, self.max_frames, n);
+        self.reverb.process(reverb_send, common, &mut []);
+        if self.hull_filter.is_active() {
+            self.hull_filter.process(hull_send, common);
+        }
+        self.profile.record(ProfileStage::Buses, buses, n);
+        let ambience = self.profile.start();
+        if self.weather.is_audible() {
+            for sample in common.iter_mut() {
+                *sample += self.weather.tick(sr);
+            }
+        } else {
+            self.weather.skip(n, sr);
+        }
+        if self.ping_reverb.is_active() {
+            for sample in common.iter_mut() {
+                *sample += self.ping_reverb.tick(sr);
+            }
+        }
+        for k in 0..self.towed_array.elements {
+            simd::add(&mut out[k * stride..k * stride + n], common);
+        }
+
+        let speed = self.own_ship.vx.hypot(self.own_ship.vz);
+        let glide = smoothing(DEZIPPER_MS, sr);
+        if self.self_noise.is_active() {
+            let buf = &mut self.scratch[..n];
+            buf.iter_mut().for_each(|s| *s = 0.0);
+            self.self_noise
+                .render(buf, speed, self.environment.listener_depth, sr);
+            // Own-ship is dead ahead of the array, so its noise arrives from forward endfire.
+            self.self_noise_delay.steer(&self.towed_array, 0.0, sr);
+            for (i, s) in buf.iter().enumerate() {
+                self.self_noise_delay.process(*s, glide, out, stride, i);
+            }
+        }
+
+        if self.noise_field.is_active() {
+            for (sector, delay) in self.sector_delays.iter_mut().enumerate() {
+                let bearing = DirectionalNoiseField::sector_bearing(sector) - self.own_ship.heading;
+                delay.steer(&self.towed_array, bearing, sr);
+            }
+            let filters = DirectionalNoiseField::filters(sr);
+            for i in 0..n {
+                let sectors = self.noise_field.tick_sectors(filters);
+                for (v, delay) in sectors.iter().zip(self.sector_delays.iter_mut()) {
+                    delay.process(*v, glide, out, stride, i);
+                }
+            }
+        }
+
+        self.towed_array.add_flow_noise(out, stride, n, speed, sr);
+        self.profile.record(ProfileStage::Ambience, ambience, n);
+
+        self.master_chain(n);
+    }
 }
 
-#[wasm_bindgen]
+// Id getters from before the Param enum was exported. The allow covers the export shim
+// wasm-bindgen generates around each one.
+#[deprecated(note = "use Param.Rpm")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_rpm() -> u32 {
-    PARAM_RPM
+    Param::Rpm as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.Blades")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_blades() -> u32 {
-    PARAM_BLADES
+    Param::Blades as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.Gain")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_gain() -> u32 {
-    PARAM_GAIN
+    Param::Gain as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.EngineMix")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_engine_mix() -> u32 {
-    PARAM_ENGINE_MIX
+    Param::EngineMix as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.CavMix")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_cav_mix() -> u32 {
-    PARAM_CAV_MIX
+    Param::CavMix as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.BioMix")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_bio_mix() -> u32 {
-    PARAM_BIO_MIX
+    Param::BioMix as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.BioType")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_bio_type() -> u32 {
-    PARAM_BIO_TYPE
+    Param::BioType as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.BioRate")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_bio_rate() -> u32 {
-    PARAM_BIO_RATE
+    Param::BioRate as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.ShaftRate")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_shaft_rate() -> u32 {
-    PARAM_SHAFT_RATE
+    Param::ShaftRate as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.Load")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_load() -> u32 {
-    PARAM_LOAD
+    Param::Load as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.RpmJitter")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_rpm_jitter() -> u32 {
-    PARAM_RPM_JITTER
+    Param::RpmJitter as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.ClassProfile")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_class_profile() -> u32 {
-    PARAM_CLASS_PROFILE
+    Param::ClassProfile as u32
 }
 
-#[wasm_bindgen]
+#[deprecated(note = "use Param.CavitationLevel")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
 pub fn param_cavitation_level() -> u32 {
-    PARAM_CAVITATION_LEVEL
+    Param::CavitationLevel as u32
+}
+
+#[deprecated(note = "use Param.Range")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_range() -> u32 {
+    Param::Range as u32
+}
+
+#[deprecated(note = "use Param.SourceDepth")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_source_depth() -> u32 {
+    Param::SourceDepth as u32
+}
+
+#[deprecated(note = "use Param.ReverbSend")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_reverb_send() -> u32 {
+    Param::ReverbSend as u32
+}
+
+#[deprecated(note = "use Param.Bearing")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_bearing() -> u32 {
+    Param::Bearing as u32
+}
+
+#[deprecated(note = "use Param.TargetStrength")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_target_strength() -> u32 {
+    Param::TargetStrength as u32
+}
+
+#[deprecated(note = "use Param.TargetLength")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_target_length() -> u32 {
+    Param::TargetLength as u32
+}
+
+#[deprecated(note = "use Param.BearingWander")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_bearing_wander() -> u32 {
+    Param::BearingWander as u32
+}
+
+#[deprecated(note = "use Param.BearingJitter")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_bearing_jitter() -> u32 {
+    Param::BearingJitter as u32
+}
+
+#[deprecated(note = "use Param.Elevation")]
+#[allow(deprecated)]
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_elevation() -> u32 {
+    Param::Elevation as u32
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn preset_trawler() -> u32 {
+    PRESET_TRAWLER
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn preset_merchant() -> u32 {
+    PRESET_MERCHANT
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn preset_destroyer() -> u32 {
+    PRESET_DESTROYER
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn preset_diesel_sub() -> u32 {
+    PRESET_DIESEL_SUB
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn preset_ssn() -> u32 {
+    PRESET_SSN
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn bus_reverb() -> u32 {
+    BUS_REVERB
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn bus_hull() -> u32 {
+    BUS_HULL
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn event_voice_activated() -> u32 {
+    EVENT_VOICE_ACTIVATED
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn event_voice_deactivated() -> u32 {
+    EVENT_VOICE_DEACTIVATED
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn event_ping() -> u32 {
+    EVENT_PING
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn event_transient() -> u32 {
+    EVENT_TRANSIENT
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn event_automation() -> u32 {
+    EVENT_AUTOMATION
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn event_bio() -> u32 {
+    EVENT_BIO
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn event_quarantine() -> u32 {
+    EVENT_QUARANTINE
 }
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+// Internal rate the low-frequency generators compute their waveforms at when low-rate
+// rendering is on. Their energy sits almost all below 400 Hz, where linear interpolation from
+// 12 kHz is flat to within 0.05 dB and leaves its images more than 55 dB down.
+const LOW_RATE_HZ: f32 = 12_000.0;
+
+// How many samples apart a low-rate generator computes its waveform at `sample_rate`; 1 (every
+// sample) at rates too low to gain from it.
+pub(crate) fn low_rate_factor(sample_rate: f32) -> u32 {
+    ((sample_rate / LOW_RATE_HZ) as u32).max(1)
+}
+
+// Upsampler for a generator whose waveform is only computed every `factor` samples. The
+// generator still advances every sample, so its phases, envelopes and events keep full-rate
+// timing; only the output is drawn as a line between the computed points, one interval late.
+// With a factor of 1 it passes every sample straight through.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub(crate) struct LowRate {
+    from: f32,
+    to: f32,
+    pos: u32,
+}
+
+impl LowRate {
+    // Whether the waveform is wanted at this sample.
+    #[inline]
+    pub(crate) fn is_due(&self) -> bool {
+        self.pos == 0
+    }
+
+    // Takes this sample's waveform, which is only read when it was due, and returns the
+    // output.
+    #[inline]
+    pub(crate) fn next(&mut self, value: f32, factor: u32) -> f32 {
+        if factor <= 1 {
+            self.pos = 0;
+            self.to = value;
+            return value;
+        }
+        if self.pos == 0 {
+            self.from = self.to;
+            self.to = value;
+        }
+        self.pos += 1;
+        let t = (self.pos as f32 / factor as f32).min(1.0);
+        if self.pos >= factor {
+            self.pos = 0;
+        }
+        self.from + (self.to - self.from) * t
+    }
+}
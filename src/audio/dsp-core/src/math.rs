@@ -0,0 +1,108 @@
+// The float methods std gives f32 and f64, for builds without it. Modules doing float math
+// import this trait when std is off, and the same calls resolve to libm.
+pub(crate) trait Float: Copy {
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn tan(self) -> Self;
+    fn tanh(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn log10(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn floor(self) -> Self;
+    fn round(self) -> Self;
+    fn round_ties_even(self) -> Self;
+    fn fract(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($t:ty, $sin:ident, $cos:ident, $sincos:ident, $tan:ident, $tanh:ident, $asin:ident,
+     $acos:ident, $atan2:ident, $exp:ident, $log:ident, $log10:ident, $pow:ident,
+     $sqrt:ident, $hypot:ident, $floor:ident, $round:ident, $rint:ident, $trunc:ident) => {
+        impl Float for $t {
+            fn sin(self) -> Self {
+                libm::$sin(self)
+            }
+            fn cos(self) -> Self {
+                libm::$cos(self)
+            }
+            fn sin_cos(self) -> (Self, Self) {
+                libm::$sincos(self)
+            }
+            fn tan(self) -> Self {
+                libm::$tan(self)
+            }
+            fn tanh(self) -> Self {
+                libm::$tanh(self)
+            }
+            fn asin(self) -> Self {
+                libm::$asin(self)
+            }
+            fn acos(self) -> Self {
+                libm::$acos(self)
+            }
+            fn atan2(self, other: Self) -> Self {
+                libm::$atan2(self, other)
+            }
+            fn exp(self) -> Self {
+                libm::$exp(self)
+            }
+            fn ln(self) -> Self {
+                libm::$log(self)
+            }
+            fn log10(self) -> Self {
+                libm::$log10(self)
+            }
+            fn powf(self, n: Self) -> Self {
+                libm::$pow(self, n)
+            }
+            fn powi(self, n: i32) -> Self {
+                libm::$pow(self, n as $t)
+            }
+            fn sqrt(self) -> Self {
+                libm::$sqrt(self)
+            }
+            fn hypot(self, other: Self) -> Self {
+                libm::$hypot(self, other)
+            }
+            fn floor(self) -> Self {
+                libm::$floor(self)
+            }
+            fn round(self) -> Self {
+                libm::$round(self)
+            }
+            fn round_ties_even(self) -> Self {
+                libm::$rint(self)
+            }
+            fn fract(self) -> Self {
+                self - libm::$trunc(self)
+            }
+            // As std has it: the remainder, moved up by |rhs| when negative.
+            fn rem_euclid(self, rhs: Self) -> Self {
+                let r = self % rhs;
+                if r < 0.0 {
+                    r + rhs.abs()
+                } else {
+                    r
+                }
+            }
+        }
+    };
+}
+
+impl_float!(
+    f32, sinf, cosf, sincosf, tanf, tanhf, asinf, acosf, atan2f, expf, logf, log10f, powf,
+    sqrtf, hypotf, floorf, roundf, rintf, truncf
+);
+impl_float!(
+    f64, sin, cos, sincos, tan, tanh, asin, acos, atan2, exp, log, log10, pow, sqrt, hypot,
+    floor, round, rint, trunc
+);
@@ -0,0 +1,685 @@
+use alloc::{vec, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{clamp, flush_denormal, simd, vec_bytes};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+pub(crate) const MAX_GROUPS: usize = 8;
+
+// Return buses every voice can send into (BUS_REVERB, BUS_HULL).
+pub(crate) const BUS_COUNT: usize = 2;
+
+// Shared gain stage for a set of voices (biologics, own-ship, contacts, ...). Voices start in
+// group 0; each smooths toward its group's level on its own, so changes never click.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct VoiceGroup {
+    pub(crate) gain: f32,
+    pub(crate) muted: bool,
+}
+
+impl VoiceGroup {
+    pub(crate) fn new() -> Self {
+        Self {
+            gain: 1.0,
+            muted: false,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn level(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.gain
+        }
+    }
+}
+
+// Running peak and mean-square since the meter was last read.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct MeterState {
+    peak: f32,
+    sum_sq: f64,
+    count: u32,
+}
+
+impl MeterState {
+    pub(crate) fn new() -> Self {
+        Self {
+            peak: 0.0,
+            sum_sq: 0.0,
+            count: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn add(&mut self, sample: f32) {
+        self.peak = self.peak.max(sample.abs());
+        self.sum_sq += (sample * sample) as f64;
+        self.count = self.count.saturating_add(1);
+    }
+
+    // Folds a planar block in, every channel weighted alike.
+    pub(crate) fn add_planar(&mut self, out: &[f32], stride: usize, channels: usize, frames: usize) {
+        for k in 0..channels {
+            for &sample in &out[k * stride..][..frames] {
+                self.add(sample);
+            }
+        }
+    }
+
+    // Returns (peak, rms) and starts a new measurement window.
+    pub(crate) fn take(&mut self) -> (f32, f32) {
+        let rms = if self.count > 0 {
+            (self.sum_sq / self.count as f64).sqrt() as f32
+        } else {
+            0.0
+        };
+        let peak = self.peak;
+        *self = Self::new();
+        (peak, rms)
+    }
+}
+
+// "Through the hull" monitor return: a resonant low-pass standing in for the steel between the
+// water and a hull-mounted hydrophone. Silent until a return level is set.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct HullFilter {
+    sample_rate: f32,
+    cutoff_hz: f32,
+    resonance: f32,
+    return_level: f32,
+    a1: f32,
+    a2: f32,
+    a3: f32,
+    ic1: f32,
+    ic2: f32,
+}
+
+impl HullFilter {
+    pub(crate) fn new(sample_rate: f32) -> Self {
+        let mut filter = Self {
+            sample_rate,
+            cutoff_hz: 600.0,
+            resonance: 0.4,
+            return_level: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+            a3: 0.0,
+            ic1: 0.0,
+            ic2: 0.0,
+        };
+        filter.update_coefficients();
+        filter
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.cutoff_hz = clamp(self.cutoff_hz, 40.0, sample_rate * 0.45);
+        self.update_coefficients();
+    }
+
+    pub(crate) fn configure(&mut self, cutoff_hz: f32, resonance: f32, return_level: f32) {
+        self.cutoff_hz = clamp(cutoff_hz, 40.0, self.sample_rate * 0.45);
+        self.resonance = clamp(resonance, 0.0, 0.95);
+        self.return_level = clamp(return_level, 0.0, 2.0);
+        self.update_coefficients();
+    }
+
+    // Trapezoidal state-variable filter (Zavalishin), stable at any cutoff.
+    fn update_coefficients(&mut self) {
+        let g = (core::f32::consts::PI * self.cutoff_hz / self.sample_rate).tan();
+        let k = 2.0 * (1.0 - self.resonance);
+        self.a1 = 1.0 / (1.0 + g * (g + k));
+        self.a2 = g * self.a1;
+        self.a3 = g * self.a2;
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.return_level > 0.0
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.ic1 = 0.0;
+        self.ic2 = 0.0;
+    }
+
+    // Adds the filtered return of `send` into `out`.
+    pub(crate) fn process(&mut self, send: &[f32], out: &mut [f32]) {
+        for (x, o) in send.iter().zip(out.iter_mut()) {
+            let v3 = x - self.ic2;
+            let v1 = self.a1 * self.ic1 + self.a2 * v3;
+            let v2 = self.ic2 + self.a2 * self.ic1 + self.a3 * v3;
+            self.ic1 = flush_denormal(2.0 * v1 - self.ic1);
+            self.ic2 = flush_denormal(2.0 * v2 - self.ic2);
+            *o += v2 * self.return_level;
+        }
+    }
+}
+
+// Operator headphone band: 12 dB/oct Butterworth high- and low-pass sections on every output
+// channel, standing in for the console's audio chain. The hydrophone mix bypasses it.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct OperatorBand {
+    pub(crate) enabled: bool,
+    sample_rate: f32,
+    low_hz: f32,
+    high_hz: f32,
+    // Trapezoidal state-variable coefficients (a1, a2, a3) for each section.
+    high_pass: [f32; 3],
+    low_pass: [f32; 3],
+    // Per channel: high-pass ic1, ic2, then low-pass ic1, ic2.
+    state: Vec<[f32; 4]>,
+}
+
+impl OperatorBand {
+    const K: f32 = core::f32::consts::SQRT_2;
+
+    pub(crate) fn new(sample_rate: f32) -> Self {
+        let mut band = Self {
+            enabled: false,
+            sample_rate,
+            low_hz: 100.0,
+            high_hz: 8000.0,
+            high_pass: [0.0; 3],
+            low_pass: [0.0; 3],
+            state: Vec::new(),
+        };
+        band.set_sample_rate(sample_rate);
+        band
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.configure(self.low_hz, self.high_hz);
+    }
+
+    // Corners in Hz, kept between 10 Hz and 0.45x the sample rate with the low corner below
+    // the high one.
+    pub(crate) fn configure(&mut self, low_hz: f32, high_hz: f32) {
+        let nyquist = self.sample_rate * 0.45;
+        self.high_hz = clamp(high_hz, 20.0, nyquist);
+        self.low_hz = clamp(low_hz, 10.0, self.high_hz * 0.5);
+        self.high_pass = Self::coefficients(self.low_hz, self.sample_rate);
+        self.low_pass = Self::coefficients(self.high_hz, self.sample_rate);
+    }
+
+    fn coefficients(cutoff_hz: f32, sample_rate: f32) -> [f32; 3] {
+        let g = (core::f32::consts::PI * cutoff_hz / sample_rate).tan();
+        let a1 = 1.0 / (1.0 + g * (g + Self::K));
+        [a1, g * a1, g * g * a1]
+    }
+
+    pub(crate) fn corners(&self) -> (f32, f32) {
+        (self.low_hz, self.high_hz)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.state.clear();
+    }
+
+    pub(crate) fn process(&mut self, out: &mut [f32], stride: usize, channels: usize, frames: usize) {
+        if !self.enabled {
+            return;
+        }
+        if self.state.len() != channels {
+            self.state = vec![[0.0; 4]; channels];
+        }
+        simd::band(out, stride, frames, &mut self.state, self.high_pass, self.low_pass, Self::K);
+        // The sections run inside the kernel, so their tails are cut once a block.
+        for state in &mut self.state {
+            *state = state.map(flush_denormal);
+        }
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.state)
+    }
+}
+
+// One-pole DC blocker at the head of the master chain, its corner a few hertz up so nothing
+// audible moves. Some biologic and cavitation settings build up a small offset, which would
+// bias the limiter and step out as a thump when its voice is removed.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct DcBlocker {
+    pole: f32,
+    // Per channel: last input, last output.
+    state: Vec<[f32; 2]>,
+}
+
+impl DcBlocker {
+    const CORNER_HZ: f32 = 5.0;
+
+    pub(crate) fn new(sample_rate: f32) -> Self {
+        let mut blocker = Self {
+            pole: 0.0,
+            state: Vec::new(),
+        };
+        blocker.set_sample_rate(sample_rate);
+        blocker
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.pole = (-core::f32::consts::TAU * Self::CORNER_HZ / sample_rate).exp();
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.state.clear();
+    }
+
+    pub(crate) fn process(&mut self, out: &mut [f32], stride: usize, channels: usize, frames: usize) {
+        if self.state.len() != channels {
+            self.state = vec![[0.0; 2]; channels];
+        }
+        for (k, [x1, y1]) in self.state.iter_mut().enumerate() {
+            for sample in &mut out[k * stride..][..frames] {
+                let y = *sample - *x1 + self.pole * *y1;
+                *x1 = *sample;
+                *y1 = flush_denormal(y);
+                *sample = y;
+            }
+        }
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.state)
+    }
+}
+
+// Master peak limiter, linked across every output channel so the image never shifts. The
+// signal runs a short lookahead behind the detector, letting the gain come down before a
+// peak arrives; below the soft knee it is untouched. A drive gain ahead of the detector sets
+// how hard the mix is pushed into it.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Limiter {
+    pub(crate) enabled: bool,
+    threshold_db: f32,
+    knee_db: f32,
+    drive_db: f32,
+    drive: f32,
+    attack: f32,
+    release_ms: f32,
+    release: f32,
+    gain: f32,
+    lookahead: usize,
+    // Planar, `lookahead` samples per channel.
+    delay: Vec<f32>,
+    write: usize,
+    // The last block's highest input peak and lowest gain, for headroom telemetry.
+    #[serde(skip)]
+    pub(crate) block_peak: f32,
+    #[serde(skip)]
+    pub(crate) block_gain: f32,
+}
+
+impl Limiter {
+    pub(crate) fn new(sample_rate: f32) -> Self {
+        let mut limiter = Self {
+            enabled: true,
+            threshold_db: -1.0,
+            knee_db: 6.0,
+            drive_db: 0.0,
+            drive: 1.0,
+            attack: 0.0,
+            release_ms: 80.0,
+            release: 0.0,
+            gain: 1.0,
+            lookahead: 1,
+            delay: Vec::new(),
+            write: 0,
+            block_peak: 0.0,
+            block_gain: 1.0,
+        };
+        limiter.set_sample_rate(sample_rate);
+        limiter
+    }
+
+    // Resizes the lookahead and recomputes both time constants; the delay refills from silence.
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.lookahead = ((0.0015 * sample_rate) as usize).max(1);
+        self.attack = 1.0 - (-3.0 / self.lookahead as f32).exp();
+        self.delay.clear();
+        self.configure(sample_rate, self.threshold_db, self.release_ms);
+    }
+
+    pub(crate) fn configure(&mut self, sample_rate: f32, threshold_db: f32, release_ms: f32) {
+        self.threshold_db = clamp(threshold_db, -24.0, 0.0);
+        self.release_ms = clamp(release_ms, 5.0, 2000.0);
+        self.release = 1.0 - (-1.0 / (self.release_ms * 0.001 * sample_rate)).exp();
+    }
+
+    // Drive in dB (-24..24) and knee width in dB (0..24, 0 for a hard knee).
+    pub(crate) fn shape(&mut self, drive_db: f32, knee_db: f32) {
+        self.drive_db = clamp(drive_db, -24.0, 24.0);
+        self.drive = 10.0f32.powf(self.drive_db / 20.0);
+        self.knee_db = clamp(knee_db, 0.0, 24.0);
+    }
+
+    pub(crate) fn drive_db(&self) -> f32 {
+        self.drive_db
+    }
+
+    pub(crate) fn knee_db(&self) -> f32 {
+        self.knee_db
+    }
+
+    // Releases any reduction at once and empties the lookahead.
+    pub(crate) fn clear(&mut self) {
+        self.gain = 1.0;
+        self.delay.fill(0.0);
+        self.write = 0;
+    }
+
+    // Current gain reduction in dB, for metering.
+    pub(crate) fn reduction_db(&self) -> f32 {
+        (-20.0 * self.gain.max(1.0e-6).log10()).max(0.0)
+    }
+
+    #[inline]
+    fn target_gain(&self, peak: f32) -> f32 {
+        let knee_start = 10.0f32.powf((self.threshold_db - 0.5 * self.knee_db) / 20.0);
+        if peak <= knee_start {
+            return 1.0;
+        }
+        let over = 20.0 * peak.log10() - self.threshold_db;
+        let half = 0.5 * self.knee_db;
+        let reduction = if over >= half {
+            over
+        } else {
+            (over + half) * (over + half) / (2.0 * self.knee_db)
+        };
+        10.0f32.powf(-reduction / 20.0)
+    }
+
+    pub(crate) fn process(&mut self, out: &mut [f32], stride: usize, channels: usize, frames: usize) {
+        self.block_gain = 1.0;
+        if !self.enabled {
+            self.block_peak = (0..channels)
+                .flat_map(|k| &out[k * stride..k * stride + frames])
+                .fold(0.0, |peak, x| peak.max(x.abs()));
+            return;
+        }
+        self.block_peak = 0.0;
+        let len = self.lookahead;
+        if self.delay.len() != channels * len {
+            self.delay = vec![0.0; channels * len];
+            self.write = 0;
+        }
+        for i in 0..frames {
+            let mut peak = 0.0f32;
+            for k in 0..channels {
+                let sample = &mut out[k * stride + i];
+                *sample *= self.drive;
+                peak = peak.max(sample.abs());
+            }
+            self.block_peak = self.block_peak.max(peak);
+            let target = self.target_gain(peak);
+            let coeff = if target < self.gain { self.attack } else { self.release };
+            self.gain += coeff * (target - self.gain);
+            self.block_gain = self.block_gain.min(self.gain);
+            for k in 0..channels {
+                let slot = &mut self.delay[k * len + self.write];
+                let delayed = *slot;
+                *slot = out[k * stride + i];
+                out[k * stride + i] = delayed * self.gain;
+            }
+            self.write = (self.write + 1) % len;
+        }
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.delay)
+    }
+}
+
+// Sidechain ducking between a voice's machinery (engine plus cavitation) and its biologic
+// component, each a compressor keyed from the other: when the key rises above the threshold,
+// the ducked side comes down by (key - threshold) * (1 - 1/ratio) dB. Keys are taken before
+// ducking, so the two directions never feed back. A ratio of 1 leaves that side alone.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Ducker {
+    threshold: f32,
+    // 1 - 1/ratio for the biologic and machinery sides.
+    bio_slope: f32,
+    machinery_slope: f32,
+    release_ms: f32,
+    attack: f32,
+    release: f32,
+    machinery_env: f32,
+    bio_env: f32,
+    machinery_gain: f32,
+    bio_gain: f32,
+}
+
+impl Ducker {
+    const ATTACK_MS: f32 = 5.0;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            threshold: 0.1,
+            bio_slope: 0.0,
+            machinery_slope: 0.0,
+            release_ms: 250.0,
+            attack: 0.0,
+            release: 0.0,
+            machinery_env: 0.0,
+            bio_env: 0.0,
+            machinery_gain: 1.0,
+            bio_gain: 1.0,
+        }
+    }
+
+    pub(crate) fn configure(&mut self, sample_rate: f32, threshold_db: f32, bio_ratio: f32, machinery_ratio: f32, release_ms: f32) {
+        self.threshold = 10.0f32.powf(clamp(threshold_db, -60.0, 0.0) / 20.0);
+        self.bio_slope = 1.0 - 1.0 / clamp(bio_ratio, 1.0, 20.0);
+        self.machinery_slope = 1.0 - 1.0 / clamp(machinery_ratio, 1.0, 20.0);
+        self.release_ms = clamp(release_ms, 5.0, 5000.0);
+        self.set_sample_rate(sample_rate);
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.attack = 1.0 - (-1.0 / (Self::ATTACK_MS * 0.001 * sample_rate)).exp();
+        self.release = 1.0 - (-1.0 / (self.release_ms * 0.001 * sample_rate)).exp();
+    }
+
+    // Takes another ducker's settings; the envelopes carry on from where they are.
+    pub(crate) fn copy_settings(&mut self, other: &Self) {
+        self.threshold = other.threshold;
+        self.bio_slope = other.bio_slope;
+        self.machinery_slope = other.machinery_slope;
+        self.release_ms = other.release_ms;
+        self.attack = other.attack;
+        self.release = other.release;
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.bio_slope > 0.0 || self.machinery_slope > 0.0
+    }
+
+    // Drops both envelopes and lets go of any reduction.
+    pub(crate) fn clear(&mut self) {
+        self.machinery_env = 0.0;
+        self.bio_env = 0.0;
+        self.machinery_gain = 1.0;
+        self.bio_gain = 1.0;
+    }
+
+    // Current reduction on the machinery and biologic sides in dB, for metering.
+    pub(crate) fn reduction_db(&self) -> (f32, f32) {
+        let db = |gain: f32| (-20.0 * gain.max(1.0e-6).log10()).max(0.0);
+        (db(self.machinery_gain), db(self.bio_gain))
+    }
+
+    #[inline]
+    fn follow(&self, env: f32, input: f32) -> f32 {
+        let level = input.abs();
+        let coeff = if level > env { self.attack } else { self.release };
+        flush_denormal(env + coeff * (level - env))
+    }
+
+    #[inline]
+    fn gain(&self, key: f32, slope: f32) -> f32 {
+        if slope <= 0.0 || key <= self.threshold {
+            1.0
+        } else {
+            (key / self.threshold).powf(-slope)
+        }
+    }
+
+    // Gains for the machinery and biologic sides this sample.
+    #[inline]
+    pub(crate) fn process(&mut self, machinery: f32, bio: f32) -> (f32, f32) {
+        if !self.is_active() {
+            return (1.0, 1.0);
+        }
+        self.machinery_env = self.follow(self.machinery_env, machinery);
+        self.bio_env = self.follow(self.bio_env, bio);
+        self.machinery_gain = self.gain(self.bio_env, self.machinery_slope);
+        self.bio_gain = self.gain(self.machinery_env, self.bio_slope);
+        (self.machinery_gain, self.bio_gain)
+    }
+}
+
+// Slow master loudness normalisation. A long-window mean-square level across every channel
+// drives a gain toward the target, bounded to the configured boost and cut and slewed a few dB
+// per second, so it rides scene changes rather than single events. Near-silence holds the
+// gain instead of boosting the noise floor. Runs ahead of the limiter, which still owns peaks.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Leveler {
+    pub(crate) enabled: bool,
+    target_db: f32,
+    max_boost_db: f32,
+    max_cut_db: f32,
+    window: f32,
+    // Largest gain change per sample, in dB.
+    slew_db: f32,
+    mean_square: f32,
+    gain_db: f32,
+}
+
+impl Leveler {
+    const WINDOW_S: f32 = 3.0;
+    const SLEW_DB_PER_S: f32 = 2.0;
+    const GATE_DB: f32 = -70.0;
+
+    pub(crate) fn new(sample_rate: f32) -> Self {
+        let mut leveler = Self {
+            enabled: false,
+            target_db: -20.0,
+            max_boost_db: 12.0,
+            max_cut_db: 12.0,
+            window: 0.0,
+            slew_db: 0.0,
+            mean_square: 0.0,
+            gain_db: 0.0,
+        };
+        leveler.set_sample_rate(sample_rate);
+        leveler
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.window = 1.0 - (-1.0 / (Self::WINDOW_S * sample_rate)).exp();
+        self.slew_db = Self::SLEW_DB_PER_S / sample_rate;
+    }
+
+    pub(crate) fn configure(&mut self, target_db: f32, max_boost_db: f32, max_cut_db: f32) {
+        self.target_db = clamp(target_db, -40.0, -6.0);
+        self.max_boost_db = clamp(max_boost_db, 0.0, 24.0);
+        self.max_cut_db = clamp(max_cut_db, 0.0, 24.0);
+        self.gain_db = clamp(self.gain_db, -self.max_cut_db, self.max_boost_db);
+    }
+
+    // Forgets the measured level and returns to unity gain.
+    pub(crate) fn clear(&mut self) {
+        self.mean_square = 0.0;
+        self.gain_db = 0.0;
+    }
+
+    // Gain currently applied, in dB (positive boosts).
+    pub(crate) fn gain_db(&self) -> f32 {
+        if self.enabled {
+            self.gain_db
+        } else {
+            0.0
+        }
+    }
+
+    pub(crate) fn process(&mut self, out: &mut [f32], stride: usize, channels: usize, frames: usize) {
+        if !self.enabled || frames == 0 {
+            return;
+        }
+        let scale = 1.0 / channels as f32;
+        for i in 0..frames {
+            let power: f32 = (0..channels).map(|k| out[k * stride + i].powi(2)).sum();
+            self.mean_square =
+                flush_denormal(self.mean_square + self.window * (power * scale - self.mean_square));
+        }
+        let level_db = 10.0 * self.mean_square.max(1.0e-12).log10();
+        let start = 10.0f32.powf(self.gain_db / 20.0);
+        if level_db > Self::GATE_DB {
+            let wanted = clamp(self.target_db - level_db, -self.max_cut_db, self.max_boost_db);
+            let step = self.slew_db * frames as f32;
+            self.gain_db += clamp(wanted - self.gain_db, -step, step);
+        }
+        // Ramp across the block so the gain never steps.
+        let end = 10.0f32.powf(self.gain_db / 20.0);
+        let delta = (end - start) / frames as f32;
+        for k in 0..channels {
+            simd::ramp(&mut out[k * stride..k * stride + frames], start, delta);
+        }
+    }
+}
+
+// How hard the master stage has been pushed since the host last asked.
+#[derive(Clone, Copy)]
+pub(crate) struct Headroom {
+    pub(crate) blocks: u32,
+    // Blocks where the limiter took off more than LIMITED_DB.
+    pub(crate) limited_blocks: u32,
+    // Blocks, and samples within them, that left the graph beyond full scale.
+    pub(crate) clipped_blocks: u32,
+    pub(crate) clipped_samples: u32,
+    pub(crate) peak: f32,
+    pub(crate) min_gain: f32,
+}
+
+impl Default for Headroom {
+    fn default() -> Self {
+        Self {
+            blocks: 0,
+            limited_blocks: 0,
+            clipped_blocks: 0,
+            clipped_samples: 0,
+            peak: 0.0,
+            min_gain: 1.0,
+        }
+    }
+}
+
+impl Headroom {
+    const LIMITED_DB: f32 = 1.0;
+
+    // Counts one block from the limiter's input peak and lowest gain and the final output.
+    pub(crate) fn add_block(&mut self, peak: f32, gain: f32, out: &[f32], stride: usize, channels: usize, frames: usize) {
+        self.blocks = self.blocks.saturating_add(1);
+        if -20.0 * gain.max(1.0e-6).log10() > Self::LIMITED_DB {
+            self.limited_blocks = self.limited_blocks.saturating_add(1);
+        }
+        let clipped = (0..channels)
+            .flat_map(|k| &out[k * stride..k * stride + frames])
+            .filter(|x| x.abs() > 1.0)
+            .count() as u32;
+        if clipped > 0 {
+            self.clipped_blocks = self.clipped_blocks.saturating_add(1);
+            self.clipped_samples = self.clipped_samples.saturating_add(clipped);
+        }
+        self.peak = self.peak.max(peak);
+        self.min_gain = self.min_gain.min(gain);
+    }
+
+    pub(crate) fn take(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
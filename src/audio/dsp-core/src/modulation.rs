@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::params::PARAM_COUNT;
+use crate::{clamp, rand_signed, Param, TWO_PI};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+pub(crate) const MOD_SOURCES: usize = 4;
+pub(crate) const MAX_MOD_ROUTES: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum ModShape {
+    Sine = 0,
+    Triangle = 1,
+    // Mean-reverting random walk; the rate sets how quickly it wanders.
+    RandomWalk = 2,
+}
+
+impl ModShape {
+    pub(crate) fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Sine),
+            1 => Some(Self::Triangle),
+            2 => Some(Self::RandomWalk),
+            _ => None,
+        }
+    }
+}
+
+// One bipolar control signal, nominally within -1..1.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct ModSource {
+    shape: ModShape,
+    rate_hz: f32,
+    // In cycles. f64, as a slow source's step would be only a few units in the last place of
+    // an f32 phase, and rounding would pull its rate.
+    phase: f64,
+    value: f32,
+}
+
+impl ModSource {
+    fn advance(&mut self, dt: f32, rng: &mut u32) {
+        match self.shape {
+            ModShape::Sine | ModShape::Triangle => {
+                self.phase = (self.phase + self.rate_hz as f64 * dt as f64).fract();
+                let phase = self.phase as f32;
+                self.value = if self.shape == ModShape::Sine {
+                    (TWO_PI * phase).sin()
+                } else {
+                    4.0 * (phase - 0.5).abs() - 1.0
+                };
+            }
+            ModShape::RandomWalk => {
+                // Same Ornstein-Uhlenbeck step as the bearing noise, held to half-scale RMS.
+                let a = (-dt * self.rate_hz).exp();
+                let kick = rand_signed(rng) * 3.0f32.sqrt();
+                self.value = clamp(self.value * a + 0.5 * (1.0 - a * a).sqrt() * kick, -1.0, 1.0);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct ModRoute {
+    pub(crate) source: usize,
+    pub(crate) param: Param,
+    // Peak offset in the parameter's own units.
+    pub(crate) depth: f32,
+}
+
+// Per-voice modulation matrix: two LFOs and two random walks by default, routed onto any
+// continuous parameter. A modulated parameter keeps its set_param value as a base and is
+// re-applied once per block as base plus the summed offsets, ramping across the block.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Modulation {
+    sources: [ModSource; MOD_SOURCES],
+    pub(crate) routes: [Option<ModRoute>; MAX_MOD_ROUTES],
+    // Unmodulated value of each parameter that currently has a route.
+    pub(crate) base: [Option<f32>; PARAM_COUNT],
+    rng: u32,
+}
+
+impl Modulation {
+    pub(crate) fn new(seed: u32) -> Self {
+        let mut rng = (seed ^ 0x3c6e_f372) | 1;
+        let mut sources = [ModSource {
+            shape: ModShape::Sine,
+            rate_hz: 0.05,
+            phase: 0.0,
+            value: 0.0,
+        }; MOD_SOURCES];
+        for (i, source) in sources.iter_mut().enumerate() {
+            if i >= 2 {
+                source.shape = ModShape::RandomWalk;
+            }
+            // Voices sharing a setting still drift out of step with each other.
+            source.phase = (0.5 + 0.5 * rand_signed(&mut rng)) as f64;
+        }
+        Self {
+            sources,
+            routes: [None; MAX_MOD_ROUTES],
+            base: [None; PARAM_COUNT],
+            rng,
+        }
+    }
+
+    pub(crate) fn reseed(&mut self, seed: u32) {
+        self.rng = (seed ^ 0x3c6e_f372) | 1;
+    }
+
+    pub(crate) fn configure_source(&mut self, source: usize, shape: ModShape, rate_hz: f32) {
+        let source = &mut self.sources[source];
+        source.shape = shape;
+        source.rate_hz = clamp(rate_hz, 0.001, 20.0);
+    }
+
+    // Takes another voice's source shapes and rates, routes and bases, keeping this voice's
+    // own phases and random stream.
+    pub(crate) fn copy_settings(&mut self, other: &Self) {
+        for (source, theirs) in self.sources.iter_mut().zip(&other.sources) {
+            source.shape = theirs.shape;
+            source.rate_hz = theirs.rate_hz;
+        }
+        self.routes = other.routes;
+        self.base = other.base;
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.routes.iter().any(Option::is_some)
+    }
+
+    pub(crate) fn has_route(&self, param: Param) -> bool {
+        self.routes.iter().flatten().any(|route| route.param == param)
+    }
+
+    pub(crate) fn advance(&mut self, dt: f32) {
+        for source in &mut self.sources {
+            source.advance(dt, &mut self.rng);
+        }
+    }
+
+    // Summed offset for one parameter from every route that targets it.
+    pub(crate) fn offset(&self, param: Param) -> f32 {
+        self.routes
+            .iter()
+            .flatten()
+            .filter(|route| route.param == param)
+            .map(|route| route.depth * self.sources[route.source].value)
+            .sum()
+    }
+}
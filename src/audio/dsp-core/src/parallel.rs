@@ -0,0 +1,89 @@
+use alloc::{vec, vec::Vec};
+
+use rayon::prelude::*;
+
+use crate::{simd, vec_bytes, Voice, VoiceLanes, VoiceMixing};
+
+// Voice slots per task. Fixed rather than taken from the pool size, so a scene mixes to the
+// same output however many workers there are. While the sounding voices all fall in one task
+// the mix matches a build without threads exactly; past that, the partial mixes are summed
+// task by task, which can round the last bits differently.
+const VOICES_PER_TASK: usize = 8;
+
+// One task's scratch and its share of the mix, laid out like the graph's output and bus sends.
+#[derive(Default)]
+pub(crate) struct PartialMix {
+    dry: Vec<f32>,
+    lanes: VoiceLanes,
+    output: Vec<f32>,
+    bus_send: Vec<f32>,
+    sounding: bool,
+}
+
+impl PartialMix {
+    // Sized on first use and whenever the block size or channel count changes.
+    fn fit(&mut self, stride: usize, output: usize, bus_send: usize) {
+        if self.dry.len() != stride {
+            self.dry = vec![0.0; stride];
+            self.lanes = VoiceLanes::new(stride);
+        }
+        if self.output.len() != output {
+            self.output = vec![0.0; output];
+        }
+        if self.bus_send.len() != bus_send {
+            self.bus_send = vec![0.0; bus_send];
+        }
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        let buffers = [&self.dry, &self.output, &self.bus_send];
+        buffers.into_iter().map(vec_bytes).sum::<usize>() + self.lanes.heap_bytes()
+    }
+
+    fn clear(&mut self, stride: usize, n: usize) {
+        for buf in [&mut self.output, &mut self.bus_send] {
+            buf.chunks_mut(stride).for_each(|c| c[..n].fill(0.0));
+        }
+    }
+}
+
+// Renders the voices in tasks of VOICES_PER_TASK slots on rayon's pool, each into its own
+// partial mix, then adds the partial mixes into `out` and `bus_send` in task order.
+pub(crate) fn mix_voices(
+    mixing: &VoiceMixing,
+    voices: &mut [Voice],
+    dry: &mut [f32],
+    lanes: &mut VoiceLanes,
+    partials: &mut Vec<PartialMix>,
+    out: &mut [f32],
+    bus_send: &mut [f32],
+) {
+    if voices.len() <= VOICES_PER_TASK {
+        mixing.mix_all(voices, 0, dry, lanes, out, bus_send);
+        return;
+    }
+    let (stride, n) = (mixing.stride, mixing.n);
+    let (out_len, bus_len) = (out.len(), bus_send.len());
+    partials.resize_with(voices.len().div_ceil(VOICES_PER_TASK), PartialMix::default);
+    voices
+        .par_chunks_mut(VOICES_PER_TASK)
+        .zip(partials.par_iter_mut())
+        .enumerate()
+        .for_each(|(task, (voices, partial))| {
+            partial.sounding = voices.iter().any(Voice::is_sounding);
+            if !partial.sounding {
+                return;
+            }
+            partial.fit(stride, out_len, bus_len);
+            partial.clear(stride, n);
+            let PartialMix { dry, lanes, output, bus_send, .. } = partial;
+            mixing.mix_all(voices, task * VOICES_PER_TASK, dry, lanes, output, bus_send);
+        });
+    for partial in partials.iter().filter(|p| p.sounding) {
+        for (into, from) in [(&mut *out, &partial.output), (&mut *bus_send, &partial.bus_send)] {
+            for (into, from) in into.chunks_mut(stride).zip(from.chunks(stride)) {
+                simd::add(&mut into[..n], &from[..n]);
+            }
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use alloc::string::{String, ToString};
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+use crate::clamp;
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Every per-voice parameter set_param accepts, numbered as in param_info().
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Param {
+    Rpm = 0,
+    Blades = 1,
+    Gain = 2,
+    EngineMix = 3,
+    CavMix = 4,
+    BioMix = 5,
+    BioType = 6,
+    BioRate = 7,
+    ShaftRate = 8,
+    Load = 9,
+    RpmJitter = 10,
+    ClassProfile = 11,
+    CavitationLevel = 12,
+    Range = 13,
+    SourceDepth = 14,
+    ReverbSend = 15,
+    Bearing = 16,
+    TargetStrength = 17,
+    TargetLength = 18,
+    BearingWander = 19,
+    BearingJitter = 20,
+    Elevation = 21,
+    Variation = 22,
+    GainDb = 23,
+    EngineMixDb = 24,
+    CavMixDb = 25,
+    BioMixDb = 26,
+    ReverbSendDb = 27,
+}
+
+impl Param {
+    pub(crate) fn from_id(id: u32) -> Option<Self> {
+        PARAM_SPECS.get(id as usize).map(|spec| spec.param)
+    }
+
+    pub(crate) fn spec(self) -> &'static ParamSpec {
+        &PARAM_SPECS[self as usize]
+    }
+
+    // Parameters that select a mode rather than a level; these can't be modulated.
+    pub(crate) fn is_discrete(self) -> bool {
+        matches!(self, Self::Blades | Self::BioType | Self::ClassProfile)
+    }
+
+    // The linear parameter a dB variant stands for. Setting either sets both.
+    pub(crate) fn as_linear(self) -> Option<Self> {
+        match self {
+            Self::GainDb => Some(Self::Gain),
+            Self::EngineMixDb => Some(Self::EngineMix),
+            Self::CavMixDb => Some(Self::CavMix),
+            Self::BioMixDb => Some(Self::BioMix),
+            Self::ReverbSendDb => Some(Self::ReverbSend),
+            _ => None,
+        }
+    }
+
+    // Values set_param takes: anything finite, and -inf for the dB variants.
+    pub(crate) fn accepts(self, value: f32) -> bool {
+        value.is_finite() || (value == f32::NEG_INFINITY && self.as_linear().is_some())
+    }
+}
+
+// dB variants treat the floor and anything below it, -inf included, as silence.
+pub(crate) const DB_FLOOR: f32 = -120.0;
+
+pub(crate) fn db_to_linear(db: f32) -> f32 {
+    if db <= DB_FLOOR {
+        0.0
+    } else {
+        10.0f32.powf(db / 20.0)
+    }
+}
+
+// Silence reads back as -inf.
+pub(crate) fn linear_to_db(gain: f32) -> f32 {
+    if gain <= 0.0 {
+        f32::NEG_INFINITY
+    } else {
+        (20.0 * gain.log10()).max(DB_FLOOR)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Smoothing {
+    // Lands on the next sample.
+    Instant,
+    // Linear ramp over a caller-settable time (see set_param_ramp).
+    Ramped,
+    // Glides toward the new value inside the DSP.
+    Smoothed,
+    // Old and new generators overlap briefly.
+    Crossfade,
+}
+
+impl Smoothing {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Instant => "instant",
+            Self::Ramped => "ramped",
+            Self::Smoothed => "smoothed",
+            Self::Crossfade => "crossfade",
+        }
+    }
+}
+
+// One row of the parameter table. set_param clamps through these bounds, so the table the UI
+// reads and the limits the DSP enforces cannot drift apart.
+pub(crate) struct ParamSpec {
+    pub(crate) param: Param,
+    name: &'static str,
+    pub(crate) min: f32,
+    pub(crate) max: f32,
+    pub(crate) default: f32,
+    units: &'static str,
+    pub(crate) smoothing: Smoothing,
+    // Default ramp length for Ramped parameters.
+    pub(crate) ramp_ms: f32,
+    // Wraps into [min, max) instead of clamping.
+    wraps: bool,
+}
+
+impl ParamSpec {
+    pub(crate) fn apply(&self, value: f32) -> f32 {
+        if self.wraps {
+            self.min + (value - self.min).rem_euclid(self.max - self.min)
+        } else {
+            clamp(value, self.min, self.max)
+        }
+    }
+}
+
+const fn spec(
+    param: Param,
+    name: &'static str,
+    min: f32,
+    max: f32,
+    default: f32,
+    units: &'static str,
+    smoothing: Smoothing,
+) -> ParamSpec {
+    ParamSpec {
+        param,
+        name,
+        min,
+        max,
+        default,
+        units,
+        smoothing,
+        ramp_ms: 0.0,
+        wraps: false,
+    }
+}
+
+const fn ramped(
+    param: Param,
+    name: &'static str,
+    min: f32,
+    max: f32,
+    default: f32,
+    units: &'static str,
+    ramp_ms: f32,
+) -> ParamSpec {
+    ParamSpec {
+        ramp_ms,
+        ..spec(param, name, min, max, default, units, Smoothing::Ramped)
+    }
+}
+
+// Indexed by parameter id.
+pub(crate) const PARAM_SPECS: [ParamSpec; 28] = [
+    ramped(Param::Rpm, "rpm", 0.0, 3000.0, 0.0, "rpm", 15.0),
+    spec(Param::Blades, "blades", 1.0, 12.0, 5.0, "count", Smoothing::Instant),
+    ramped(Param::Gain, "gain", 0.0, 2.0, 1.0, "linear", 10.0),
+    ramped(Param::EngineMix, "engine_mix", 0.0, 1.5, 1.0, "linear", 10.0),
+    ramped(Param::CavMix, "cav_mix", 0.0, 1.5, 0.55, "linear", 10.0),
+    ramped(Param::BioMix, "bio_mix", 0.0, 1.5, 0.25, "linear", 10.0),
+    spec(Param::BioType, "bio_type", 0.0, 24.0, 0.0, "index", Smoothing::Crossfade),
+    spec(Param::BioRate, "bio_rate", 0.0, 1.0, 0.35, "normalized", Smoothing::Instant),
+    // 0 derives the shaft rate from RPM.
+    ramped(Param::ShaftRate, "shaft_rate", 0.0, 120.0, 0.0, "Hz", 15.0),
+    ramped(Param::Load, "load", 0.0, 1.0, 0.45, "normalized", 20.0),
+    spec(Param::RpmJitter, "rpm_jitter", 0.0, 1.0, 0.12, "normalized", Smoothing::Instant),
+    spec(Param::ClassProfile, "class_profile", 0.0, 4.0, 0.0, "index", Smoothing::Instant),
+    ramped(Param::CavitationLevel, "cavitation_level", 0.0, 1.0, 0.35, "normalized", 20.0),
+    // 0 bypasses propagation loss entirely.
+    spec(Param::Range, "range", 0.0, 200_000.0, 0.0, "m", Smoothing::Smoothed),
+    spec(Param::SourceDepth, "source_depth", 0.0, 11_000.0, 50.0, "m", Smoothing::Smoothed),
+    ramped(Param::ReverbSend, "reverb_send", 0.0, 1.0, 0.0, "linear", 10.0),
+    ParamSpec {
+        wraps: true,
+        ..spec(Param::Bearing, "bearing", 0.0, 360.0, 0.0, "deg", Smoothing::Smoothed)
+    },
+    spec(Param::TargetStrength, "target_strength", -40.0, 40.0, 10.0, "dB", Smoothing::Instant),
+    spec(Param::TargetLength, "target_length", 0.0, 400.0, 80.0, "m", Smoothing::Instant),
+    spec(Param::BearingWander, "bearing_wander", 0.0, 45.0, 0.0, "deg", Smoothing::Instant),
+    spec(Param::BearingJitter, "bearing_jitter", 0.0, 45.0, 0.0, "deg", Smoothing::Instant),
+    spec(Param::Elevation, "elevation", -90.0, 90.0, 0.0, "deg", Smoothing::Smoothed),
+    // How far this instance strays from its preset; 0 renders the preset exactly.
+    spec(Param::Variation, "variation", 0.0, 1.0, 0.0, "normalized", Smoothing::Instant),
+    // The same levels as gain, the mixes and the reverb send, in dB for faders and fades that
+    // spend their time near silence. The top of each range is its linear maximum.
+    ramped(Param::GainDb, "gain_db", DB_FLOOR, 6.0206, 0.0, "dB", 10.0),
+    ramped(Param::EngineMixDb, "engine_mix_db", DB_FLOOR, 3.5218, 0.0, "dB", 10.0),
+    ramped(Param::CavMixDb, "cav_mix_db", DB_FLOOR, 3.5218, -5.1927, "dB", 10.0),
+    ramped(Param::BioMixDb, "bio_mix_db", DB_FLOOR, 3.5218, -12.0412, "dB", 10.0),
+    ramped(Param::ReverbSendDb, "reverb_send_db", DB_FLOOR, 0.0, DB_FLOOR, "dB", 10.0),
+];
+
+pub(crate) const PARAM_COUNT: usize = PARAM_SPECS.len();
+
+// Linear ramp toward the last value set; a zero-length ramp jumps.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Ramp {
+    pub(crate) value: f32,
+    pub(crate) target: f32,
+    step: f32,
+    remaining: u32,
+}
+
+impl Ramp {
+    pub(crate) fn new(value: f32) -> Self {
+        Self {
+            value,
+            target: value,
+            step: 0.0,
+            remaining: 0,
+        }
+    }
+
+    pub(crate) fn set(&mut self, target: f32, samples: u32) {
+        self.target = target;
+        if samples == 0 {
+            self.value = target;
+            self.remaining = 0;
+        } else {
+            self.step = (target - self.value) / samples as f32;
+            self.remaining = samples;
+        }
+    }
+
+    // Pins both value and target, abandoning any ramp in flight.
+    pub(crate) fn hold(&mut self, value: f32) {
+        self.value = value;
+        self.target = value;
+        self.remaining = 0;
+    }
+
+    // Stretches the ramp in flight so it still ends at the same time after a sample-rate change.
+    pub(crate) fn rescale(&mut self, ratio: f32) {
+        if self.remaining > 0 {
+            self.remaining = crate::scale_samples(self.remaining, ratio).max(1);
+            self.step = (self.target - self.value) / self.remaining as f32;
+        }
+    }
+
+    pub(crate) fn is_settled(&self) -> bool {
+        self.remaining == 0
+    }
+
+    #[inline]
+    pub(crate) fn next(&mut self) -> f32 {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            self.value = if self.remaining == 0 { self.target } else { self.value + self.step };
+        }
+        self.value
+    }
+
+    // Moves `samples` along the ramp without producing them.
+    pub(crate) fn advance(&mut self, samples: u32) {
+        if samples >= self.remaining {
+            self.value = self.target;
+            self.remaining = 0;
+        } else {
+            self.remaining -= samples;
+            self.value = self.target - self.step * self.remaining as f32;
+        }
+    }
+
+    // next() for every sample of `out`.
+    #[inline]
+    pub(crate) fn fill(&mut self, out: &mut [f32]) {
+        if self.remaining == 0 {
+            out.fill(self.value);
+        } else {
+            out.iter_mut().for_each(|v| *v = self.next());
+        }
+    }
+}
+
+pub(crate) fn param_spec(id: u32) -> Option<&'static ParamSpec> {
+    Param::from_id(id).map(Param::spec)
+}
+
+// Read-only view of one parameter's metadata for JS.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub struct ParamInfo {
+    index: usize,
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+impl ParamInfo {
+    #[cfg_attr(feature = "std", wasm_bindgen(getter))]
+    pub fn id(&self) -> u32 {
+        PARAM_SPECS[self.index].param as u32
+    }
+
+    #[cfg_attr(feature = "std", wasm_bindgen(getter))]
+    pub fn name(&self) -> String {
+        PARAM_SPECS[self.index].name.to_string()
+    }
+
+    #[cfg_attr(feature = "std", wasm_bindgen(getter))]
+    pub fn min(&self) -> f32 {
+        PARAM_SPECS[self.index].min
+    }
+
+    #[cfg_attr(feature = "std", wasm_bindgen(getter))]
+    pub fn max(&self) -> f32 {
+        PARAM_SPECS[self.index].max
+    }
+
+    #[cfg_attr(feature = "std", wasm_bindgen(getter))]
+    pub fn default_value(&self) -> f32 {
+        PARAM_SPECS[self.index].default
+    }
+
+    #[cfg_attr(feature = "std", wasm_bindgen(getter))]
+    pub fn units(&self) -> String {
+        PARAM_SPECS[self.index].units.to_string()
+    }
+
+    // "instant", "ramped", "smoothed" or "crossfade".
+    #[cfg_attr(feature = "std", wasm_bindgen(getter))]
+    pub fn smoothing(&self) -> String {
+        PARAM_SPECS[self.index].smoothing.as_str().to_string()
+    }
+
+    // Ramp length a Ramped parameter starts with; 0 for the others.
+    #[cfg_attr(feature = "std", wasm_bindgen(getter))]
+    pub fn default_ramp_ms(&self) -> f32 {
+        PARAM_SPECS[self.index].ramp_ms
+    }
+
+    // True when out-of-range values wrap (bearing) rather than clamp.
+    #[cfg_attr(feature = "std", wasm_bindgen(getter))]
+    pub fn wraps(&self) -> bool {
+        PARAM_SPECS[self.index].wraps
+    }
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_count() -> u32 {
+    PARAM_SPECS.len() as u32
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn param_info(param_id: u32) -> Option<ParamInfo> {
+    param_spec(param_id).map(|spec| ParamInfo {
+        index: spec.param as usize,
+    })
+}
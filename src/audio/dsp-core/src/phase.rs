@@ -0,0 +1,33 @@
+use core::f64::consts::TAU;
+
+use serde::{Deserialize, Serialize};
+
+// An oscillator's phase in radians, held in [0, 2π) and accumulated in f64. In f32 a slow
+// oscillator's step is only a few units in the last place of the phase it is added to, so
+// rounding pulls its rate: a 0.1 Hz LFO runs 0.4% slow, and over half an hour at 48 kHz
+// falls most of a cycle behind. Read out in f32 for the generators, which lose nothing by it.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct Phase(f64);
+
+impl Phase {
+    // Moves on one sample of an oscillator at `hz`, which may be negative. True when that
+    // completes a cycle, for oscillators that time events by theirs.
+    #[inline]
+    pub(crate) fn advance(&mut self, hz: f32, sample_rate: f32) -> bool {
+        self.0 += TAU * hz as f64 / sample_rate as f64;
+        if self.0 >= TAU {
+            self.0 -= TAU;
+            return true;
+        }
+        if self.0 < 0.0 {
+            self.0 += TAU;
+        }
+        false
+    }
+
+    #[inline]
+    pub(crate) fn radians(self) -> f32 {
+        self.0 as f32
+    }
+}
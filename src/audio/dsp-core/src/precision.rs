@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+use crate::sine::{cosine, fold, sine};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// How closely the synthesis voices' generators compute their oscillators and waveshapers.
+// Reduced is for hosts too slow for a full scene, such as old mobile WebViews: in wasm it about
+// halves the cost of a cavitating voice. Its sines are good to about 7e-5 (-83 dB), the
+// saturators to about 1.4e-3 and the cavitation pulse shapes to about 0.3%; a whole scene
+// lands around -45 dB from Full. Everything outside the voice generators, and every random
+// stream, is the same in both.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Precision {
+    #[default]
+    Full = 0,
+    Reduced = 1,
+}
+
+// The transcendental functions the voice generators call per sample. They are generic over
+// it, so each precision gets its own copy of the generator loops instead of a branch per call.
+pub(crate) trait Math {
+    fn sin(x: f32) -> f32;
+    fn cos(x: f32) -> f32;
+    fn tanh(x: f32) -> f32;
+    // Only called with bases in or just around 0..1.
+    fn powf(base: f32, exp: f32) -> f32;
+}
+
+pub(crate) struct FullMath;
+
+impl Math for FullMath {
+    #[inline]
+    fn sin(x: f32) -> f32 {
+        sine(x)
+    }
+
+    #[inline]
+    fn cos(x: f32) -> f32 {
+        cosine(x)
+    }
+
+    #[inline]
+    fn tanh(x: f32) -> f32 {
+        x.tanh()
+    }
+
+    #[inline]
+    fn powf(base: f32, exp: f32) -> f32 {
+        base.powf(exp)
+    }
+}
+
+// Degree-5 odd minimax fit of sin on [-π/2, π/2].
+const S1: f32 = 0.999_696_8;
+const S3: f32 = -0.165_673_08;
+const S5: f32 = 0.007_514_377;
+
+// Minimax fits of log2(1 + t) and 2^t - 1 on [0, 1].
+const L1: f32 = 1.438_948_4;
+const L2: f32 = -0.677_150_8;
+const L3: f32 = 0.318_213_22;
+const L4: f32 = -0.080_010_9;
+const E1: f32 = 0.695_542_7;
+const E2: f32 = 0.225_371_6;
+const E3: f32 = 0.079_085_7;
+
+pub(crate) struct ReducedMath;
+
+impl Math for ReducedMath {
+    #[inline]
+    fn sin(x: f32) -> f32 {
+        let x = fold(x);
+        let x2 = x * x;
+        x * (S1 + x2 * (S3 + x2 * S5))
+    }
+
+    #[inline]
+    fn cos(x: f32) -> f32 {
+        Self::sin(x + core::f32::consts::FRAC_PI_2)
+    }
+
+    // [5/4] Padé approximant, which reaches ±1 near |x| = 4.5 and is clamped there.
+    #[inline]
+    fn tanh(x: f32) -> f32 {
+        let x2 = x * x;
+        let y = x * (945.0 + x2 * (105.0 + x2)) / (945.0 + x2 * (420.0 + 15.0 * x2));
+        y.clamp(-1.0, 1.0)
+    }
+
+    // 2^(exp * log2(base)), each half from the float's exponent bits and a short polynomial
+    // over the mantissa. A base of zero or below gives zero.
+    #[inline]
+    fn powf(base: f32, exp: f32) -> f32 {
+        if base <= 0.0 {
+            return 0.0;
+        }
+        let bits = base.to_bits();
+        let t = f32::from_bits(bits & 0x007f_ffff | 0x3f80_0000) - 1.0;
+        let log2 = ((bits >> 23) as i32 - 127) as f32 + t * (L1 + t * (L2 + t * (L3 + t * L4)));
+        // Biased, the power is never below 1, so truncating it floors it.
+        let y = (exp * log2).clamp(-126.0, 127.0) + 127.0;
+        let biased = y as u32;
+        let t = y - biased as f32;
+        f32::from_bits(biased << 23) * (1.0 + t * (E1 + t * (E2 + t * E3)))
+    }
+}
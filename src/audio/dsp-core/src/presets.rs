@@ -0,0 +1,133 @@
+use alloc::string::{String, ToString};
+
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+use crate::Param;
+use crate::{PRESET_DESTROYER, PRESET_DIESEL_SUB, PRESET_MERCHANT, PRESET_SSN, PRESET_TRAWLER};
+
+// A vessel class as a set of parameter values. Placement (bearing, range, gain) is left to
+// the caller; everything that identifies the class by ear or on active sonar is here.
+pub(crate) struct VesselPreset {
+    id: u32,
+    name: &'static str,
+    pub(crate) values: &'static [(Param, f32)],
+}
+
+// Indexed by preset id.
+pub(crate) const PRESETS: [VesselPreset; 5] = [
+    // Small single-screw diesel: fast-turning, three blades, noisy and cavitating at any speed.
+    VesselPreset {
+        id: PRESET_TRAWLER,
+        name: "fishing_trawler",
+        values: &[
+            (Param::Rpm, 180.0),
+            (Param::Blades, 3.0),
+            (Param::ShaftRate, 3.0),
+            (Param::Load, 0.55),
+            (Param::RpmJitter, 0.22),
+            (Param::ClassProfile, 3.0),
+            (Param::CavitationLevel, 0.45),
+            (Param::EngineMix, 1.0),
+            (Param::CavMix, 0.7),
+            (Param::BioMix, 0.0),
+            (Param::SourceDepth, 3.0),
+            (Param::TargetStrength, 5.0),
+            (Param::TargetLength, 30.0),
+        ],
+    },
+    // Slow-speed two-stroke on a large five-blade screw; strong low machinery lines.
+    VesselPreset {
+        id: PRESET_MERCHANT,
+        name: "merchant",
+        values: &[
+            (Param::Rpm, 110.0),
+            (Param::Blades, 5.0),
+            (Param::ShaftRate, 110.0 / 60.0),
+            (Param::Load, 0.5),
+            (Param::RpmJitter, 0.1),
+            (Param::ClassProfile, 2.0),
+            (Param::CavitationLevel, 0.35),
+            (Param::EngineMix, 1.1),
+            (Param::CavMix, 0.6),
+            (Param::BioMix, 0.0),
+            (Param::SourceDepth, 8.0),
+            (Param::TargetStrength, 25.0),
+            (Param::TargetLength, 190.0),
+        ],
+    },
+    // Gas-turbine warship at cruise: quieter machinery, but twin screws turning fast.
+    VesselPreset {
+        id: PRESET_DESTROYER,
+        name: "destroyer",
+        values: &[
+            (Param::Rpm, 180.0),
+            (Param::Blades, 5.0),
+            (Param::ShaftRate, 3.0),
+            (Param::Load, 0.65),
+            (Param::RpmJitter, 0.1),
+            (Param::ClassProfile, 2.0),
+            (Param::CavitationLevel, 0.5),
+            (Param::EngineMix, 0.9),
+            (Param::CavMix, 0.8),
+            (Param::BioMix, 0.0),
+            (Param::SourceDepth, 5.0),
+            (Param::TargetStrength, 15.0),
+            (Param::TargetLength, 155.0),
+        ],
+    },
+    // Diesel-electric boat on the battery: barely turning, almost no cavitation.
+    VesselPreset {
+        id: PRESET_DIESEL_SUB,
+        name: "diesel_sub",
+        values: &[
+            (Param::Rpm, 60.0),
+            (Param::Blades, 7.0),
+            (Param::ShaftRate, 1.0),
+            (Param::Load, 0.2),
+            (Param::RpmJitter, 0.03),
+            (Param::ClassProfile, 1.0),
+            (Param::CavitationLevel, 0.03),
+            (Param::EngineMix, 0.6),
+            (Param::CavMix, 0.2),
+            (Param::BioMix, 0.0),
+            (Param::SourceDepth, 100.0),
+            (Param::TargetStrength, 10.0),
+            (Param::TargetLength, 70.0),
+        ],
+    },
+    // Nuclear attack boat at patrol speed: steady reactor-plant tonals, skewed seven-blade screw.
+    VesselPreset {
+        id: PRESET_SSN,
+        name: "ssn",
+        values: &[
+            (Param::Rpm, 90.0),
+            (Param::Blades, 7.0),
+            (Param::ShaftRate, 1.5),
+            (Param::Load, 0.35),
+            (Param::RpmJitter, 0.06),
+            (Param::ClassProfile, 1.0),
+            (Param::CavitationLevel, 0.1),
+            (Param::EngineMix, 0.8),
+            (Param::CavMix, 0.3),
+            (Param::BioMix, 0.0),
+            (Param::SourceDepth, 150.0),
+            (Param::TargetStrength, 15.0),
+            (Param::TargetLength, 110.0),
+        ],
+    },
+];
+
+pub(crate) fn preset(id: u32) -> Option<&'static VesselPreset> {
+    PRESETS.get(id as usize).filter(|preset| preset.id == id)
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn preset_count() -> u32 {
+    PRESETS.len() as u32
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub fn preset_name(preset_id: u32) -> Option<String> {
+    preset(preset_id).map(|preset| preset.name.to_string())
+}
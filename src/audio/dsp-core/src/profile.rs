@@ -0,0 +1,158 @@
+#[cfg(feature = "profile")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+// The parts of process() that stage_profile() times. Engine, Cavitation and Bio are the voice
+// generators and run inside Voices, which covers everything else done per voice too: control
+// lanes, propagation, panning and bus sends. The rest follow one another.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfileStage {
+    // Scenario and replay steps, modulation, tracks and propagation updates ahead of the mix.
+    Control = 0,
+    Voices = 1,
+    Engine = 2,
+    Cavitation = 3,
+    Bio = 4,
+    // Reverb and the hull-borne return.
+    Buses = 5,
+    // Own-ship noise, the surface weather bed, ping reverberation and the ambient noise field.
+    Ambience = 6,
+    // Operator band, leveler, limiter and the hydrophone copy.
+    Master = 7,
+    // Master metering, headroom tracking, the interleaved and 16-bit copies of the block and
+    // the slice of any requested DEMON spectrum.
+    Analysis = 8,
+}
+
+#[cfg(feature = "profile")]
+pub(crate) const STAGE_COUNT: usize = 9;
+
+// Time and work in one stage since the counters were last reset. For the generators, frames
+// and calls count each voice separately, and with the threads feature their time is summed
+// across workers, so it can exceed the Voices time it falls inside.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy)]
+pub struct StageProfile {
+    pub ms: f64,
+    pub frames: u64,
+    pub calls: u32,
+}
+
+#[cfg(feature = "profile")]
+#[derive(Default)]
+struct Counter {
+    nanos: AtomicU64,
+    frames: AtomicU64,
+    calls: AtomicU64,
+}
+
+// Per-stage counters. Atomic so that workers rendering voices in parallel can all add to them;
+// while profiling is off no clock is read and nothing is counted. Builds without the profile
+// feature have no counters at all, so targets lacking 64-bit atomics need none.
+#[derive(Default)]
+pub(crate) struct Profile {
+    enabled: bool,
+    #[cfg(feature = "profile")]
+    counters: [Counter; STAGE_COUNT],
+}
+
+impl Profile {
+    // Fails when the build has no clock to read.
+    pub(crate) fn set_enabled(&mut self, enabled: bool) -> bool {
+        if enabled && now_ms().is_none() {
+            return false;
+        }
+        self.enabled = enabled;
+        true
+    }
+
+    // Marks the start of a stage; None while profiling is off.
+    #[inline]
+    pub(crate) fn start(&self) -> Option<f64> {
+        if self.enabled {
+            now_ms()
+        } else {
+            None
+        }
+    }
+
+    // Charges the time since `start` and `frames` frames to `stage`.
+    #[inline]
+    pub(crate) fn record(&self, stage: ProfileStage, start: Option<f64>, frames: usize) {
+        let Some(start) = start else {
+            return;
+        };
+        let Some(end) = now_ms() else {
+            return;
+        };
+        self.count(stage, end - start, frames);
+    }
+
+    #[cfg(feature = "profile")]
+    fn count(&self, stage: ProfileStage, ms: f64, frames: usize) {
+        let counter = &self.counters[stage as usize];
+        let nanos = (ms.max(0.0) * 1.0e6) as u64;
+        counter.nanos.fetch_add(nanos, Ordering::Relaxed);
+        counter.frames.fetch_add(frames as u64, Ordering::Relaxed);
+        counter.calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg(not(feature = "profile"))]
+    fn count(&self, _stage: ProfileStage, _ms: f64, _frames: usize) {}
+
+    #[cfg(feature = "profile")]
+    pub(crate) fn stage(&self, stage: ProfileStage) -> StageProfile {
+        let counter = &self.counters[stage as usize];
+        StageProfile {
+            ms: counter.nanos.load(Ordering::Relaxed) as f64 * 1.0e-6,
+            frames: counter.frames.load(Ordering::Relaxed),
+            calls: counter.calls.load(Ordering::Relaxed).min(u32::MAX as u64) as u32,
+        }
+    }
+
+    #[cfg(not(feature = "profile"))]
+    pub(crate) fn stage(&self, _stage: ProfileStage) -> StageProfile {
+        StageProfile {
+            ms: 0.0,
+            frames: 0,
+            calls: 0,
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        #[cfg(feature = "profile")]
+        {
+            self.counters = Default::default();
+        }
+    }
+}
+
+#[cfg(all(feature = "profile", target_arch = "wasm32"))]
+#[wasm_bindgen]
+extern "C" {
+    // Missing from some audio worklet scopes, where it throws instead.
+    #[wasm_bindgen(catch, js_namespace = performance, js_name = now)]
+    fn performance_now() -> Result<f64, JsValue>;
+}
+
+#[cfg(all(feature = "profile", target_arch = "wasm32"))]
+fn now_ms() -> Option<f64> {
+    performance_now().ok()
+}
+
+#[cfg(all(feature = "profile", not(target_arch = "wasm32")))]
+fn now_ms() -> Option<f64> {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    Some(EPOCH.get_or_init(Instant::now).elapsed().as_secs_f64() * 1.0e3)
+}
+
+#[cfg(not(feature = "profile"))]
+fn now_ms() -> Option<f64> {
+    None
+}
@@ -0,0 +1,669 @@
+use alloc::{vec, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{clamp, flush_denormal, vec_bytes, Environment};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Extra loss (dB) in the shadow between the direct-path region and the convergence zones.
+const CZ_SHADOW_DB: f32 = 12.0;
+// Zones past the third are too weak and smeared to matter.
+const CZ_COUNT: usize = 3;
+
+#[inline]
+fn spreading_db(r: f32, transition: f32) -> f32 {
+    let rt = transition.max(1.0);
+    if r <= rt {
+        20.0 * r.log10()
+    } else {
+        20.0 * rt.log10() + 10.0 * (r / rt).log10()
+    }
+}
+
+// Transmission loss in dB: spherical spreading out to the transition range, cylindrical
+// beyond it, plus frequency-independent volume absorption.
+#[inline]
+pub(crate) fn transmission_loss_db(range_m: f32, env: &Environment) -> f32 {
+    let r = range_m.max(1.0);
+    spreading_db(r, env.spreading_transition) + env.absorption_db_per_km * r * 0.001
+}
+
+// Thorp's seawater absorption in dB/km for a frequency in Hz.
+#[inline]
+pub(crate) fn thorp_db_per_km(frequency_hz: f32) -> f32 {
+    let f2 = (frequency_hz * 0.001).powi(2);
+    0.11 * f2 / (1.0 + f2) + 44.0 * f2 / (4100.0 + f2) + 2.75e-4 * f2 + 0.003
+}
+
+// Frequency at which Thorp absorption over `range_m` (scaled by `strength`) reaches 3 dB: the
+// corner of the one-pole that stands in for it. Infinite when nothing audible is lost.
+pub(crate) fn absorption_corner_hz(range_m: f32, strength: f32) -> f32 {
+    let km = range_m * 0.001 * strength;
+    let (mut lo, mut hi) = (20.0f32, 40_000.0f32);
+    if km <= 0.0 || thorp_db_per_km(hi) * km < 3.0 {
+        return f32::INFINITY;
+    }
+    if thorp_db_per_km(lo) * km >= 3.0 {
+        return lo;
+    }
+    // Absorption rises monotonically with frequency, so bisect in the log domain.
+    for _ in 0..20 {
+        let mid = (lo * hi).sqrt();
+        if thorp_db_per_km(mid) * km < 3.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    (lo * hi).sqrt()
+}
+
+// Range-dependent departure from plain spreading (dB; negative is a gain). A surface duct
+// traps sound when both ends sit inside it, so spreading turns cylindrical past the skip
+// distance at the cost of a leakage rate. Convergence zones refocus deep-refracted energy
+// into annuli at multiples of the zone interval, with a shadow between them.
+pub(crate) fn range_anomaly_db(range_m: f32, source_depth: f32, env: &Environment) -> f32 {
+    let r = range_m.max(1.0);
+    let mut anomaly = 0.0;
+
+    if env.duct_depth > 0.0 && source_depth < env.duct_depth && env.listener_depth < env.duct_depth {
+        let skip = (env.duct_depth * 2.0).max(env.reference_range);
+        if r > skip {
+            let ordinary = spreading_db(r, env.spreading_transition);
+            let ducted = spreading_db(skip, env.spreading_transition) + 10.0 * (r / skip).log10();
+            anomaly += ducted.min(ordinary) - ordinary + env.duct_leak_db_per_km * (r - skip) * 0.001;
+        }
+    }
+
+    if env.cz_interval > 0.0 {
+        let interval = env.cz_interval;
+        let t = clamp((r / interval - 0.2) / 0.15, 0.0, 1.0);
+        let shadow = t * t * (3.0 - 2.0 * t);
+        let mut focus = 0.0f32;
+        let mut gain_db = 0.0;
+        for n in 1..=CZ_COUNT {
+            let nf = n as f32;
+            // Each successive zone is wider and weaker.
+            let width = env.cz_width.max(1.0) * nf.sqrt();
+            let x = (r - nf * interval) / width;
+            let bump = (-x * x).exp();
+            if bump > focus {
+                focus = bump;
+                gain_db = (env.cz_gain_db - 4.0 * (nf - 1.0)).max(0.0);
+            }
+        }
+        anomaly += shadow * CZ_SHADOW_DB * (1.0 - focus) - gain_db * focus;
+    }
+
+    anomaly
+}
+
+// Extra loss (dB) for a path that crosses the thermocline. The layer is treated as a
+// +/- 10 m transition so a boat easing through it fades rather than switches.
+#[inline]
+pub(crate) fn layer_loss_db(source_depth: f32, env: &Environment) -> f32 {
+    if env.layer_depth <= 0.0 {
+        return 0.0;
+    }
+    let side = |z: f32| {
+        let t = clamp((z - env.layer_depth) / 20.0 + 0.5, 0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    };
+    let crossing = (side(source_depth) - side(env.listener_depth)).abs();
+    env.layer_loss_db * crossing
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum ChannelModel {
+    // Horizontal-range spreading plus constant-speed image bounces.
+    Spreading = 0,
+    // Eigenrays traced through a two-layer sound-speed profile.
+    Rays = 1,
+}
+
+impl ChannelModel {
+    pub(crate) fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Spreading),
+            1 => Some(Self::Rays),
+            _ => None,
+        }
+    }
+}
+
+// One arrival at the receiver. `gain` is signed (surface bounces invert) and already
+// includes spreading, absorption, boundary and layer losses relative to the reference range.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Eigenray {
+    pub(crate) travel_s: f32,
+    pub(crate) gain: f32,
+    // Low-pass corner imposed by boundary interactions; infinite for an untouched path.
+    #[serde(with = "crate::snapshot::unbounded")]
+    pub(crate) cutoff_hz: f32,
+}
+
+pub(crate) const MAX_EIGENRAYS: usize = 1 + MAX_MULTIPATH_TAPS;
+
+#[inline]
+fn surface_reflection(env: &Environment) -> (f32, f32) {
+    // Rough seas scatter energy and strip high frequencies from the surface bounce.
+    let coef = -clamp(0.95 - 0.07 * env.sea_state, 0.3, 0.95);
+    (coef, 9000.0 / (1.0 + env.sea_state))
+}
+
+// Traces direct, surface, bottom, surface-bottom and bottom-surface eigenrays. Geometry uses
+// unfolded straight segments; the sound-speed profile sets travel time per layer, and layer
+// crossings lose energy by grazing angle, with rays beyond the critical angle shadowed.
+pub(crate) fn trace_eigenrays(range: f32, source_depth: f32, env: &Environment) -> [Option<Eigenray>; MAX_EIGENRAYS] {
+    let depth = env.water_depth.max(1.0);
+    let zs = source_depth.clamp(0.0, depth);
+    let zr = env.listener_depth.clamp(0.0, depth);
+    let layer = if env.layer_depth > 0.0 && env.layer_depth < depth {
+        env.layer_depth
+    } else {
+        depth
+    };
+    let c_upper = env.sound_speed_upper.max(1.0);
+    let c_lower = env.sound_speed_lower.max(1.0);
+    let (surface_coef, surface_hz) = surface_reflection(env);
+    let (bottom_coef, bottom_hz) = env.bottom_type.reflection();
+    let reference_loss = transmission_loss_db(env.reference_range, env);
+    let anomaly = range_anomaly_db(range, zs, env);
+
+    let paths: [&[f32]; MAX_EIGENRAYS] = [
+        &[zs, zr],
+        &[zs, 0.0, zr],
+        &[zs, depth, zr],
+        &[zs, 0.0, depth, zr],
+        &[zs, depth, 0.0, zr],
+    ];
+
+    let mut rays = [None; MAX_EIGENRAYS];
+    for (ray, points) in rays.iter_mut().zip(paths.iter()) {
+        let mut vertical = 0.0;
+        let mut upper = 0.0;
+        let mut crossings = 0;
+        for seg in points.windows(2) {
+            vertical += (seg[1] - seg[0]).abs();
+            upper += (seg[1].min(layer) - seg[0].min(layer)).abs();
+            if (seg[0] < layer) != (seg[1] < layer) {
+                crossings += 1;
+            }
+        }
+        let length = (range * range + vertical * vertical).sqrt().max(1.0);
+        let upper_frac = if vertical > 0.0 {
+            upper / vertical
+        } else if zs < layer {
+            1.0
+        } else {
+            0.0
+        };
+        let travel_s = length * (upper_frac / c_upper + (1.0 - upper_frac) / c_lower);
+
+        let sin_grazing = vertical / length;
+        let cos_grazing = range / length;
+        let mut loss = transmission_loss_db(length.max(env.reference_range), env) - reference_loss + anomaly;
+        if crossings > 0 {
+            let trapped = cos_grazing * c_lower / c_upper > 1.0;
+            let per_crossing = if trapped {
+                env.layer_loss_db
+            } else {
+                env.layer_loss_db * (1.0 - sin_grazing) * 0.7
+            };
+            loss += per_crossing * crossings as f32;
+        }
+
+        let interior = &points[1..points.len() - 1];
+        let mut amplitude = 1.0;
+        let mut cutoff_hz = f32::INFINITY;
+        for &z in interior {
+            if z <= 0.0 {
+                amplitude *= surface_coef;
+                cutoff_hz = cutoff_hz.min(surface_hz);
+            } else {
+                amplitude *= bottom_coef;
+                cutoff_hz = cutoff_hz.min(bottom_hz);
+            }
+        }
+
+        *ray = Some(Eigenray {
+            travel_s,
+            gain: amplitude * 10.0f32.powf(-loss / 20.0),
+            cutoff_hz,
+        });
+    }
+    rays
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct VoicePropagation {
+    // Metres to the receiver; 0 leaves the voice unattenuated.
+    pub(crate) range: f32,
+    // Latest eigenrays when the ray model is active; index 0 is the direct path.
+    pub(crate) rays: [Option<Eigenray>; MAX_EIGENRAYS],
+    target_gain: f32,
+    gain: f32,
+    // Absorption low-pass: corner (Hz, infinite when bypassed) and one-pole coefficients.
+    #[serde(with = "crate::snapshot::unbounded")]
+    pub(crate) cutoff_hz: f32,
+    target_alpha: f32,
+    alpha: f32,
+    lp: f32,
+    primed: bool,
+}
+
+impl VoicePropagation {
+    pub(crate) fn new() -> Self {
+        Self {
+            range: 0.0,
+            rays: [None; MAX_EIGENRAYS],
+            target_gain: 1.0,
+            gain: 1.0,
+            cutoff_hz: f32::INFINITY,
+            target_alpha: 1.0,
+            alpha: 1.0,
+            lp: 0.0,
+            primed: false,
+        }
+    }
+
+    // Drops the absorption filter's memory and lands the glides on their targets.
+    pub(crate) fn clear(&mut self) {
+        self.gain = self.target_gain;
+        self.alpha = self.target_alpha;
+        self.lp = 0.0;
+    }
+
+    // One-way gain the voice is gliding toward.
+    pub(crate) fn target_gain(&self) -> f32 {
+        self.target_gain
+    }
+
+    pub(crate) fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    // Ranges inside the reference distance play at full level; loss is relative to it.
+    pub(crate) fn update(&mut self, source_depth: f32, env: &Environment, sample_rate: f32) {
+        if env.channel_model == ChannelModel::Rays && self.range > 0.0 {
+            self.rays = trace_eigenrays(self.range, source_depth, env);
+            self.target_gain = self.rays[0].map_or(0.0, |direct| direct.gain.abs());
+        } else {
+            self.rays = [None; MAX_EIGENRAYS];
+            let mut loss = layer_loss_db(source_depth, env);
+            if self.range > 0.0 {
+                let r = self.range.max(env.reference_range);
+                loss += transmission_loss_db(r, env) - transmission_loss_db(env.reference_range, env);
+                loss += range_anomaly_db(self.range, source_depth, env);
+            }
+            self.target_gain = 10.0f32.powf(-loss / 20.0);
+        }
+
+        self.cutoff_hz = if self.range > 0.0 {
+            absorption_corner_hz(self.range, env.absorption_filter)
+        } else {
+            f32::INFINITY
+        };
+        self.target_alpha = if self.cutoff_hz < sample_rate * 0.45 {
+            let rc = 1.0 / (crate::TWO_PI * self.cutoff_hz);
+            let dt = 1.0 / sample_rate.max(1.0);
+            dt / (rc + dt)
+        } else {
+            1.0
+        };
+
+        // A freshly placed voice starts at its range instead of gliding in from full level.
+        if !self.primed {
+            self.gain = self.target_gain;
+            self.alpha = self.target_alpha;
+            self.primed = true;
+        }
+    }
+
+    // Applies the smoothed loss and absorption low-pass to one sample. Both glide by `glide`
+    // so per-frame range updates from the host don't zipper.
+    #[inline]
+    pub(crate) fn process(&mut self, input: f32, glide: f32) -> f32 {
+        self.gain = flush_denormal(self.gain + glide * (self.target_gain - self.gain));
+        self.alpha += glide * (self.target_alpha - self.alpha);
+        self.lp = flush_denormal(self.lp + self.alpha * (input - self.lp));
+        self.lp * self.gain
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum BottomType {
+    Mud = 0,
+    Sand = 1,
+    Rock = 2,
+}
+
+impl BottomType {
+    pub(crate) fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Mud),
+            1 => Some(Self::Sand),
+            2 => Some(Self::Rock),
+            _ => None,
+        }
+    }
+
+    // Bottom-bounce amplitude and the corner of the high-frequency loss it imposes.
+    pub(crate) fn reflection(self) -> (f32, f32) {
+        match self {
+            Self::Mud => (0.25, 900.0),
+            Self::Sand => (0.5, 2200.0),
+            Self::Rock => (0.8, 5000.0),
+        }
+    }
+
+    // Scale on the reverb bus T60 and the damping it should run with.
+    pub(crate) fn reverb_character(self) -> (f32, f32) {
+        match self {
+            Self::Mud => (0.6, 0.75),
+            Self::Sand => (1.0, 0.55),
+            Self::Rock => (1.5, 0.3),
+        }
+    }
+}
+
+// Reverb tail scale for a water depth: bottom-surface round trips get rarer as the column
+// deepens, so energy lingers longer over deep water than over the shelf.
+pub(crate) fn depth_reverb_scale(water_depth: f32) -> f32 {
+    clamp((water_depth / 200.0).sqrt(), 0.5, 2.5)
+}
+
+// Regular depth grid in model space (+X east, +Z north), sampled bilinearly under own-ship so
+// the bottom bounce follows the terrain. Empty until the host uploads one.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Bathymetry {
+    origin_x: f32,
+    origin_z: f32,
+    cell: f32,
+    cols: usize,
+    rows: usize,
+    depths: Vec<f32>,
+}
+
+impl Bathymetry {
+    pub(crate) fn new() -> Self {
+        Self {
+            origin_x: 0.0,
+            origin_z: 0.0,
+            cell: 1.0,
+            cols: 0,
+            rows: 0,
+            depths: Vec::new(),
+        }
+    }
+
+    // `depths` is row-major, `cols` wide, with row 0 at `origin_z`.
+    pub(crate) fn load(&mut self, origin_x: f32, origin_z: f32, cell: f32, cols: usize, depths: &[f32]) {
+        self.origin_x = origin_x;
+        self.origin_z = origin_z;
+        self.cell = cell.max(1.0);
+        self.cols = cols;
+        self.rows = depths.len().checked_div(cols).unwrap_or(0);
+        self.depths = depths[..self.cols * self.rows]
+            .iter()
+            .map(|d| if d.is_finite() { d.max(1.0) } else { 1.0 })
+            .collect();
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.cols = 0;
+        self.rows = 0;
+        self.depths = Vec::new();
+    }
+
+    // Depth at a point, clamped to the grid's edge; None without a grid.
+    pub(crate) fn depth_at(&self, x: f32, z: f32) -> Option<f32> {
+        if self.depths.is_empty() {
+            return None;
+        }
+        let gx = clamp((x - self.origin_x) / self.cell, 0.0, (self.cols - 1) as f32);
+        let gz = clamp((z - self.origin_z) / self.cell, 0.0, (self.rows - 1) as f32);
+        let (c0, r0) = (gx as usize, gz as usize);
+        let (c1, r1) = ((c0 + 1).min(self.cols - 1), (r0 + 1).min(self.rows - 1));
+        let (fx, fz) = (gx - c0 as f32, gz - r0 as f32);
+        let at = |c: usize, r: usize| self.depths[r * self.cols + c];
+        let near = at(c0, r0) + (at(c1, r0) - at(c0, r0)) * fx;
+        let far = at(c0, r1) + (at(c1, r1) - at(c0, r1)) * fx;
+        Some(near + (far - near) * fz)
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.depths)
+    }
+}
+
+pub(crate) const SOUND_SPEED: f32 = 1500.0;
+pub(crate) const MAX_MULTIPATH_TAPS: usize = 4;
+// Longer bounces are attenuated enough to drop, and the cap bounds per-voice memory.
+const MAX_MULTIPATH_DELAY_S: f32 = 0.5;
+
+// A bounce arrival measured against the direct path.
+#[derive(Clone, Copy)]
+struct Bounce {
+    delay_s: f32,
+    // Signed amplitude relative to the direct arrival.
+    gain: f32,
+    cutoff_hz: f32,
+}
+
+// Surface, bottom, then the two double bounces. The ray model reuses its traced eigenrays;
+// otherwise the bounces come from constant-speed image sources.
+fn bounce_paths(propagation: &VoicePropagation, source_depth: f32, env: &Environment) -> [Option<Bounce>; MAX_MULTIPATH_TAPS] {
+    let mut bounces = [None; MAX_MULTIPATH_TAPS];
+
+    if let Some(direct) = propagation.rays[0] {
+        let reference = direct.gain.abs().max(1.0e-6);
+        for (bounce, ray) in bounces.iter_mut().zip(propagation.rays[1..].iter()) {
+            *bounce = ray.map(|ray| Bounce {
+                delay_s: ray.travel_s - direct.travel_s,
+                gain: clamp(ray.gain / reference, -10.0, 10.0),
+                cutoff_hz: ray.cutoff_hz,
+            });
+        }
+        return bounces;
+    }
+
+    let range = propagation.range;
+    let depth = env.water_depth.max(1.0);
+    let zs = source_depth.clamp(0.0, depth);
+    let zr = env.listener_depth.clamp(0.0, depth);
+    let direct = (range * range + (zs - zr) * (zs - zr)).sqrt().max(1.0);
+
+    let (surface_coef, surface_hz) = surface_reflection(env);
+    let (bottom_coef, bottom_hz) = env.bottom_type.reflection();
+    let paths = [
+        (-zs, surface_coef, surface_hz),
+        (2.0 * depth - zs, bottom_coef, bottom_hz),
+        (2.0 * depth + zs, surface_coef * bottom_coef, surface_hz.min(bottom_hz)),
+        (zs - 2.0 * depth, surface_coef * bottom_coef, surface_hz.min(bottom_hz)),
+    ];
+    for (bounce, &(image_z, coef, cutoff)) in bounces.iter_mut().zip(paths.iter()) {
+        let dz = image_z - zr;
+        let length = (range * range + dz * dz).sqrt();
+        *bounce = Some(Bounce {
+            delay_s: (length - direct) / SOUND_SPEED,
+            gain: coef * direct / length,
+            cutoff_hz: cutoff,
+        });
+    }
+    bounces
+}
+
+// Transmission loss (dB re 1 m) a voice at `range_m` is actually rendered with, evaluated at
+// one frequency: the direct path plus any enabled bounces summed in power, each bounce rolled
+// off by its boundary low-pass. A voice at the reference range plays at its nominal level.
+pub(crate) fn predicted_loss_db(range_m: f32, source_depth: f32, frequency_hz: f32, env: &Environment) -> f32 {
+    let mut propagation = VoicePropagation::new();
+    propagation.range = range_m.max(1.0);
+    // Only the gain and absorption corner are read, so any sample rate will do.
+    propagation.update(source_depth, env, 48_000.0);
+    let direct = propagation.target_gain();
+
+    let mut power = 1.0;
+    let taps = env.multipath_taps.min(MAX_MULTIPATH_TAPS);
+    for bounce in bounce_paths(&propagation, source_depth, env).iter().take(taps).flatten() {
+        if bounce.delay_s > MAX_MULTIPATH_DELAY_S {
+            continue;
+        }
+        let ratio = frequency_hz / bounce.cutoff_hz;
+        power += bounce.gain * bounce.gain / (1.0 + ratio * ratio);
+    }
+    let ratio = frequency_hz / propagation.cutoff_hz;
+    power *= direct * direct / (1.0 + ratio * ratio);
+
+    transmission_loss_db(env.reference_range, env) - 10.0 * power.max(1.0e-30).log10()
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct MultipathTap {
+    delay: f32,
+    target_delay: f32,
+    gain: f32,
+    target_gain: f32,
+    lp_alpha: f32,
+    lp: f32,
+}
+
+impl MultipathTap {
+    fn new() -> Self {
+        Self {
+            delay: 0.0,
+            target_delay: 0.0,
+            gain: 0.0,
+            target_gain: 0.0,
+            lp_alpha: 1.0,
+            lp: 0.0,
+        }
+    }
+}
+
+// Surface / bottom bounce copies of a voice, positioned with the method of images.
+// Taps in order: surface, bottom, surface-bottom, bottom-surface.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Multipath {
+    buffer: Vec<f32>,
+    write: usize,
+    taps: [MultipathTap; MAX_MULTIPATH_TAPS],
+    active_taps: usize,
+}
+
+impl Multipath {
+    pub(crate) fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            write: 0,
+            taps: [MultipathTap::new(); MAX_MULTIPATH_TAPS],
+            active_taps: 0,
+        }
+    }
+
+    // Empties the delay line and the taps' filters; the geometry stays.
+    pub(crate) fn clear(&mut self) {
+        self.buffer.fill(0.0);
+        for tap in &mut self.taps {
+            tap.lp = 0.0;
+        }
+    }
+
+    // Tap delays are in samples; scale them so the paths don't glide after a rate change.
+    pub(crate) fn rescale(&mut self, ratio: f32) {
+        for tap in &mut self.taps {
+            tap.delay *= ratio;
+            tap.target_delay *= ratio;
+        }
+    }
+
+    pub(crate) fn update(
+        &mut self,
+        propagation: &VoicePropagation,
+        source_depth: f32,
+        env: &Environment,
+        sample_rate: f32,
+    ) {
+        let range = propagation.range;
+        let taps = if range > 0.0 { env.multipath_taps.min(MAX_MULTIPATH_TAPS) } else { 0 };
+        if taps == 0 {
+            self.active_taps = 0;
+            return;
+        }
+
+        let capacity = (sample_rate * MAX_MULTIPATH_DELAY_S) as usize + 2;
+        if self.buffer.len() != capacity {
+            self.buffer = vec![0.0; capacity];
+            self.write = 0;
+        }
+
+        let max_delay = (capacity - 2) as f32;
+        let newly_active = self.active_taps == 0;
+        self.active_taps = taps;
+
+        let bounces = bounce_paths(propagation, source_depth, env);
+        for (tap, bounce) in self.taps.iter_mut().zip(bounces.iter()).take(taps) {
+            let Some(bounce) = bounce else {
+                tap.target_gain = 0.0;
+                continue;
+            };
+            let delay = bounce.delay_s * sample_rate;
+            tap.lp_alpha = if bounce.cutoff_hz.is_finite() {
+                crate::lowpass(bounce.cutoff_hz, sample_rate)
+            } else {
+                1.0
+            };
+            if delay > max_delay {
+                tap.target_gain = 0.0;
+                tap.target_delay = max_delay;
+            } else {
+                tap.target_gain = bounce.gain;
+                tap.target_delay = delay.max(1.0);
+            }
+            if newly_active {
+                tap.delay = tap.target_delay;
+                tap.gain = tap.target_gain;
+            }
+        }
+    }
+
+    // Most the dry input plus every bounce can sum to, relative to the dry input alone.
+    pub(crate) fn peak_gain(&self) -> f32 {
+        let taps = &self.taps[..self.active_taps];
+        1.0 + taps.iter().map(|t| t.gain.abs().max(t.target_gain.abs())).sum::<f32>()
+    }
+
+    // Returns the dry input plus every active bounce. Tap gains glide by `glide` a sample.
+    #[inline]
+    pub(crate) fn process(&mut self, input: f32, glide: f32) -> f32 {
+        if self.active_taps == 0 {
+            return input;
+        }
+
+        let len = self.buffer.len();
+        self.buffer[self.write] = input;
+        let mut out = input;
+        for tap in &mut self.taps[..self.active_taps] {
+            // Glide geometry changes so moving contacts bend pitch instead of clicking, the
+            // delays four times slower than the gains.
+            tap.delay += 0.25 * glide * (tap.target_delay - tap.delay);
+            tap.gain = flush_denormal(tap.gain + glide * (tap.target_gain - tap.gain));
+            let read = self.write as f32 - tap.delay;
+            let read = if read < 0.0 { read + len as f32 } else { read };
+            let i0 = read as usize % len;
+            let i1 = (i0 + 1) % len;
+            let frac = read - read.floor();
+            let delayed = self.buffer[i0] + (self.buffer[i1] - self.buffer[i0]) * frac;
+            tap.lp = flush_denormal(tap.lp + tap.lp_alpha * (delayed - tap.lp));
+            out += tap.lp * tap.gain;
+        }
+        self.write = (self.write + 1) % len;
+        out
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.buffer)
+    }
+}
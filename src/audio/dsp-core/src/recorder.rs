@@ -0,0 +1,210 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+use crate::scenario::whole;
+use crate::{vec_bytes, DspError, DspResult, Param, MAX_VOICE_LIMIT};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Values per change in take_recording(): sample, change, voice id, detail, value.
+pub(crate) const RECORD_STRIDE: usize = 5;
+
+// Changes held between take_recording() calls; past this, further changes are not recorded.
+pub(crate) const MAX_RECORDED_CHANGES: usize = 262_144;
+
+// A recorded change. AddVoice carries the priority as its detail and the start delay in
+// samples as its value; SetParam the Param id and the value passed in; RemoveVoice neither.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordedChange {
+    AddVoice = 0,
+    RemoveVoice = 1,
+    SetParam = 2,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Change {
+    AddVoice { priority: u32, delay: u32 },
+    RemoveVoice,
+    SetParam { param: Param, value: f32 },
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Recorded {
+    // Samples since recording started; absolute on the graph clock once loaded for replay.
+    pub(crate) at: u64,
+    pub(crate) voice: u32,
+    pub(crate) change: Change,
+}
+
+// Host-driven voice changes logged against the graph clock.
+#[derive(Default)]
+pub(crate) struct Recorder {
+    armed: bool,
+    // Graph clock when recording started.
+    origin: u64,
+    log: Vec<Recorded>,
+}
+
+impl Recorder {
+    pub(crate) fn start(&mut self, clock: u64) {
+        self.armed = true;
+        self.origin = clock;
+        self.log.clear();
+    }
+
+    pub(crate) fn stop(&mut self) {
+        self.armed = false;
+    }
+
+    pub(crate) fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.log.len()
+    }
+
+    pub(crate) fn record(&mut self, clock: u64, voice: u32, change: Change) {
+        if self.armed && self.log.len() < MAX_RECORDED_CHANGES {
+            self.log.push(Recorded {
+                at: clock - self.origin,
+                voice,
+                change,
+            });
+        }
+    }
+
+    // Stops recording while the graph acts on its own behalf; hand the result to resume().
+    pub(crate) fn suspend(&mut self) -> bool {
+        core::mem::replace(&mut self.armed, false)
+    }
+
+    pub(crate) fn resume(&mut self, armed: bool) {
+        self.armed = armed;
+    }
+
+    // Flattens and empties the log. Times keep counting from the start of the recording, so
+    // successive takes concatenate into one replayable log.
+    pub(crate) fn take(&mut self) -> Vec<f64> {
+        let mut out = Vec::with_capacity(self.log.len() * RECORD_STRIDE);
+        for r in self.log.drain(..) {
+            let (kind, detail, value) = match r.change {
+                Change::AddVoice { priority, delay } => {
+                    (RecordedChange::AddVoice, priority as f64, delay as f64)
+                }
+                Change::RemoveVoice => (RecordedChange::RemoveVoice, 0.0, 0.0),
+                Change::SetParam { param, value } => {
+                    (RecordedChange::SetParam, param as u32 as f64, value as f64)
+                }
+            };
+            out.extend_from_slice(&[r.at as f64, kind as u32 as f64, r.voice as f64, detail, value]);
+        }
+        out
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.log)
+    }
+}
+
+// A recording being played back into the graph.
+#[derive(Default)]
+pub(crate) struct Replay {
+    log: Vec<Recorded>,
+    // Index of the first change not yet applied.
+    next: usize,
+    // Recorded voice id to the id its replayed add got; ids never re-added map to themselves.
+    voices: Vec<u32>,
+}
+
+impl Replay {
+    // Replaces the playback with a recording whose times count from `clock`. A malformed
+    // change rejects the whole log and leaves the current playback in place.
+    pub(crate) fn load(&mut self, records: &[f64], clock: u64) -> DspResult<usize> {
+        if !records.len().is_multiple_of(RECORD_STRIDE) {
+            return Err(DspError::OutOfRange);
+        }
+        let mut log = Vec::with_capacity(records.len() / RECORD_STRIDE);
+        for record in records.chunks_exact(RECORD_STRIDE) {
+            let mut r = parse(record)?;
+            r.at = clock.saturating_add(r.at);
+            log.push(r);
+        }
+        log.sort_by_key(|r| r.at);
+        self.log = log;
+        self.next = 0;
+        self.voices.clear();
+        Ok(self.log.len())
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.log.clear();
+        self.next = 0;
+        self.voices.clear();
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.log.len() - self.next
+    }
+
+    // Moves pending changes so each still lands the same time from now at the new rate.
+    pub(crate) fn rescale(&mut self, clock: u64, ratio: f32) {
+        for r in &mut self.log[self.next..] {
+            r.at = clock + ((r.at - clock) as f64 * ratio as f64).round() as u64;
+        }
+    }
+
+    // Takes the next change landing before `end`, if any.
+    pub(crate) fn next_due(&mut self, end: u64) -> Option<Recorded> {
+        let r = *self.log.get(self.next).filter(|r| r.at < end)?;
+        self.next += 1;
+        Some(r)
+    }
+
+    pub(crate) fn voice(&self, recorded: u32) -> u32 {
+        self.voices.get(recorded as usize).copied().unwrap_or(recorded)
+    }
+
+    pub(crate) fn bind(&mut self, recorded: u32, live: u32) {
+        let len = self.voices.len() as u32;
+        if recorded >= len {
+            self.voices.extend(len..=recorded);
+        }
+        self.voices[recorded as usize] = live;
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.log) + vec_bytes(&self.voices)
+    }
+}
+
+fn parse(record: &[f64]) -> DspResult<Recorded> {
+    let at = whole(record[0]).ok_or(DspError::OutOfRange)?;
+    let voice = whole(record[2])
+        .filter(|&v| v < MAX_VOICE_LIMIT as u64)
+        .ok_or(DspError::InvalidVoice)? as u32;
+    let whole_u32 = |value: f64| whole(value).and_then(|v| u32::try_from(v).ok());
+    let change = match whole_u32(record[1]) {
+        Some(0) => {
+            let priority = whole_u32(record[3]).ok_or(DspError::OutOfRange)?;
+            let delay = whole_u32(record[4]).ok_or(DspError::OutOfRange)?;
+            Change::AddVoice { priority, delay }
+        }
+        Some(1) => Change::RemoveVoice,
+        Some(2) => {
+            let param = whole_u32(record[3])
+                .and_then(Param::from_id)
+                .ok_or(DspError::InvalidParam)?;
+            let value = record[4] as f32;
+            if !param.accepts(value) {
+                return Err(DspError::OutOfRange);
+            }
+            Change::SetParam { param, value }
+        }
+        _ => return Err(DspError::OutOfRange),
+    };
+    Ok(Recorded { at, voice, change })
+}
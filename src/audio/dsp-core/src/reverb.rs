@@ -0,0 +1,169 @@
+use alloc::{vec, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{clamp, flush_denormal, simd, vec_bytes};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+const LINES: usize = 8;
+// Mutually prime-ish line lengths, stretched for the long, smeared tails of a water column.
+const LINE_MS: [f32; LINES] = [47.5, 55.7, 61.9, 65.9, 79.9, 89.9, 100.7, 110.5];
+// A sand bottom's damping, which leaves the configured damping as it is.
+const NEUTRAL_BOTTOM_DAMPING: f32 = 0.55;
+
+// Eight-line feedback delay network with a Hadamard mixing matrix. Each line carries a
+// one-pole damping filter so high frequencies die well before the low-frequency body.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Reverb {
+    lines: Vec<Vec<f32>>,
+    pos: [usize; LINES],
+    feedback: [f32; LINES],
+    damp_state: [f32; LINES],
+    damping: f32,
+    decay_s: f32,
+    // Seabed influence on top of the configured decay/damping.
+    bottom_decay_scale: f32,
+    bottom_damping: f32,
+    // Water-column depth influence on the tail length.
+    depth_scale: f32,
+    return_level: f32,
+    sample_rate: f32,
+    tail_left: u32,
+}
+
+impl Reverb {
+    pub(crate) fn new(sample_rate: f32) -> Self {
+        let mut reverb = Self {
+            lines: Self::allocate_lines(sample_rate),
+            pos: [0; LINES],
+            feedback: [0.0; LINES],
+            damp_state: [0.0; LINES],
+            damping: 0.55,
+            decay_s: 4.5,
+            bottom_decay_scale: 1.0,
+            bottom_damping: NEUTRAL_BOTTOM_DAMPING,
+            depth_scale: 1.0,
+            return_level: 0.6,
+            sample_rate,
+            tail_left: 0,
+        };
+        reverb.refresh_feedback();
+        reverb
+    }
+
+    // Rebuilds the delay lines at their nominal lengths for the new rate. The tail in flight is
+    // dropped; the settings are kept.
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.lines = Self::allocate_lines(sample_rate);
+        self.pos = [0; LINES];
+        self.damp_state = [0.0; LINES];
+        self.sample_rate = sample_rate;
+        self.tail_left = 0;
+        self.refresh_feedback();
+    }
+
+    // Drops the tail in flight.
+    pub(crate) fn clear(&mut self) {
+        for line in &mut self.lines {
+            line.fill(0.0);
+        }
+        self.pos = [0; LINES];
+        self.damp_state = [0.0; LINES];
+        self.tail_left = 0;
+    }
+
+    fn allocate_lines(sample_rate: f32) -> Vec<Vec<f32>> {
+        LINE_MS
+            .iter()
+            .map(|ms| vec![0.0; ((sample_rate * ms * 0.001) as usize).max(1)])
+            .collect()
+    }
+
+    fn refresh_feedback(&mut self) {
+        let decay = self.effective_decay();
+        for (gain, line) in self.feedback.iter_mut().zip(self.lines.iter()) {
+            let len_s = line.len() as f32 / self.sample_rate;
+            *gain = 10.0f32.powf(-3.0 * len_s / decay);
+        }
+    }
+
+    fn effective_decay(&self) -> f32 {
+        self.decay_s * self.bottom_decay_scale * self.depth_scale
+    }
+
+    // The seabed bends the configured damping rather than replacing it: soft mud swallows
+    // more of the top end, rock keeps it ringing. 0 stays 0 and the top stays near the top on
+    // any bottom, so the whole range set_reverb offers still means what it says.
+    fn effective_damping(&self) -> f32 {
+        let bend = self.bottom_damping.ln() / NEUTRAL_BOTTOM_DAMPING.ln();
+        clamp(self.damping.powf(bend), 0.0, 0.99)
+    }
+
+    pub(crate) fn set_bottom(&mut self, decay_scale: f32, damping: f32) {
+        self.bottom_decay_scale = decay_scale;
+        self.bottom_damping = damping;
+        self.refresh_feedback();
+    }
+
+    // Called every block as the water depth glides; ignores changes too small to hear so the
+    // feedback gains aren't recomputed constantly.
+    pub(crate) fn set_depth_scale(&mut self, scale: f32) {
+        if (scale - self.depth_scale).abs() > 0.01 * self.depth_scale {
+            self.depth_scale = scale;
+            self.refresh_feedback();
+        }
+    }
+
+    // `decay_s` is the low-frequency T60; `damping` (0-1) shortens the high end further.
+    pub(crate) fn configure(&mut self, decay_s: f32, damping: f32, return_level: f32) {
+        self.decay_s = clamp(decay_s, 0.1, 30.0);
+        self.damping = clamp(damping, 0.0, 0.99);
+        self.return_level = clamp(return_level, 0.0, 2.0);
+        self.refresh_feedback();
+    }
+
+    // Adds the wet return for `send` into `left` (and `right`, when non-empty, from a
+    // decorrelated tap pattern). Skips all work once the tail has died out.
+    pub(crate) fn process(&mut self, send: &[f32], left: &mut [f32], right: &mut [f32]) {
+        let has_input = send.iter().any(|s| *s != 0.0);
+        if has_input {
+            self.tail_left = (self.effective_decay() * 1.2 * self.sample_rate) as u32;
+        } else if self.tail_left == 0 {
+            return;
+        }
+
+        let lp = 1.0 - self.effective_damping();
+        let stereo = !right.is_empty();
+        for (n, input) in send.iter().enumerate() {
+            let mut taps = [0.0f32; LINES];
+            for (i, tap) in taps.iter_mut().enumerate() {
+                *tap = self.lines[i][self.pos[i]];
+            }
+
+            let mut wet_l = 0.0;
+            let mut wet_r = 0.0;
+            for (i, t) in taps.iter().enumerate() {
+                wet_l += if i % 2 == 0 { *t } else { -*t };
+                wet_r += if i & 2 == 0 { *t } else { -*t };
+            }
+            left[n] += wet_l * 0.25 * self.return_level;
+            if stereo {
+                right[n] += wet_r * 0.25 * self.return_level;
+            }
+
+            simd::fdn(&mut taps, &mut self.damp_state, &self.feedback, lp);
+            self.damp_state = self.damp_state.map(flush_denormal);
+            for (i, line) in self.lines.iter_mut().enumerate() {
+                line[self.pos[i]] = self.damp_state[i] + input * 0.35;
+                self.pos[i] = (self.pos[i] + 1) % line.len();
+            }
+        }
+
+        self.tail_left = self.tail_left.saturating_sub(send.len() as u32);
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.lines) + self.lines.iter().map(vec_bytes).sum::<usize>()
+    }
+}
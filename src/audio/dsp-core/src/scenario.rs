@@ -0,0 +1,183 @@
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+use crate::presets::preset;
+use crate::{vec_bytes, DspError, DspResult, Param};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Upper bound on steps in one loaded scenario.
+pub(crate) const MAX_SCENARIO_STEPS: usize = 4096;
+
+// Values per step in load_scenario(): time (s), action, voice tag, then three arguments.
+pub(crate) const SCENARIO_STRIDE: usize = 6;
+
+// Largest integer an f64 record field carries exactly.
+const MAX_WHOLE: f64 = 9_007_199_254_740_992.0;
+
+// What a scenario step does. Arguments by action: AddVoice takes a preset id (-1 for none)
+// and a priority; SetParam a Param id, the value and a ramp in ms (negative for the
+// parameter's configured ramp); TriggerBio and RemoveVoice take none.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScenarioAction {
+    AddVoice = 0,
+    SetParam = 1,
+    TriggerBio = 2,
+    RemoveVoice = 3,
+}
+
+impl ScenarioAction {
+    fn from_id(id: u64) -> Option<Self> {
+        match id {
+            0 => Some(Self::AddVoice),
+            1 => Some(Self::SetParam),
+            2 => Some(Self::TriggerBio),
+            3 => Some(Self::RemoveVoice),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum Step {
+    AddVoice { preset: Option<u32>, priority: u32 },
+    SetParam { param: Param, value: f32, ramp_ms: f32 },
+    TriggerBio,
+    RemoveVoice,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct TimedStep {
+    // Absolute sample index on the graph clock.
+    pub(crate) at: u64,
+    // Voice the step addresses; for AddVoice, the tag the new voice gets.
+    pub(crate) tag: u64,
+    pub(crate) step: Step,
+}
+
+// A loaded mission script, run by process() in time order. Steps sharing a sample keep the
+// order they were loaded in.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Scenario {
+    steps: Vec<TimedStep>,
+    // Index of the first step not yet run.
+    next: usize,
+}
+
+impl Scenario {
+    pub(crate) fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            next: 0,
+        }
+    }
+
+    // Replaces the script with packed records whose times count from `clock`. A malformed
+    // record rejects the whole load and leaves the current script in place.
+    pub(crate) fn load(&mut self, records: &[f64], clock: u64, sample_rate: f32) -> DspResult<usize> {
+        let ok = records.len().is_multiple_of(SCENARIO_STRIDE)
+            && records.len() / SCENARIO_STRIDE <= MAX_SCENARIO_STEPS;
+        if !ok {
+            return Err(DspError::OutOfRange);
+        }
+        let mut steps = Vec::with_capacity(records.len() / SCENARIO_STRIDE);
+        for record in records.chunks_exact(SCENARIO_STRIDE) {
+            steps.push(parse(record, clock, sample_rate)?);
+        }
+        steps.sort_by_key(|s| s.at);
+        self.steps = steps;
+        self.next = 0;
+        Ok(self.steps.len())
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.steps.clear();
+        self.next = 0;
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.steps.len() - self.next
+    }
+
+    // Moves pending steps so each still lands the same time from now at the new rate.
+    pub(crate) fn rescale(&mut self, clock: u64, ratio: f32) {
+        for step in &mut self.steps[self.next..] {
+            step.at = clock + ((step.at - clock) as f64 * ratio as f64).round() as u64;
+        }
+    }
+
+    // Takes the next step landing before `end`, if any.
+    pub(crate) fn next_due(&mut self, end: u64) -> Option<TimedStep> {
+        let step = *self.steps.get(self.next).filter(|s| s.at < end)?;
+        self.next += 1;
+        if self.next == self.steps.len() {
+            self.clear();
+        }
+        Some(step)
+    }
+
+    pub(crate) fn heap_bytes(&self) -> usize {
+        vec_bytes(&self.steps)
+    }
+}
+
+pub(crate) fn whole(value: f64) -> Option<u64> {
+    let ok = value.is_finite() && value >= 0.0 && value.fract() == 0.0 && value <= MAX_WHOLE;
+    ok.then_some(value as u64)
+}
+
+fn parse(record: &[f64], clock: u64, sample_rate: f32) -> DspResult<TimedStep> {
+    let time = record[0];
+    if !(time.is_finite() && time >= 0.0) {
+        return Err(DspError::OutOfRange);
+    }
+    let action = whole(record[1])
+        .and_then(ScenarioAction::from_id)
+        .ok_or(DspError::OutOfRange)?;
+    let tag = whole(record[2]).ok_or(DspError::OutOfRange)?;
+    let step = match action {
+        ScenarioAction::AddVoice => {
+            let preset = if record[3] < 0.0 {
+                None
+            } else {
+                let id = whole(record[3]).and_then(|id| u32::try_from(id).ok());
+                Some(id.filter(|&id| preset(id).is_some()).ok_or(DspError::OutOfRange)?)
+            };
+            let priority = whole(record[4])
+                .and_then(|p| u32::try_from(p).ok())
+                .ok_or(DspError::OutOfRange)?;
+            Step::AddVoice { preset, priority }
+        }
+        ScenarioAction::SetParam => {
+            let param = whole(record[3])
+                .and_then(|id| u32::try_from(id).ok())
+                .and_then(Param::from_id)
+                .ok_or(DspError::InvalidParam)?;
+            let (value, ramp_ms) = (record[4] as f32, record[5] as f32);
+            if !param.accepts(value) || ramp_ms.is_nan() {
+                return Err(DspError::OutOfRange);
+            }
+            Step::SetParam {
+                param,
+                value,
+                ramp_ms,
+            }
+        }
+        ScenarioAction::TriggerBio => Step::TriggerBio,
+        ScenarioAction::RemoveVoice => Step::RemoveVoice,
+    };
+    // Tag 0 marks an untagged voice, so only AddVoice may use it.
+    if tag == 0 && action != ScenarioAction::AddVoice {
+        return Err(DspError::OutOfRange);
+    }
+    let offset = (time * sample_rate as f64).round() as u64;
+    Ok(TimedStep {
+        at: clock.saturating_add(offset),
+        tag,
+        step,
+    })
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use crate::phase::Phase;
+use crate::sine::{cosine, sine};
+use crate::{clamp, flush_denormal, rand_signed, TWO_PI};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+const MS_TO_KNOTS: f32 = 1.943_844;
+const OWN_BLADES: f32 = 7.0;
+
+// Own-ship radiated and flow noise as heard on our own hydrophones. Flow noise grows
+// steeply with speed, the propeller adds blade-rate tonals, and running the plant harder adds
+// machinery lines, so a fast boat buries quiet contacts under its own signature.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct SelfNoise {
+    level: f32,
+    machinery: f32,
+    rng: u32,
+    flow_lp_a: f32,
+    flow_lp_b: f32,
+    shaft_phase: Phase,
+    hum_phase: Phase,
+    pump_phase: Phase,
+    cav_env: f32,
+    #[serde(with = "crate::snapshot::silent_db")]
+    broadband_db: f32,
+}
+
+impl SelfNoise {
+    pub(crate) fn new(seed: u32) -> Self {
+        Self {
+            level: 0.0,
+            machinery: 0.3,
+            rng: seed | 1,
+            flow_lp_a: 0.0,
+            flow_lp_b: 0.0,
+            shaft_phase: Phase::default(),
+            hum_phase: Phase::default(),
+            pump_phase: Phase::default(),
+            cav_env: 0.0,
+            broadband_db: f32::NEG_INFINITY,
+        }
+    }
+
+    pub(crate) fn reseed(&mut self, seed: u32) {
+        self.rng = seed | 1;
+    }
+
+    pub(crate) fn configure(&mut self, level: f32, machinery: f32) {
+        self.level = clamp(level, 0.0, 2.0);
+        self.machinery = clamp(machinery, 0.0, 1.0);
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.level > 0.0
+    }
+
+    // Approximate broadband self-noise level (dB re full scale) from the last block.
+    pub(crate) fn broadband_db(&self) -> f32 {
+        self.broadband_db
+    }
+
+    // Renders one block of self-noise into `out` for the given own-ship speed (m/s) and depth.
+    pub(crate) fn render(&mut self, out: &mut [f32], speed_ms: f32, depth_m: f32, sample_rate: f32) {
+        let knots = speed_ms.abs() * MS_TO_KNOTS;
+        let speed_norm = clamp(knots / 30.0, 0.0, 1.5);
+        let flow_amp = speed_norm * speed_norm * speed_norm.sqrt() * 0.5;
+        let tonal_amp = speed_norm * speed_norm.sqrt() * 0.08;
+        let hum_amp = 0.01 + 0.03 * self.machinery;
+        // Propeller cavitation inception deepens with depth pressure.
+        let inception_kn = 8.0 + depth_m.max(0.0) * 0.12;
+        let cav_drive = clamp((knots - inception_kn) / 10.0, 0.0, 1.0);
+
+        let shaft_hz = knots * 0.25;
+        let hum_hz = 60.0;
+        let pump_hz = 23.0 + 9.0 * self.machinery;
+
+        let mut energy = 0.0f32;
+        for sample in out.iter_mut() {
+            let white = rand_signed(&mut self.rng);
+            self.flow_lp_a += 0.02 * (white - self.flow_lp_a);
+            self.flow_lp_b += 0.02 * (self.flow_lp_a - self.flow_lp_b);
+            let flow = self.flow_lp_b * 12.0 * flow_amp;
+
+            self.shaft_phase.advance(shaft_hz, sample_rate);
+            let blade_phase = self.shaft_phase.radians() * OWN_BLADES;
+            let tonal = (sine(blade_phase) * 0.7 + sine(2.0 * blade_phase) * 0.2) * tonal_amp;
+
+            self.hum_phase.advance(hum_hz, sample_rate);
+            self.pump_phase.advance(pump_hz, sample_rate);
+            let machinery = (sine(self.hum_phase.radians()) * 0.6
+                + sine(2.0 * self.hum_phase.radians()) * 0.25
+                + sine(self.pump_phase.radians()) * 0.4 * self.machinery)
+                * hum_amp;
+
+            let mut cav = 0.0;
+            if cav_drive > 0.0 {
+                let pulse = (0.5 + 0.5 * cosine(blade_phase % TWO_PI)).powf(8.0);
+                let target = pulse * cav_drive;
+                self.cav_env = flush_denormal(self.cav_env + 0.01 * (target - self.cav_env));
+                cav = (white - self.flow_lp_a) * self.cav_env * 0.6;
+            }
+
+            let s = (flow + tonal + machinery + cav) * self.level;
+            energy += s * s;
+            *sample += s;
+        }
+
+        let mean = energy / out.len().max(1) as f32;
+        self.broadband_db = if mean > 0.0 {
+            10.0 * mean.log10()
+        } else {
+            f32::NEG_INFINITY
+        };
+    }
+}
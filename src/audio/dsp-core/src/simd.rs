@@ -0,0 +1,308 @@
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Kernels for the hot mix, filter and gain loops. With the `simd` feature, a wasm32 build that
+// enables simd128 runs them four lanes at a time through core::arch::wasm32; every other build
+// gets the plain loops. Each lane does the same f32 operations in the same order as the scalar
+// code, so both builds render bit-identical output.
+
+// Adds `src` into `out`, sample for sample.
+#[inline]
+pub(crate) fn add(out: &mut [f32], src: &[f32]) {
+    let n = out.len().min(src.len());
+    let done = lanes::add(&mut out[..n], &src[..n]);
+    for (o, s) in out[done..n].iter_mut().zip(&src[done..n]) {
+        *o += s;
+    }
+}
+
+// Scales sample i by `start + delta * (i + 1)`: a linear gain ramp ending on the block's last
+// sample.
+#[inline]
+pub(crate) fn ramp(samples: &mut [f32], start: f32, delta: f32) {
+    let done = lanes::ramp(samples, start, delta);
+    for (i, sample) in samples.iter_mut().enumerate().skip(done) {
+        *sample *= start + delta * (i + 1) as f32;
+    }
+}
+
+// Glides each speaker gain toward its target by `glide` and adds `input` at that gain into the
+// speaker's channel of a planar output whose channels are `stride` apart.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pan(
+    gains: &mut [f32],
+    target: &[f32],
+    glide: f32,
+    input: f32,
+    out: &mut [f32],
+    stride: usize,
+    index: usize,
+    channels: usize,
+) {
+    let done = lanes::pan(gains, target, glide, input, out, stride, index, channels);
+    for k in done..channels {
+        gains[k] += glide * (target[k] - gains[k]);
+        out[k * stride + index] += input * gains[k];
+    }
+}
+
+// One FDN feedback step: an orthonormal Walsh-Hadamard mix of `taps`, then each line's decay
+// gain and one-pole damping into `damp`.
+#[inline]
+pub(crate) fn fdn(taps: &mut [f32; 8], damp: &mut [f32; 8], feedback: &[f32; 8], lp: f32) {
+    if lanes::fdn(taps, damp, feedback, lp) {
+        return;
+    }
+    let mut h = 1;
+    while h < 8 {
+        let mut i = 0;
+        while i < 8 {
+            for j in i..i + h {
+                let a = taps[j];
+                let b = taps[j + h];
+                taps[j] = a + b;
+                taps[j + h] = a - b;
+            }
+            i += h * 2;
+        }
+        h *= 2;
+    }
+    let norm = 1.0 / 8.0f32.sqrt();
+    taps.iter_mut().for_each(|x| *x *= norm);
+    for i in 0..8 {
+        damp[i] += lp * (taps[i] * feedback[i] - damp[i]);
+    }
+}
+
+// Runs a trapezoidal SVF high-pass into a low-pass, both with damping `k`, over the first
+// `frames` samples of each channel; `state` holds each channel's high-pass ic1, ic2, then
+// low-pass ic1, ic2.
+#[inline]
+pub(crate) fn band(
+    out: &mut [f32],
+    stride: usize,
+    frames: usize,
+    state: &mut [[f32; 4]],
+    high: [f32; 3],
+    low: [f32; 3],
+    k: f32,
+) {
+    let done = lanes::band(out, stride, frames, state, high, low, k);
+    let ([h1, h2, h3], [l1, l2, l3]) = (high, low);
+    for (c, state) in state.iter_mut().enumerate().skip(done) {
+        let [hc1, hc2, lc1, lc2] = state;
+        for x in &mut out[c * stride..c * stride + frames] {
+            let v3 = *x - *hc2;
+            let v1 = h1 * *hc1 + h2 * v3;
+            let v2 = *hc2 + h2 * *hc1 + h3 * v3;
+            *hc1 = 2.0 * v1 - *hc1;
+            *hc2 = 2.0 * v2 - *hc2;
+            let hp = *x - k * v1 - v2;
+
+            let v3 = hp - *lc2;
+            let v1 = l1 * *lc1 + l2 * v3;
+            let v2 = *lc2 + l2 * *lc1 + l3 * v3;
+            *lc1 = 2.0 * v1 - *lc1;
+            *lc2 = 2.0 * v2 - *lc2;
+            *x = v2;
+        }
+    }
+}
+
+// The vector halves of the kernels above. Each returns how much of the work it did (samples,
+// speakers or channels, always a multiple of four), leaving the rest to the scalar loop.
+#[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+mod lanes {
+    use core::arch::wasm32::*;
+
+    #[inline]
+    fn load(s: &[f32]) -> v128 {
+        f32x4(s[0], s[1], s[2], s[3])
+    }
+
+    #[inline]
+    fn store(s: &mut [f32], v: v128) {
+        s[0] = f32x4_extract_lane::<0>(v);
+        s[1] = f32x4_extract_lane::<1>(v);
+        s[2] = f32x4_extract_lane::<2>(v);
+        s[3] = f32x4_extract_lane::<3>(v);
+    }
+
+    pub(super) fn add(out: &mut [f32], src: &[f32]) -> usize {
+        let mut done = 0;
+        for (o, s) in out.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+            store(o, f32x4_add(load(o), load(s)));
+            done += 4;
+        }
+        done
+    }
+
+    pub(super) fn ramp(samples: &mut [f32], start: f32, delta: f32) -> usize {
+        let (start, delta) = (f32x4_splat(start), f32x4_splat(delta));
+        let mut index = f32x4(1.0, 2.0, 3.0, 4.0);
+        let mut done = 0;
+        for chunk in samples.chunks_exact_mut(4) {
+            let gain = f32x4_add(start, f32x4_mul(delta, index));
+            store(chunk, f32x4_mul(load(chunk), gain));
+            index = f32x4_add(index, f32x4_splat(4.0));
+            done += 4;
+        }
+        done
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn pan(
+        gains: &mut [f32],
+        target: &[f32],
+        glide: f32,
+        input: f32,
+        out: &mut [f32],
+        stride: usize,
+        index: usize,
+        channels: usize,
+    ) -> usize {
+        let (rate, input) = (f32x4_splat(glide), f32x4_splat(input));
+        let mut k = 0;
+        while k + 4 <= channels {
+            let g = load(&gains[k..]);
+            let g = f32x4_add(g, f32x4_mul(rate, f32x4_sub(load(&target[k..]), g)));
+            store(&mut gains[k..], g);
+            let wet = f32x4_mul(input, g);
+            out[k * stride + index] += f32x4_extract_lane::<0>(wet);
+            out[(k + 1) * stride + index] += f32x4_extract_lane::<1>(wet);
+            out[(k + 2) * stride + index] += f32x4_extract_lane::<2>(wet);
+            out[(k + 3) * stride + index] += f32x4_extract_lane::<3>(wet);
+            k += 4;
+        }
+        k
+    }
+
+    // Butterfly between lanes `h` apart within one vector (h = 1 or 2).
+    #[inline]
+    fn butterfly(v: v128, h: usize) -> v128 {
+        if h == 1 {
+            let a = i32x4_shuffle::<0, 0, 2, 2>(v, v);
+            let b = i32x4_shuffle::<1, 1, 3, 3>(v, v);
+            i32x4_shuffle::<0, 5, 2, 7>(f32x4_add(a, b), f32x4_sub(a, b))
+        } else {
+            let a = i32x4_shuffle::<0, 1, 0, 1>(v, v);
+            let b = i32x4_shuffle::<2, 3, 2, 3>(v, v);
+            i32x4_shuffle::<0, 1, 6, 7>(f32x4_add(a, b), f32x4_sub(a, b))
+        }
+    }
+
+    pub(super) fn fdn(
+        taps: &mut [f32; 8],
+        damp: &mut [f32; 8],
+        feedback: &[f32; 8],
+        lp: f32,
+    ) -> bool {
+        let (lo, hi) = (butterfly(load(&taps[..4]), 1), butterfly(load(&taps[4..]), 1));
+        let (lo, hi) = (butterfly(lo, 2), butterfly(hi, 2));
+        let norm = f32x4_splat(1.0 / 8.0f32.sqrt());
+        let lp = f32x4_splat(lp);
+        let mixed = [f32x4_add(lo, hi), f32x4_sub(lo, hi)];
+        for (half, v) in mixed.into_iter().enumerate() {
+            let at = half * 4;
+            let v = f32x4_mul(v, norm);
+            store(&mut taps[at..at + 4], v);
+            let d = load(&damp[at..]);
+            let fed = f32x4_mul(v, load(&feedback[at..]));
+            store(&mut damp[at..at + 4], f32x4_add(d, f32x4_mul(lp, f32x4_sub(fed, d))));
+        }
+        true
+    }
+
+    #[inline]
+    fn svf(x: v128, ic1: &mut v128, ic2: &mut v128, [a1, a2, a3]: [v128; 3]) -> (v128, v128) {
+        let two = f32x4_splat(2.0);
+        let v3 = f32x4_sub(x, *ic2);
+        let v1 = f32x4_add(f32x4_mul(a1, *ic1), f32x4_mul(a2, v3));
+        let v2 = f32x4_add(f32x4_add(*ic2, f32x4_mul(a2, *ic1)), f32x4_mul(a3, v3));
+        *ic1 = f32x4_sub(f32x4_mul(two, v1), *ic1);
+        *ic2 = f32x4_sub(f32x4_mul(two, v2), *ic2);
+        (v1, v2)
+    }
+
+    // Runs four channels side by side, one per lane.
+    pub(super) fn band(
+        out: &mut [f32],
+        stride: usize,
+        frames: usize,
+        state: &mut [[f32; 4]],
+        high: [f32; 3],
+        low: [f32; 3],
+        k: f32,
+    ) -> usize {
+        let high = high.map(|c| f32x4_splat(c));
+        let low = low.map(|c| f32x4_splat(c));
+        let k = f32x4_splat(k);
+        let mut done = 0;
+        for quad in state.chunks_exact_mut(4) {
+            let lane = |n: usize| f32x4(quad[0][n], quad[1][n], quad[2][n], quad[3][n]);
+            let (mut hc1, mut hc2, mut lc1, mut lc2) = (lane(0), lane(1), lane(2), lane(3));
+            let base = done * stride;
+            for i in 0..frames {
+                let at = |c: usize| base + c * stride + i;
+                let x = f32x4(out[at(0)], out[at(1)], out[at(2)], out[at(3)]);
+                let (v1, v2) = svf(x, &mut hc1, &mut hc2, high);
+                let hp = f32x4_sub(f32x4_sub(x, f32x4_mul(k, v1)), v2);
+                let (_, y) = svf(hp, &mut lc1, &mut lc2, low);
+                out[at(0)] = f32x4_extract_lane::<0>(y);
+                out[at(1)] = f32x4_extract_lane::<1>(y);
+                out[at(2)] = f32x4_extract_lane::<2>(y);
+                out[at(3)] = f32x4_extract_lane::<3>(y);
+            }
+            for (n, v) in [hc1, hc2, lc1, lc2].into_iter().enumerate() {
+                quad[0][n] = f32x4_extract_lane::<0>(v);
+                quad[1][n] = f32x4_extract_lane::<1>(v);
+                quad[2][n] = f32x4_extract_lane::<2>(v);
+                quad[3][n] = f32x4_extract_lane::<3>(v);
+            }
+            done += 4;
+        }
+        done
+    }
+}
+
+#[cfg(not(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128")))]
+mod lanes {
+    pub(super) fn add(_: &mut [f32], _: &[f32]) -> usize {
+        0
+    }
+
+    pub(super) fn ramp(_: &mut [f32], _: f32, _: f32) -> usize {
+        0
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn pan(
+        _: &mut [f32],
+        _: &[f32],
+        _: f32,
+        _: f32,
+        _: &mut [f32],
+        _: usize,
+        _: usize,
+        _: usize,
+    ) -> usize {
+        0
+    }
+
+    pub(super) fn fdn(_: &mut [f32; 8], _: &mut [f32; 8], _: &[f32; 8], _: f32) -> bool {
+        false
+    }
+
+    pub(super) fn band(
+        _: &mut [f32],
+        _: usize,
+        _: usize,
+        _: &mut [[f32; 4]],
+        _: [f32; 3],
+        _: [f32; 3],
+        _: f32,
+    ) -> usize {
+        0
+    }
+}
@@ -0,0 +1,41 @@
+use core::f32::consts::{FRAC_PI_2, PI};
+
+use crate::TWO_PI;
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Odd minimax fit of sin on [-π/2, π/2]; the linear term rounds to exactly 1 in f32.
+const C3: f32 = -0.166_666_48;
+const C5: f32 = 0.008_332_9;
+const C7: f32 = -0.000_198_008_97;
+const C9: f32 = 2.590_488_5e-6;
+
+// Polynomial sine for the per-sample oscillators, in place of a libm call. Absolute error is
+// under 3e-7 for |x| up to 2π and grows by about 1.3e-7 per radian past that, from reducing x
+// to one period in f32: roughly the spacing of f32 values near x, so no coarser than the phase
+// itself. Peaks can overshoot ±1 by one f32 step.
+#[inline]
+pub(crate) fn sine(x: f32) -> f32 {
+    let x = fold(x);
+    let x2 = x * x;
+    x + x * x2 * (C3 + x2 * (C5 + x2 * (C7 + x2 * C9)))
+}
+
+#[inline]
+pub(crate) fn cosine(x: f32) -> f32 {
+    sine(x + FRAC_PI_2)
+}
+
+// Reduces x to one period, then folds the outer quarters back into [-π/2, π/2], about which
+// sin is symmetric.
+#[inline]
+pub(crate) fn fold(x: f32) -> f32 {
+    let x = x - TWO_PI * (x * (1.0 / TWO_PI)).round_ties_even();
+    if x > FRAC_PI_2 {
+        PI - x
+    } else if x < -FRAC_PI_2 {
+        -PI - x
+    } else {
+        x
+    }
+}
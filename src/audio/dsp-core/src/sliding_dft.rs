@@ -0,0 +1,215 @@
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use wasm_bindgen::prelude::*;
+
+use crate::error::ensure;
+use crate::{host_floats, vec_bytes, DspError, DspResult};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+const MIN_SIZE: usize = 16;
+const MAX_SIZE: usize = 65_536;
+
+// Sliding DFT for a live waterfall: keeps a chosen set of bins of a size-point transform over
+// the latest samples, updated as each sample arrives instead of transforming whole windows.
+// Each bin costs one complex multiply per sample, so tracking a band of interest costs a
+// fraction of the transforms it replaces, and the spectrum can be read after any push.
+// Magnitudes are Hann-windowed, applied across neighbouring bins, which are tracked too.
+#[cfg_attr(feature = "std", wasm_bindgen)]
+pub struct SlidingSpectrum {
+    size: usize,
+    // The last `size` samples, oldest at `pos`.
+    history: Vec<f32>,
+    pos: usize,
+    // Every bin updated per sample, sorted, and its running (unwindowed) DFT and per-sample
+    // rotation e^(j 2π k / size). In f64, so rounding doesn't build up over hours of use.
+    tracked: Vec<u32>,
+    re: Vec<f64>,
+    im: Vec<f64>,
+    step_re: Vec<f64>,
+    step_im: Vec<f64>,
+    // For each selected bin, where it and its two neighbours sit in `tracked`.
+    selected: Vec<[usize; 3]>,
+    magnitudes: Vec<f32>,
+    // push_frames() output, bin_count() magnitudes per frame.
+    frames: Vec<f32>,
+}
+
+#[cfg_attr(feature = "std", wasm_bindgen)]
+impl SlidingSpectrum {
+    // A transform over the last `size` samples (16-65536; need not be a power of two), so bin
+    // k sits at k * sample_rate / size. No bins are selected at first.
+    #[cfg_attr(feature = "std", wasm_bindgen(constructor))]
+    pub fn new(size: usize) -> Self {
+        let size = size.clamp(MIN_SIZE, MAX_SIZE);
+        Self {
+            size,
+            history: vec![0.0; size],
+            pos: 0,
+            tracked: Vec::new(),
+            re: Vec::new(),
+            im: Vec::new(),
+            step_re: Vec::new(),
+            step_im: Vec::new(),
+            selected: Vec::new(),
+            magnitudes: Vec::new(),
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // Chooses the bins to follow, each 0..=size/2, in the order magnitudes() reports them.
+    // Bins already followed carry on; new ones start from the samples already pushed, so
+    // changing the band never blanks the display. Fails with OutOfRange, keeping the old
+    // selection, if any bin is past size/2.
+    pub fn select_bins(&mut self, bins: &[u32]) -> DspResult {
+        let nyquist = (self.size / 2) as u32;
+        ensure(bins.iter().all(|&k| k <= nyquist), DspError::OutOfRange)?;
+        let size = self.size as u32;
+        let mut tracked: Vec<u32> = bins
+            .iter()
+            .flat_map(|&k| [(k + size - 1) % size, k, k + 1])
+            .collect();
+        tracked.sort_unstable();
+        tracked.dedup();
+
+        let (re, im) = tracked
+            .iter()
+            .map(|&k| match self.tracked.binary_search(&k) {
+                Ok(at) => (self.re[at], self.im[at]),
+                Err(_) => self.exact_bin(k),
+            })
+            .unzip();
+        let omega = core::f64::consts::TAU / self.size as f64;
+        let (step_im, step_re) = tracked.iter().map(|&k| (omega * k as f64).sin_cos()).unzip();
+        let at = |k: u32| tracked.partition_point(|&t| t < k);
+        self.selected = bins
+            .iter()
+            .map(|&k| [at((k + size - 1) % size), at(k), at(k + 1)])
+            .collect();
+        self.magnitudes = vec![0.0; bins.len()];
+        self.frames.clear();
+        self.tracked = tracked;
+        self.re = re;
+        self.im = im;
+        self.step_re = step_re;
+        self.step_im = step_im;
+        Ok(())
+    }
+
+    pub fn bin_count(&self) -> usize {
+        self.selected.len()
+    }
+
+    // Slides the window along `samples`, updating every followed bin at each one.
+    pub fn push(&mut self, samples: &[f32]) {
+        for &x in samples {
+            let delta = x as f64 - self.history[self.pos] as f64;
+            self.history[self.pos] = x;
+            self.pos = (self.pos + 1) % self.size;
+            let bins = self.re.iter_mut().zip(self.im.iter_mut());
+            for ((re, im), (&c, &s)) in bins.zip(self.step_re.iter().zip(&self.step_im)) {
+                let r = *re + delta;
+                *re = r * c - *im * s;
+                *im = r * s + *im * c;
+            }
+        }
+    }
+
+    // Pushes a whole recording in one call, taking the magnitudes after every `hop` samples:
+    // a spectrogram of the selected bins. Returns a pointer to frame_count() frames of
+    // bin_count() floats each, one after another, valid until the next call or selection.
+    // Samples past the last whole hop are pushed too. Fails with OutOfRange for a zero hop.
+    pub fn push_frames(&mut self, samples: &[f32], hop: usize) -> DspResult<usize> {
+        ensure(hop > 0, DspError::OutOfRange)?;
+        let bins = self.selected.len();
+        let mut frames = core::mem::take(&mut self.frames);
+        frames.clear();
+        frames.resize(samples.len() / hop * bins, 0.0);
+        let mut chunks = samples.chunks_exact(hop);
+        for (k, chunk) in chunks.by_ref().enumerate() {
+            self.push(chunk);
+            self.fill_magnitudes(&mut frames[k * bins..][..bins]);
+        }
+        self.push(chunks.remainder());
+        self.frames = frames;
+        Ok(self.frames.as_ptr() as usize)
+    }
+
+    // Frames the last push_frames() call produced.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len() / self.selected.len().max(1)
+    }
+
+    // Refreshes the magnitudes and returns a pointer to them in this module's memory:
+    // bin_count() floats in select_bins() order, valid until the next call or selection. A
+    // sine of amplitude 1 centred on a bin reads 1 there.
+    pub fn magnitudes(&mut self) -> usize {
+        let mut magnitudes = core::mem::take(&mut self.magnitudes);
+        self.fill_magnitudes(&mut magnitudes);
+        self.magnitudes = magnitudes;
+        self.magnitudes.as_ptr() as usize
+    }
+
+    /// As magnitudes(), but writes them to `ptr`, a host-owned region of this module's memory
+    /// (see alloc_f32_buffer) with room for `len` floats. Fails with OutOfRange for a null or
+    /// misaligned buffer, or one shorter than bin_count().
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must address `len` writable floats, such as a live alloc_f32_buffer block, that
+    /// nothing else reads or writes during the call.
+    pub unsafe fn magnitudes_into(&self, ptr: usize, len: usize) -> DspResult {
+        ensure(len >= self.selected.len(), DspError::OutOfRange)?;
+        // SAFETY: upheld by the caller, as above.
+        let out = unsafe { host_floats(ptr, len)? };
+        self.fill_magnitudes(&mut out[..self.selected.len()]);
+        Ok(())
+    }
+
+    // Bytes this spectrum holds, which grow with its size and the number of bins followed.
+    pub fn memory_bytes(&self) -> usize {
+        let running = [&self.re, &self.im, &self.step_re, &self.step_im];
+        core::mem::size_of::<Self>()
+            + vec_bytes(&self.history)
+            + vec_bytes(&self.tracked)
+            + running.into_iter().map(vec_bytes).sum::<usize>()
+            + vec_bytes(&self.selected)
+            + vec_bytes(&self.magnitudes)
+            + vec_bytes(&self.frames)
+    }
+
+    // Forgets every sample pushed, keeping the selection.
+    pub fn reset(&mut self) {
+        self.history.fill(0.0);
+        self.pos = 0;
+        self.re.fill(0.0);
+        self.im.fill(0.0);
+    }
+}
+
+impl SlidingSpectrum {
+    fn fill_magnitudes(&self, magnitudes: &mut [f32]) {
+        let scale = 4.0 / self.size as f64;
+        for (out, &[lower, k, upper]) in magnitudes.iter_mut().zip(&self.selected) {
+            let re = 0.5 * self.re[k] - 0.25 * (self.re[lower] + self.re[upper]);
+            let im = 0.5 * self.im[k] - 0.25 * (self.im[lower] + self.im[upper]);
+            *out = (re.hypot(im) * scale) as f32;
+        }
+    }
+
+    // Bin k of the DFT over the history as it stands, oldest sample first, which is what the
+    // running bins hold.
+    fn exact_bin(&self, k: u32) -> (f64, f64) {
+        let omega = -core::f64::consts::TAU * k as f64 / self.size as f64;
+        let oldest_first = self.history[self.pos..].iter().chain(&self.history[..self.pos]);
+        oldest_first.enumerate().fold((0.0, 0.0), |(re, im), (m, &x)| {
+            let (s, c) = (omega * m as f64).sin_cos();
+            (re + x as f64 * c, im + x as f64 * s)
+        })
+    }
+}
@@ -0,0 +1,178 @@
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::{voice_seeds, Param};
+
+// Bumped whenever a serialized field is renamed or changes meaning, with a step in upgrade()
+// to carry older snapshots across. New fields need neither: restore() fills whatever a
+// snapshot lacks from a fresh graph. Version 1 named parameters by raw id.
+pub(crate) const SNAPSHOT_VERSION: u32 = 4;
+
+#[derive(Serialize)]
+pub(crate) struct SnapshotRef<'a, T> {
+    pub(crate) version: u32,
+    pub(crate) graph: &'a T,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Snapshot<T> {
+    pub(crate) version: u32,
+    pub(crate) graph: T,
+}
+
+// Rewrites a snapshot's graph from `version` to the current layout in place. `template` is a
+// fresh graph at the restoring graph's rate; whatever the old layout lacks starts out as it
+// would there. False when the snapshot is from a newer build or can't be migrated.
+pub(crate) fn upgrade(graph: &mut Value, version: u32, template: &Value) -> bool {
+    if version == 0 || version > SNAPSHOT_VERSION {
+        return false;
+    }
+    if version < 2 && !name_params(graph) {
+        return false;
+    }
+    if version < 3 {
+        untagged_events(graph);
+    }
+    if version < 4 {
+        split_noise_streams(graph);
+    }
+    fill_missing(graph, template);
+    // Per-parameter tables grew as parameters were appended; new entries take the defaults.
+    extend(graph.get_mut("ramp_ms"), template.get("ramp_ms"));
+    let fresh_base = template.pointer("/voices/0/modulation/base");
+    if let Some(Value::Array(voices)) = graph.get_mut("voices") {
+        for voice in voices {
+            extend(voice.pointer_mut("/modulation/base"), fresh_base);
+        }
+    }
+    true
+}
+
+// Version 1 stored queued events and modulation routes against raw parameter ids.
+fn name_params(value: &mut Value) -> bool {
+    match value {
+        Value::Object(map) => {
+            if let Some(id) = map.remove("param_id") {
+                let param = id
+                    .as_u64()
+                    .and_then(|id| u32::try_from(id).ok())
+                    .and_then(Param::from_id)
+                    .and_then(|param| serde_json::to_value(param).ok());
+                let Some(param) = param else {
+                    return false;
+                };
+                map.insert("param".into(), param);
+            }
+            map.values_mut().all(name_params)
+        }
+        Value::Array(items) => items.iter_mut().all(name_params),
+        _ => true,
+    }
+}
+
+// Voice tags arrived during version 2; events logged before them belong to no tag. A fresh
+// graph's log is empty, so the fill-in below can't supply this.
+fn untagged_events(graph: &mut Value) {
+    if let Some(Value::Array(events)) = graph.pointer_mut("/events/events") {
+        for event in events.iter_mut().filter_map(Value::as_object_mut) {
+            event.entry("tag").or_insert(Value::from(0));
+        }
+    }
+}
+
+// Through version 3 a voice's generators all drew on the voice's own noise stream. Each now
+// has its own, taken from the voice's as a new voice takes them from its seed; filling them
+// from the template would give every voice the same noise.
+fn split_noise_streams(graph: &mut Value) {
+    if let Some(Value::Array(voices)) = graph.get_mut("voices") {
+        for voice in voices {
+            let Some(state) = voice.get("rng").and_then(Value::as_u64) else {
+                continue;
+            };
+            let [_, engine, cav, bio, ..] = voice_seeds(state as u32);
+            for (key, seed) in ["engine", "cav", "bio"].into_iter().zip([engine, cav, bio]) {
+                if let Some(Value::Object(generator)) = voice.get_mut(key) {
+                    generator.insert("rng".into(), Value::from(seed));
+                }
+            }
+        }
+    }
+}
+
+// Adds the fields a snapshot predates. Array elements fill from the template's first one, so
+// every saved voice gains the fields a fresh voice has.
+fn fill_missing(value: &mut Value, template: &Value) {
+    match (value, template) {
+        (Value::Object(map), Value::Object(fresh)) => {
+            for (key, default) in fresh {
+                match map.get_mut(key) {
+                    Some(field) => fill_missing(field, default),
+                    None => {
+                        map.insert(key.clone(), default.clone());
+                    }
+                }
+            }
+        }
+        (Value::Array(items), Value::Array(fresh)) => {
+            if let Some(default) = fresh.first() {
+                for item in items {
+                    fill_missing(item, default);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn extend(table: Option<&mut Value>, fresh: Option<&Value>) {
+    if let (Some(Value::Array(items)), Some(Value::Array(fresh))) = (table, fresh) {
+        if items.len() < fresh.len() {
+            items.extend_from_slice(&fresh[items.len()..]);
+        }
+    }
+}
+
+// serde only derives for arrays up to 32 elements; longer delay lines go through a sequence.
+pub(crate) mod big_array {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer, const N: usize>(values: &[f32; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter())
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[f32; N], D::Error> {
+        let values = Vec::<f32>::deserialize(deserializer)?;
+        let len = values.len();
+        values
+            .try_into()
+            .map_err(|_| serde::de::Error::invalid_length(len, &"a fixed-size delay line"))
+    }
+}
+
+// JSON has no infinity; an unbounded cutoff round-trips as null.
+pub(crate) mod unbounded {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        value.is_finite().then_some(*value).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        Ok(Option::<f32>::deserialize(deserializer)?.unwrap_or(f32::INFINITY))
+    }
+}
+
+// Likewise for a level that has not been measured yet.
+pub(crate) mod silent_db {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        value.is_finite().then_some(*value).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        Ok(Option::<f32>::deserialize(deserializer)?.unwrap_or(f32::NEG_INFINITY))
+    }
+}
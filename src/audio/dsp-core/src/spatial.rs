@@ -0,0 +1,419 @@
+use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{clamp, flush_denormal, rand_signed, simd, TWO_PI};
+#[cfg(not(feature = "std"))]
+use crate::math::Float;
+
+// Largest interaural delay for a source fully abeam (roughly a human head in air).
+const MAX_ITD_S: f32 = 0.00066;
+// Holds the ITD at sample rates up to 192 kHz.
+const ITD_BUF: usize = 128;
+
+// Constant-power gains for a bearing measured clockwise from dead ahead, in radians.
+// Front and back fold onto the same image, as they would on a single stereo pair.
+#[inline]
+pub(crate) fn pan_gains(bearing: f32) -> (f32, f32) {
+    let theta = (bearing.sin() + 1.0) * FRAC_PI_4;
+    (theta.cos(), theta.sin())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum ReceiverPattern {
+    Omni = 0,
+    // Flat forward coverage with a deaf cone astern around the screws.
+    Baffles = 1,
+    // Smooth front-to-back rolloff down to the floor.
+    Cardioid = 2,
+}
+
+impl ReceiverPattern {
+    pub(crate) fn from_id(id: u32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Omni),
+            1 => Some(Self::Baffles),
+            2 => Some(Self::Cardioid),
+            _ => None,
+        }
+    }
+}
+
+// Hull receiver directivity: how much a contact is heard as a function of its bearing from
+// the bow, applied before panning so a contact in the baffles really goes quiet.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Directivity {
+    pattern: ReceiverPattern,
+    half_width: f32,
+    floor: f32,
+}
+
+impl Directivity {
+    pub(crate) fn new() -> Self {
+        Self {
+            pattern: ReceiverPattern::Omni,
+            half_width: 0.0,
+            floor: 1.0,
+        }
+    }
+
+    pub(crate) fn configure(&mut self, pattern: ReceiverPattern, width_rad: f32, loss_db: f32) {
+        self.pattern = pattern;
+        self.half_width = clamp(width_rad, 0.0, TWO_PI) * 0.5;
+        self.floor = 10.0f32.powf(-clamp(loss_db, 0.0, 60.0) / 20.0);
+    }
+
+    // Linear gain for a relative bearing in radians, clockwise from the bow.
+    pub(crate) fn gain(&self, bearing: f32) -> f32 {
+        match self.pattern {
+            ReceiverPattern::Omni => 1.0,
+            ReceiverPattern::Baffles => {
+                // Off-stern angle; the edge of the cone softens over 20 degrees.
+                let from_stern = (bearing.rem_euclid(TWO_PI) - PI).abs();
+                let edge = 20.0f32.to_radians();
+                let t = clamp((self.half_width + edge - from_stern) / edge, 0.0, 1.0);
+                let blind = t * t * (3.0 - 2.0 * t);
+                1.0 + (self.floor - 1.0) * blind
+            }
+            ReceiverPattern::Cardioid => self.floor + (1.0 - self.floor) * (0.5 + 0.5 * bearing.cos()),
+        }
+    }
+}
+
+// Correlation times of the slow wander and the short-term jitter.
+const WANDER_TAU_S: f32 = 20.0;
+const JITTER_TAU_S: f32 = 0.15;
+
+// Measurement noise on a voice's apparent bearing: a slow Ornstein-Uhlenbeck wander (refraction,
+// platform motion) plus a fast jittery one (scintillation), each with a configurable RMS size.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct BearingNoise {
+    // RMS magnitudes in radians.
+    pub(crate) wander: f32,
+    pub(crate) jitter: f32,
+    wander_state: f32,
+    jitter_state: f32,
+    rng: u32,
+}
+
+impl BearingNoise {
+    pub(crate) fn new(seed: u32) -> Self {
+        Self {
+            wander: 0.0,
+            jitter: 0.0,
+            wander_state: 0.0,
+            jitter_state: 0.0,
+            rng: (seed ^ 0x6a09_e667) | 1,
+        }
+    }
+
+    pub(crate) fn reseed(&mut self, seed: u32) {
+        self.rng = (seed ^ 0x6a09_e667) | 1;
+    }
+
+    // Advances both processes by `dt` seconds and returns the bearing error in radians.
+    pub(crate) fn advance(&mut self, dt: f32) -> f32 {
+        if self.wander <= 0.0 && self.jitter <= 0.0 {
+            self.wander_state = 0.0;
+            self.jitter_state = 0.0;
+            return 0.0;
+        }
+        // Uniform draws scaled to unit variance are Gaussian enough at these time scales.
+        let step = |state: f32, tau: f32, rms: f32, rng: &mut u32| {
+            let a = (-dt / tau).exp();
+            let kick = rand_signed(rng) * 3.0f32.sqrt();
+            state * a + rms * (1.0 - a * a).sqrt() * kick
+        };
+        self.wander_state = step(self.wander_state, WANDER_TAU_S, self.wander, &mut self.rng);
+        self.jitter_state = step(self.jitter_state, JITTER_TAU_S, self.jitter, &mut self.rng);
+        self.wander_state + self.jitter_state
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Panner {
+    // Relative bearing in radians, clockwise from own-ship's bow.
+    pub(crate) bearing: f32,
+    // Measurement error added on top of the true bearing.
+    pub(crate) error: f32,
+    gain_l: f32,
+    gain_r: f32,
+    delay: f32,
+    #[serde(with = "crate::snapshot::big_array")]
+    history: [f32; ITD_BUF],
+    write: usize,
+}
+
+impl Panner {
+    pub(crate) fn new() -> Self {
+        Self {
+            bearing: 0.0,
+            error: 0.0,
+            gain_l: FRAC_PI_4.cos(),
+            gain_r: FRAC_PI_4.sin(),
+            delay: 0.0,
+            history: [0.0; ITD_BUF],
+            write: 0,
+        }
+    }
+
+    // Bearing the receiver perceives, including measurement error.
+    #[inline]
+    pub(crate) fn apparent_bearing(&self) -> f32 {
+        self.bearing + self.error
+    }
+
+    // Splits a mono voice into left/right. With `itd` the far ear hears a slightly late copy.
+    // Gains and delay glide toward the bearing's by `glide` a sample.
+    #[inline]
+    pub(crate) fn process(
+        &mut self,
+        input: f32,
+        itd: bool,
+        sample_rate: f32,
+        glide: f32,
+    ) -> (f32, f32) {
+        let bearing = self.apparent_bearing();
+        let (target_l, target_r) = pan_gains(bearing);
+        self.gain_l = flush_denormal(self.gain_l + glide * (target_l - self.gain_l));
+        self.gain_r = flush_denormal(self.gain_r + glide * (target_r - self.gain_r));
+
+        if !itd {
+            return (input * self.gain_l, input * self.gain_r);
+        }
+
+        let lateral = bearing.sin();
+        let target_delay = (MAX_ITD_S * lateral.abs() * sample_rate).min((ITD_BUF - 2) as f32);
+        self.delay += glide * (target_delay - self.delay);
+
+        self.history[self.write] = input;
+        let read = self.write as f32 - self.delay;
+        let read = if read < 0.0 { read + ITD_BUF as f32 } else { read };
+        let i0 = read as usize % ITD_BUF;
+        let i1 = (i0 + 1) % ITD_BUF;
+        let frac = read - read.floor();
+        let late = self.history[i0] + (self.history[i1] - self.history[i0]) * frac;
+        self.write = (self.write + 1) % ITD_BUF;
+
+        if lateral >= 0.0 {
+            (late * self.gain_l, input * self.gain_r)
+        } else {
+            (input * self.gain_l, late * self.gain_r)
+        }
+    }
+}
+
+pub(crate) const MAX_RING_CHANNELS: usize = 8;
+
+// Gains for a ring of equally spaced speakers, speaker 0 dead ahead and the rest clockwise.
+// A source feeds only the pair either side of it, constant-power between them.
+pub(crate) fn ring_gains(bearing: f32, channels: usize) -> [f32; MAX_RING_CHANNELS] {
+    let mut gains = [0.0; MAX_RING_CHANNELS];
+    let position = bearing.rem_euclid(TWO_PI) / TWO_PI * channels as f32;
+    let a = (position as usize) % channels;
+    let b = (a + 1) % channels;
+    let frac = position - position.floor();
+    gains[a] = (frac * FRAC_PI_2).cos();
+    gains[b] += (frac * FRAC_PI_2).sin();
+    gains
+}
+
+// Multichannel counterpart of Panner for speaker rings wider than a stereo pair.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct RingPanner {
+    gains: [f32; MAX_RING_CHANNELS],
+    target: [f32; MAX_RING_CHANNELS],
+}
+
+impl RingPanner {
+    pub(crate) fn new() -> Self {
+        Self {
+            gains: [0.0; MAX_RING_CHANNELS],
+            target: [0.0; MAX_RING_CHANNELS],
+        }
+    }
+
+    // Called once a block; the gains glide inside the pan kernel, so their tails toward zero
+    // are cut here rather than per sample.
+    pub(crate) fn steer(&mut self, bearing: f32, channels: usize) {
+        self.target = ring_gains(bearing, channels);
+        self.gains = self.gains.map(flush_denormal);
+    }
+
+    // Adds one sample to every speaker of a planar output whose channels are `stride` apart,
+    // gliding the gains by `glide`.
+    #[inline]
+    pub(crate) fn process(
+        &mut self,
+        input: f32,
+        glide: f32,
+        out: &mut [f32],
+        stride: usize,
+        index: usize,
+        channels: usize,
+    ) {
+        simd::pan(&mut self.gains, &self.target, glide, input, out, stride, index, channels);
+    }
+}
+
+// Spherical-head radius (m) and the speed of sound in air it is heard through.
+const HEAD_RADIUS: f32 = 0.0875;
+const AIR_SPEED: f32 = 343.0;
+const PINNA_BUF: usize = 64;
+
+// First-order head-shadow filter (Brown & Duda): a high shelf whose gain depends on how far
+// the source sits from this ear's axis.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct HeadShadow {
+    b0: f32,
+    b1: f32,
+    a1: f32,
+    x1: f32,
+    y1: f32,
+}
+
+impl HeadShadow {
+    fn new() -> Self {
+        Self {
+            b0: 1.0,
+            b1: 0.0,
+            a1: 0.0,
+            x1: 0.0,
+            y1: 0.0,
+        }
+    }
+
+    // `off_axis` is the angle between the source and the ear's outward axis, in radians.
+    fn steer(&mut self, off_axis: f32, sample_rate: f32) {
+        let alpha = 1.05 + 0.95 * (off_axis * 1.2).cos();
+        let beta = 2.0 * AIR_SPEED / HEAD_RADIUS;
+        let k = 2.0 * sample_rate;
+        let norm = 1.0 / (k + beta);
+        self.b0 = (alpha * k + beta) * norm;
+        self.b1 = (beta - alpha * k) * norm;
+        self.a1 = (beta - k) * norm;
+    }
+
+    #[inline]
+    fn process(&mut self, x: f32) -> f32 {
+        let y = flush_denormal(self.b0 * x + self.b1 * self.x1 - self.a1 * self.y1);
+        self.x1 = x;
+        self.y1 = y;
+        y
+    }
+}
+
+// HRTF-lite headphone rendering: Woodworth ITD, per-ear head shadow, a single pinna echo whose
+// delay moves with azimuth and elevation, and a gentle darkening of sources behind the head.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Binaural {
+    // Elevation above (+) or below (-) the horizontal, in radians.
+    pub(crate) elevation: f32,
+    shadow_l: HeadShadow,
+    shadow_r: HeadShadow,
+    itd: f32,
+    target_itd: f32,
+    pinna_delay: f32,
+    target_pinna: f32,
+    rear: f32,
+    target_rear: f32,
+    rear_lp: f32,
+    // Broadband level difference on top of the head shadow, as the right-ear gain; the left
+    // ear gets its reciprocal.
+    ild: f32,
+    target_ild: f32,
+    #[serde(with = "crate::snapshot::big_array")]
+    history: [f32; ITD_BUF],
+    #[serde(with = "crate::snapshot::big_array")]
+    pinna: [f32; PINNA_BUF],
+    write: usize,
+    primed: bool,
+}
+
+impl Binaural {
+    pub(crate) fn new() -> Self {
+        Self {
+            elevation: 0.0,
+            shadow_l: HeadShadow::new(),
+            shadow_r: HeadShadow::new(),
+            itd: 0.0,
+            target_itd: 0.0,
+            pinna_delay: 0.0,
+            target_pinna: 0.0,
+            rear: 0.0,
+            target_rear: 0.0,
+            rear_lp: 0.0,
+            ild: 1.0,
+            target_ild: 1.0,
+            history: [0.0; ITD_BUF],
+            pinna: [0.0; PINNA_BUF],
+            write: 0,
+            primed: false,
+        }
+    }
+
+    // Block-rate update for an azimuth clockwise from the listener's nose, in radians.
+    pub(crate) fn steer(&mut self, azimuth: f32, sample_rate: f32) {
+        let cos_el = self.elevation.cos();
+        let lateral = clamp(azimuth.sin() * cos_el, -1.0, 1.0);
+        self.shadow_r.steer(lateral.acos(), sample_rate);
+        self.shadow_l.steer((-lateral).acos(), sample_rate);
+
+        // Positive means the right ear leads.
+        let itd_s = HEAD_RADIUS / AIR_SPEED * (lateral.asin() + lateral);
+        self.target_itd = (itd_s * sample_rate).clamp(-((ITD_BUF - 2) as f32), (ITD_BUF - 2) as f32);
+        // The shadow filter barely acts below 1 kHz, where most of the ocean lives, so a
+        // mild broadband difference keeps low-frequency contacts lateralised.
+        self.target_ild = 10.0f32.powf(2.0 * lateral / 20.0);
+
+        let front = 0.5 + 0.5 * azimuth.cos() * cos_el;
+        let up = 0.5 + 0.5 * self.elevation.sin();
+        let pinna_s = 0.00005 + 0.00025 * front * (1.0 - 0.6 * up);
+        self.target_pinna = (pinna_s * sample_rate).min((PINNA_BUF - 2) as f32);
+        self.target_rear = 1.0 - front;
+
+        if !self.primed {
+            self.itd = self.target_itd;
+            self.pinna_delay = self.target_pinna;
+            self.rear = self.target_rear;
+            self.ild = self.target_ild;
+            self.primed = true;
+        }
+    }
+
+    #[inline]
+    fn read(buf: &[f32], write: usize, delay: f32) -> f32 {
+        let len = buf.len();
+        let read = write as f32 - delay;
+        let read = if read < 0.0 { read + len as f32 } else { read };
+        let i0 = read as usize % len;
+        let i1 = (i0 + 1) % len;
+        let frac = read - read.floor();
+        buf[i0] + (buf[i1] - buf[i0]) * frac
+    }
+
+    // Renders one sample, gliding toward the last steer() by `glide`.
+    #[inline]
+    pub(crate) fn process(&mut self, input: f32, glide: f32) -> (f32, f32) {
+        self.itd = flush_denormal(self.itd + glide * (self.target_itd - self.itd));
+        self.pinna_delay += glide * (self.target_pinna - self.pinna_delay);
+        self.rear = flush_denormal(self.rear + glide * (self.target_rear - self.rear));
+        self.ild += glide * (self.target_ild - self.ild);
+
+        // Sources behind lose some top end to the outer ear.
+        self.rear_lp = flush_denormal(self.rear_lp + 0.35 * (input - self.rear_lp));
+        let shaped = input + (self.rear_lp - input) * self.rear * 0.6;
+
+        let pinna_write = self.write % PINNA_BUF;
+        self.pinna[pinna_write] = shaped;
+        let echo = Self::read(&self.pinna, pinna_write, self.pinna_delay.max(1.0));
+        let direct = shaped - 0.4 * echo;
+
+        self.history[self.write] = direct;
+        let late = Self::read(&self.history, self.write, self.itd.abs());
+        self.write = (self.write + 1) % ITD_BUF;
+
+        let (left, right) = if self.itd >= 0.0 { (late, direct) } else { (direct, late) };
+        (self.shadow_l.process(left) / self.ild, self.shadow_r.process(right) * self.ild)
+    }
+}
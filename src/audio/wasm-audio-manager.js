@@ -123,6 +123,9 @@ export class WasmAudioManager {
         }
 
         if (data.type === 'VOICE_ADDED') {
+            if (data.voiceId < 0) {
+                console.warn('ADD_VOICE failed:', data.message);
+            }
             const resolver = this._addVoiceQueue.shift();
             if (resolver) {
                 resolver(data.voiceId);
@@ -62,7 +62,10 @@ class DspGraph {
     }
     add_voice() {
         const ret = wasm.dspgraph_add_voice(this.__wbg_ptr);
-        return ret;
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] >>> 0;
     }
     max_frames() {
         const ret = wasm.dspgraph_max_frames(this.__wbg_ptr);
@@ -106,11 +109,15 @@ class DspGraph {
     }
     remove_voice(voice_id) {
         const ret = wasm.dspgraph_remove_voice(this.__wbg_ptr, voice_id);
-        return ret !== 0;
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
     }
-    set_param(voice_id, param_id, value) {
-        const ret = wasm.dspgraph_set_param(this.__wbg_ptr, voice_id, param_id, value);
-        return ret !== 0;
+    set_param(voice_id, param, value) {
+        const ret = wasm.dspgraph_set_param(this.__wbg_ptr, voice_id, param, value);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
     }
 }
 if (Symbol.dispose) DspGraph.prototype[Symbol.dispose] = DspGraph.prototype.free;
@@ -121,6 +128,11 @@ function __wbg_get_imports() {
         __wbg___wbindgen_throw_be289d5034ed271b: function(arg0, arg1) {
             throw new Error(getStringFromWasm0(arg0, arg1));
         },
+        __wbindgen_cast_0000000000000001: function(arg0) {
+            // Cast intrinsic for `F64 -> Externref`.
+            const ret = arg0;
+            return ret;
+        },
         __wbindgen_init_externref_table: function() {
             const table = wasm.__wbindgen_externrefs;
             const offset = table.grow(4);
@@ -141,6 +153,19 @@ const DspGraphFinalization = (typeof FinalizationRegistry === 'undefined')
     ? { register: () => {}, unregister: () => {} }
     : new FinalizationRegistry(ptr => wasm.__wbg_dspgraph_free(ptr >>> 0, 1));
 
+function dsp_error_message(error) {
+    let deferred1_0;
+    let deferred1_1;
+    try {
+        const ret = wasm.dsp_error_message(error);
+        deferred1_0 = ret[0];
+        deferred1_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+}
+
 function getStringFromWasm0(ptr, len) {
     ptr = ptr >>> 0;
     return decodeText(ptr, len);
@@ -153,6 +178,12 @@ function getUint8ArrayMemory0() {
     return cachedUint8ArrayMemory0;
 }
 
+function takeFromExternrefTable0(idx) {
+    const value = wasm.__wbindgen_externrefs.get(idx);
+    wasm.__externref_table_dealloc(idx);
+    return value;
+}
+
 const MAX_SAFARI_DECODE_BYTES = 2146435072;
 let numBytesDecoded = 0;
 function decodeText(ptr, len) {
@@ -179,6 +210,14 @@ function initSync(module) {
 }
 // --- END INLINED dsp_core.js ---
 
+// Graph calls that return a DspResult throw the DspError's number. Turns one into the fields
+// a failure message carries; anything else (a Rust panic, a glue bug) is rethrown for
+// handleMessage to report as ERROR.
+function dspFailure(error) {
+    if (typeof error !== 'number') throw error;
+    return { error, message: dsp_error_message(error) };
+}
+
 class WasmEngineProcessor extends AudioWorkletProcessor {
     constructor() {
         super();
@@ -262,8 +301,7 @@ class WasmEngineProcessor extends AudioWorkletProcessor {
         this.graph = new DspGraph(sampleRate, maxFrames, maxVoices);
 
         if (data.autoAddVoice !== false) {
-            const voiceId = this.graph.add_voice();
-            if (voiceId >= 0) this.defaultVoiceId = voiceId;
+            this.defaultVoiceId = this.graph.add_voice();
         }
 
         this.ready = true;
@@ -277,21 +315,32 @@ class WasmEngineProcessor extends AudioWorkletProcessor {
         const paramId = Math.floor(data.paramId);
         const value = Number(data.value);
 
-        const ok = this.graph.set_param(voiceId, paramId, value);
-        if (!ok) this.port.postMessage({ type: 'SET_PARAM_FAILED', voiceId, paramId, value });
+        try {
+            this.graph.set_param(voiceId, paramId, value);
+        } catch (error) {
+            this.port.postMessage({ type: 'SET_PARAM_FAILED', voiceId, paramId, value, ...dspFailure(error) });
+        }
     }
 
     handleAddVoice() {
         if (!this.graph) return;
-        const voiceId = this.graph.add_voice();
-        this.port.postMessage({ type: 'VOICE_ADDED', voiceId });
+        try {
+            const voiceId = this.graph.add_voice();
+            this.port.postMessage({ type: 'VOICE_ADDED', voiceId });
+        } catch (error) {
+            this.port.postMessage({ type: 'VOICE_ADDED', voiceId: -1, ...dspFailure(error) });
+        }
     }
 
     handleRemoveVoice(data) {
         if (!this.graph) return;
         const voiceId = Math.floor(data.voiceId);
-        const ok = this.graph.remove_voice(voiceId);
-        this.port.postMessage({ type: 'VOICE_REMOVED', voiceId, ok });
+        try {
+            this.graph.remove_voice(voiceId);
+            this.port.postMessage({ type: 'VOICE_REMOVED', voiceId, ok: true });
+        } catch (error) {
+            this.port.postMessage({ type: 'VOICE_REMOVED', voiceId, ok: false, ...dspFailure(error) });
+        }
     }
 
     process(inputs, outputs) {